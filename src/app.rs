@@ -3,23 +3,54 @@ use crate::{
         Event, Storage,
         error::{AppResult, ErrorState},
         events::{AppEvent, EventHandler},
-        task::TaskList,
+        pomodoro::{Pomodoro, PomodoroPhase},
+        quick_add::QuickAdd,
+        reminders::{Notification, fire_due_reminders, run_reminder_command},
+        sync::git::AutoCommit,
+        task::{SmartView, Task, TaskLayout, TaskList, agenda::compare_for_display, task::Status},
         validation::TaskValidator,
     },
-    ui::forms::{date_input::DateInputMode, task_form::TaskForm},
+    ui::forms::{
+        date_input::{DateInputMode, navigate_calendar},
+        dependency_picker::{DependencyPicker, DependencyPickerAction},
+        quick_add::QuickAddForm,
+        reminder_list::{ReminderList, ReminderListAction},
+        snooze_picker::{SnoozePicker, SnoozePickerAction},
+        status_picker::{StatusPicker, StatusPickerAction},
+        task_form::TaskForm,
+        time_log::{TimeLog, TimeLogAction},
+    },
+    utils::{
+        config::Config,
+        date::{get_today_with_fallbacks, now_timestamp},
+    },
 };
 
 use ratatui::{
     DefaultTerminal,
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
 };
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    time::Instant,
+};
+use time::{Date, Duration, OffsetDateTime};
+use uuid::Uuid;
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum CurrentScreen {
     #[default]
     Normal,
     Create,
+    QuickAdd,
+    StatusPicker,
+    DependencyPicker,
+    TimeLog,
+    ReminderList,
+    Pomodoro,
+    SnoozePicker,
     Editing,
 }
 
@@ -32,9 +63,24 @@ pub struct App {
     pub events: EventHandler,
     pub current_screen: CurrentScreen,
     pub storage: Storage,
+    /// Commits saves to git when git sync is on.
+    pub auto_commit: Option<AutoCommit>,
     pub task_form: TaskForm,
+    pub quick_add: QuickAddForm,
+    pub status_picker: StatusPicker,
+    pub dependency_picker: DependencyPicker,
+    pub time_log: TimeLog,
+    pub reminder_list: ReminderList,
+    pub snooze_picker: SnoozePicker,
     pub error_state: ErrorState,
     pub show_delete_popup: bool,
+    /// A closing status for a task others depend on, waiting to be confirmed.
+    pub pending_close: Option<Status>,
+    /// Wall-clock time as of the last tick, used to show running timers.
+    pub now: OffsetDateTime,
+    pub pomodoro: Option<Pomodoro>,
+    /// The banner for reminders that just went off.
+    pub notification: Option<Notification>,
 }
 
 impl App {
@@ -43,6 +89,21 @@ impl App {
         let file_path = Self::initialize_storage()?;
         let storage = Storage::new(file_path);
 
+        let mut error_state = ErrorState::default();
+
+        match Config::load() {
+            Ok(config) => Config::install(config),
+            Err(error) => {
+                Config::install(Config::default());
+                error_state.set_error(error);
+            }
+        }
+
+        let auto_commit = AutoCommit::open(&storage.file_path).unwrap_or_else(|error| {
+            error_state.set_error(error);
+            None
+        });
+
         let mut task_list = TaskList::new();
 
         if let Ok(tasks) = storage.load() {
@@ -55,12 +116,23 @@ impl App {
             running: true,
             task_list,
             storage,
+            auto_commit,
             events: EventHandler::new(),
             task_form: TaskForm::default(),
+            quick_add: QuickAddForm::default(),
+            status_picker: StatusPicker::default(),
+            dependency_picker: DependencyPicker::default(),
+            time_log: TimeLog::default(),
+            reminder_list: ReminderList::default(),
+            snooze_picker: SnoozePicker::default(),
             current_screen: CurrentScreen::Normal,
-            error_state: ErrorState::default(),
+            error_state,
             show_helper_popup: false,
             show_delete_popup: false,
+            pending_close: None,
+            now: now_timestamp(),
+            pomodoro: None,
+            notification: None,
         })
     }
     /// Run the application's main loop.
@@ -69,16 +141,18 @@ impl App {
             terminal.draw(|frame| frame.render_widget(&mut self, frame.area()))?;
             self.handle_events()?;
         }
-        Ok(())
+        self.commit_pending()
+            .map_err(|error| color_eyre::eyre::eyre!(error.user_message()))
     }
 
     pub fn handle_events(&mut self) -> color_eyre::Result<()> {
         match self.events.next()? {
             Event::Tick => self.tick(),
-            Event::Crossterm(event) => match event {
-                crossterm::event::Event::Key(key_event) => self.handle_key_event(key_event)?,
-                _ => {}
-            },
+            Event::Crossterm(event) => {
+                if let crossterm::event::Event::Key(key_event) = event {
+                    self.handle_key_event(key_event)?
+                }
+            }
             Event::App(app_event) => match app_event {
                 AppEvent::Quit => self.quit(),
                 AppEvent::AddTask(title, description, due_date) => {
@@ -119,8 +193,31 @@ impl App {
             }
         }
 
+        if let Some(status) = self.pending_close {
+            match key_event.code {
+                KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                    self.pending_close = None;
+                }
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    self.pending_close = None;
+                    self.apply_selected_status(status, None);
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
         match self.current_screen {
             CurrentScreen::Normal => {
+                let handled = match self.task_list.layout {
+                    TaskLayout::Calendar => self.handle_calendar_key(key_event),
+                    TaskLayout::Board => self.handle_board_key(key_event),
+                    TaskLayout::List | TaskLayout::Agenda | TaskLayout::Stats => false,
+                };
+                if handled {
+                    return Ok(());
+                }
+
                 match key_event.code {
                     KeyCode::Char('q') => self.events.send(AppEvent::Quit),
                     KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => {
@@ -131,43 +228,220 @@ impl App {
                         self.current_screen = CurrentScreen::Create;
                         self.task_form.toggle_task_form();
                     }
+                    KeyCode::Char('e') => self.open_edit_form(),
+                    KeyCode::Char('S') => self.open_status_picker(),
+                    KeyCode::Char('D') => self.open_dependency_picker(),
+                    KeyCode::Char('T') => self.toggle_timer(),
+                    KeyCode::Char('M') => self.open_time_log(),
+                    KeyCode::Char('R') => self.open_reminder_list(),
+                    KeyCode::Char('P') => self.start_pomodoro(),
+                    KeyCode::Char('z') => self.open_snooze_picker(),
+                    KeyCode::Char('Z') => self.task_list.toggle_show_deferred(),
+                    KeyCode::Char('a') => {
+                        self.current_screen = CurrentScreen::QuickAdd;
+                        self.quick_add.open();
+                    }
+                    KeyCode::Char(key @ '1'..='9') => {
+                        if let Some(view) = SmartView::from_key(key) {
+                            self.task_list.set_view(view);
+                        }
+                    }
+                    KeyCode::Char('v') => {
+                        let layout = self.task_list.layout.next();
+                        self.task_list.set_layout(layout);
+                    }
                     KeyCode::Char('j') => self.task_list.select_next(),
                     KeyCode::Char('k') => self.task_list.select_previous(),
-                    KeyCode::Char('d') => {
-                        if self.task_list.selected_task_id.is_some() {
-                            self.show_delete_popup = true;
-                        }
+                    KeyCode::Char('d') if self.task_list.selected_task_id.is_some() => {
+                        self.show_delete_popup = true;
                     }
                     KeyCode::Char('h') => self.show_helper_popup = true,
                     KeyCode::Char('s') => self.toggle_selected_favorite(),
                     KeyCode::Enter => self.toggle_task(),
                     KeyCode::Esc if self.show_helper_popup => self.show_helper_popup = false,
+                    KeyCode::Esc => self.notification = None,
                     _ => {}
                 }
             }
-            CurrentScreen::Create => match key_event.code {
+            CurrentScreen::Create | CurrentScreen::Editing => match key_event.code {
                 KeyCode::Esc => {
                     if self.task_form.form_input.due_date.input_mode == DateInputMode::Calendar {
                         self.task_form.form_input.due_date.toggle_date_input_mode();
                     } else {
-                        self.current_screen = CurrentScreen::Normal;
-                        self.task_form.toggle_task_form();
-                        self.task_form.reset_form_input();
+                        self.close_task_form();
                     }
                 }
                 KeyCode::Char('s') if key_event.modifiers == KeyModifiers::CONTROL => {
-                    self.add_task();
+                    if self.current_screen == CurrentScreen::Editing {
+                        self.save_edited_task();
+                    } else {
+                        self.add_task();
+                    }
                 }
 
                 KeyCode::Tab => self.task_form.select_next(),
                 _ => self.task_form.input(key_event),
             },
 
-            CurrentScreen::Editing => {}
+            CurrentScreen::StatusPicker => match self.status_picker.handle_input(key_event) {
+                StatusPickerAction::None => {}
+                StatusPickerAction::Cancel => self.close_status_picker(),
+                StatusPickerAction::Confirm(status, blocked_reason) => {
+                    self.close_status_picker();
+                    self.set_selected_status(status, blocked_reason);
+                }
+            },
+
+            CurrentScreen::DependencyPicker => {
+                match self.dependency_picker.handle_input(key_event) {
+                    DependencyPickerAction::None => {}
+                    DependencyPickerAction::Cancel => self.close_dependency_picker(),
+                    DependencyPickerAction::Confirm(depends_on) => {
+                        self.set_selected_dependencies(depends_on)
+                    }
+                }
+            }
+
+            CurrentScreen::TimeLog => {
+                let durations: Vec<Duration> = self
+                    .task_list
+                    .get_selected_task()
+                    .map(|task| {
+                        task.time_entries
+                            .iter()
+                            .map(|entry| entry.duration(self.now))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                match self.time_log.handle_input(key_event, &durations) {
+                    TimeLogAction::None => {}
+                    TimeLogAction::Close => self.close_time_log(),
+                    TimeLogAction::Add(duration) => {
+                        self.task_list.add_time_entry(duration, now_timestamp());
+                        self.time_log_changed();
+                    }
+                    TimeLogAction::SetDuration(index, duration) => {
+                        self.task_list.set_time_entry_duration(index, duration);
+                        self.time_log_changed();
+                    }
+                    TimeLogAction::Delete(index) => {
+                        self.task_list.delete_time_entry(index);
+                        self.time_log_changed();
+                    }
+                }
+            }
+
+            CurrentScreen::ReminderList => {
+                let has_due_date = self
+                    .task_list
+                    .get_selected_task()
+                    .is_some_and(|task| task.due_date.is_some());
+
+                match self
+                    .reminder_list
+                    .handle_input(key_event, now_timestamp(), has_due_date)
+                {
+                    ReminderListAction::None => {}
+                    ReminderListAction::Close => self.close_reminder_list(),
+                    ReminderListAction::Add(time) => {
+                        self.task_list.add_reminder(time);
+                        self.reminder_list_changed();
+                    }
+                    ReminderListAction::Delete(index) => {
+                        self.task_list.delete_reminder(index);
+                        self.reminder_list_changed();
+                    }
+                }
+            }
+
+            CurrentScreen::SnoozePicker => match self.snooze_picker.handle_input(key_event) {
+                SnoozePickerAction::None => {}
+                SnoozePickerAction::Cancel => self.close_snooze_picker(),
+                SnoozePickerAction::Confirm(date) => self.set_selected_defer_until(date),
+            },
+
+            CurrentScreen::Pomodoro => match key_event.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.stop_pomodoro(),
+                KeyCode::Char(' ') => {
+                    if let Some(pomodoro) = &mut self.pomodoro {
+                        pomodoro.toggle_pause(Instant::now());
+                    }
+                }
+                KeyCode::Char('s') => {
+                    if let Some(pomodoro) = &mut self.pomodoro {
+                        pomodoro.skip(&Config::global().pomodoro);
+                    }
+                }
+                _ => {}
+            },
+
+            CurrentScreen::QuickAdd => match key_event.code {
+                KeyCode::Esc => {
+                    self.quick_add.close();
+                    self.current_screen = CurrentScreen::Normal;
+                }
+                KeyCode::Enter => self.quick_add_task(),
+                _ => self.quick_add.input(key_event),
+            },
         }
         Ok(())
     }
 
+    /// Handles the calendar layout's own keys. Returns `false` for keys it leaves to
+    /// the normal key bindings.
+    ///
+    /// Days, months and years move with `←`/`→`, `[`/`]` and `{`/`}` rather than
+    /// the date picker's letters, which add a task, show help or start a pomodoro
+    /// here.
+    fn handle_calendar_key(&mut self, key_event: KeyEvent) -> bool {
+        let picker_key = match key_event.code {
+            KeyCode::Up => {
+                self.task_list.select_previous();
+                return true;
+            }
+            KeyCode::Down => {
+                self.task_list.select_next();
+                return true;
+            }
+            KeyCode::Left => 'h',
+            KeyCode::Right => 'l',
+            KeyCode::Char('[') => 'p',
+            KeyCode::Char(']') => 'n',
+            KeyCode::Char('{') => 'P',
+            KeyCode::Char('}') => 'N',
+            KeyCode::Char(key @ ('j' | 'k' | 't')) => key,
+            _ => return false,
+        };
+        if let Some(date) =
+            navigate_calendar(self.task_list.get_focused_date(), KeyCode::Char(picker_key))
+        {
+            self.task_list.set_focused_date(date);
+        }
+        true
+    }
+
+    /// Handles the board layout's own keys: `j`/`k` move within a column, `←`/`→`
+    /// across columns and `H`/`L` move the selected card to the neighbouring column.
+    fn handle_board_key(&mut self, key_event: KeyEvent) -> bool {
+        match key_event.code {
+            KeyCode::Char('j') => self.task_list.select_in_board_column(true),
+            KeyCode::Char('k') => self.task_list.select_in_board_column(false),
+            KeyCode::Right => self.task_list.select_board_column(true),
+            KeyCode::Left => self.task_list.select_board_column(false),
+            KeyCode::Char('L') => self.move_selected_card(true),
+            KeyCode::Char('H') => self.move_selected_card(false),
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn move_selected_card(&mut self, forward: bool) {
+        if let Some(status) = self.task_list.board_neighbour_status(forward) {
+            self.set_selected_status(status, None);
+        }
+    }
+
     pub fn initialize_storage() -> Result<PathBuf, String> {
         let config_dir = dirs::config_dir().ok_or("Connot find config directory")?;
         let app_dir = config_dir.join("delibird");
@@ -181,16 +455,19 @@ impl App {
     }
 
     pub fn save_tasks(&self) -> AppResult<()> {
-        self.storage.save(&self.task_list.task_list)
+        self.storage.save(&self.task_list.records())
     }
 
     pub fn auto_save(&mut self) {
         match self.save_tasks() {
             Ok(_) => {
-                if let Some(current_error) = &self.error_state.current_error {
-                    if current_error.is_storage_error() {
-                        self.error_state.clear_error();
-                    }
+                if let Some(auto_commit) = &mut self.auto_commit {
+                    auto_commit.saved(Instant::now());
+                }
+                if let Some(current_error) = &self.error_state.current_error
+                    && current_error.is_storage_error()
+                {
+                    self.error_state.clear_error();
                 }
             }
             Err(error) => {
@@ -202,18 +479,15 @@ impl App {
     pub fn add_task(&mut self) {
         let task_data = self.task_form.to_task_data();
 
-        match TaskValidator::validate_all_task_field(
-            &task_data.title,
-            &task_data.description,
-            task_data.due_date,
-        ) {
+        match self.task_form.validate_all_field() {
             Ok(_) => {
-                self.task_list
-                    .add_task(task_data.title, task_data.description, task_data.due_date);
-                self.task_form.reset_form_input();
+                let mut task =
+                    Task::new(task_data.title, task_data.description, task_data.due_date);
+                task.estimate = task_data.estimate;
+                self.task_list.insert_task(task);
+                self.close_task_form();
                 self.auto_save();
-                self.current_screen = CurrentScreen::Normal;
-                self.task_form.clear_field_errors();
+                self.warn_if_past_due(task_data.due_date);
             }
             Err(validation_error) => {
                 self.task_form.field_errors = validation_error;
@@ -221,11 +495,316 @@ impl App {
         }
     }
 
-    pub fn toggle_task(&mut self) {
-        self.task_list.toggle_status();
+    pub fn open_edit_form(&mut self) {
+        if let Some(task) = self.task_list.get_selected_task() {
+            self.task_form.load_task(task);
+            self.task_form.is_open = true;
+            self.current_screen = CurrentScreen::Editing;
+        }
+    }
+
+    pub fn save_edited_task(&mut self) {
+        let Some(task_id) = self.task_form.editing_task_id else {
+            return;
+        };
+        let task_data = self.task_form.to_task_data();
+        let original_due_date = self.task_form.form_input.due_date.original_date;
+
+        match self.task_form.validate_all_field() {
+            Ok(_) => {
+                self.task_list.update_task(
+                    task_id,
+                    task_data.title,
+                    task_data.description,
+                    task_data.due_date,
+                    task_data.estimate,
+                );
+                self.close_task_form();
+                self.auto_save();
+                if original_due_date != task_data.due_date {
+                    self.warn_if_past_due(task_data.due_date);
+                }
+            }
+            Err(validation_error) => {
+                self.task_form.field_errors = validation_error;
+            }
+        }
+    }
+
+    pub fn close_task_form(&mut self) {
+        self.task_form.is_open = false;
+        self.task_form.reset_form_input();
+        self.task_form.clear_field_errors();
+        self.current_screen = CurrentScreen::Normal;
+    }
+
+    /// Shows a warning banner when the policy accepted a due date that is already past.
+    fn warn_if_past_due(&mut self, due_date: Option<Date>) {
+        if let Some(warning) = due_date.and_then(TaskValidator::due_date_warning) {
+            self.error_state.set_error(warning);
+        }
+    }
+
+    pub fn quick_add_task(&mut self) {
+        match QuickAdd::parse(self.quick_add.line()) {
+            Ok(quick_add) => {
+                let due_date = quick_add.due_date;
+                self.task_list.insert_task(quick_add.into_task());
+                self.quick_add.close();
+                self.auto_save();
+                self.current_screen = CurrentScreen::Normal;
+                self.warn_if_past_due(due_date);
+            }
+            Err(app_error) => {
+                self.quick_add.parsed = Some(Err(app_error));
+            }
+        }
+    }
+
+    pub fn open_status_picker(&mut self) {
+        if let Some(task) = self.task_list.get_selected_task() {
+            self.status_picker
+                .open(task.status, task.blocked_reason.as_deref());
+            self.current_screen = CurrentScreen::StatusPicker;
+        }
+    }
+
+    pub fn close_status_picker(&mut self) {
+        self.status_picker.close();
+        self.current_screen = CurrentScreen::Normal;
+    }
+
+    /// Moves the selected task to `status`, asking first if that would close a task
+    /// other open tasks still depend on.
+    pub fn set_selected_status(&mut self, status: Status, blocked_reason: Option<String>) {
+        if status.is_closed() && self.selected_has_open_dependents() {
+            self.pending_close = Some(status);
+        } else {
+            self.apply_selected_status(status, blocked_reason);
+        }
+    }
+
+    fn apply_selected_status(&mut self, status: Status, blocked_reason: Option<String>) {
+        match self.task_list.set_status(status, blocked_reason) {
+            Ok(_) => self.auto_save(),
+            Err(error) => self.error_state.set_error(error),
+        }
+    }
+
+    fn selected_has_open_dependents(&self) -> bool {
+        self.task_list.get_selected_task().is_some_and(|task| {
+            task.is_open()
+                && self
+                    .task_list
+                    .dependents_of(task.id)
+                    .iter()
+                    .any(|dependent| dependent.is_open())
+        })
+    }
+
+    pub fn open_dependency_picker(&mut self) {
+        let Some(task) = self.task_list.get_selected_task() else {
+            return;
+        };
+
+        let mut candidates: Vec<&Task> = self
+            .task_list
+            .task_list
+            .iter()
+            .filter(|candidate| candidate.id != task.id)
+            .collect();
+        candidates.sort_by(|a, b| compare_for_display(a, b));
+
+        let candidates = candidates
+            .into_iter()
+            .map(|candidate| {
+                (
+                    candidate.id,
+                    format!("{} {}", candidate.glyph(), candidate.title),
+                )
+            })
+            .collect();
+
+        self.dependency_picker.open(candidates, &task.depends_on);
+        self.current_screen = CurrentScreen::DependencyPicker;
+    }
+
+    pub fn close_dependency_picker(&mut self) {
+        self.dependency_picker.close();
+        self.current_screen = CurrentScreen::Normal;
+    }
+
+    /// Saves the picked prerequisites. The picker stays open when they would form a cycle.
+    pub fn set_selected_dependencies(&mut self, depends_on: Vec<Uuid>) {
+        match self.task_list.set_dependencies(depends_on) {
+            Ok(_) => {
+                self.close_dependency_picker();
+                self.auto_save();
+            }
+            Err(error) => self.error_state.set_error(error),
+        }
+    }
+
+    /// Starts or stops the timer on the selected task. Only one timer runs at a time.
+    pub fn toggle_timer(&mut self) {
+        self.now = now_timestamp();
+        self.task_list.toggle_timer(self.now);
+        self.auto_save();
+    }
+
+    pub fn open_time_log(&mut self) {
+        if let Some(task) = self.task_list.get_selected_task() {
+            self.time_log.open(task.time_entries.len());
+            self.current_screen = CurrentScreen::TimeLog;
+        }
+    }
+
+    pub fn close_time_log(&mut self) {
+        self.time_log.close();
+        self.current_screen = CurrentScreen::Normal;
+    }
+
+    fn time_log_changed(&mut self) {
+        let entry_count = self
+            .task_list
+            .get_selected_task()
+            .map_or(0, |task| task.time_entries.len());
+        self.time_log.set_entry_count(entry_count);
+        self.auto_save();
+    }
+
+    pub fn open_reminder_list(&mut self) {
+        if let Some(task) = self.task_list.get_selected_task() {
+            self.reminder_list.open(task.reminders.len());
+            self.current_screen = CurrentScreen::ReminderList;
+        }
+    }
+
+    pub fn close_reminder_list(&mut self) {
+        self.reminder_list.close();
+        self.current_screen = CurrentScreen::Normal;
+    }
+
+    fn reminder_list_changed(&mut self) {
+        let reminder_count = self
+            .task_list
+            .get_selected_task()
+            .map_or(0, |task| task.reminders.len());
+        self.reminder_list.set_reminder_count(reminder_count);
         self.auto_save();
     }
 
+    /// Fires reminders that have come due: shows the banner, rings the bell and
+    /// runs the configured command for each.
+    fn check_reminders(&mut self) {
+        if self
+            .notification
+            .as_ref()
+            .is_some_and(|notification| notification.is_expired())
+        {
+            self.notification = None;
+        }
+
+        let config = &Config::global().reminders;
+        let fired = fire_due_reminders(&mut self.task_list.task_list, self.now, config.due_time());
+        let Some(notification) = Notification::new(&fired) else {
+            return;
+        };
+
+        self.notification = Some(notification);
+        self.auto_save();
+
+        for reminder in &fired {
+            if let Err(error) = run_reminder_command(&config.command, reminder) {
+                self.error_state.set_error(error);
+                break;
+            }
+        }
+
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+    }
+
+    pub fn open_snooze_picker(&mut self) {
+        let today = get_today_with_fallbacks().0;
+
+        if let Some(task) = self.task_list.get_selected_task() {
+            let base = task
+                .defer_until
+                .filter(|date| *date > today)
+                .unwrap_or(today);
+            self.snooze_picker.open(base);
+            self.current_screen = CurrentScreen::SnoozePicker;
+        }
+    }
+
+    pub fn close_snooze_picker(&mut self) {
+        self.snooze_picker.close();
+        self.current_screen = CurrentScreen::Normal;
+    }
+
+    /// Defers the selected task. The picker stays open when the date is rejected.
+    pub fn set_selected_defer_until(&mut self, date: Option<Date>) {
+        match self.task_list.set_defer_until(date) {
+            Ok(_) => {
+                self.close_snooze_picker();
+                self.auto_save();
+            }
+            Err(error) => self.error_state.set_error(error),
+        }
+    }
+
+    /// Starts a focus session on the selected task.
+    pub fn start_pomodoro(&mut self) {
+        if let Some(task) = self.task_list.get_selected_task() {
+            self.pomodoro = Some(Pomodoro::start(
+                task.id,
+                &Config::global().pomodoro,
+                Instant::now(),
+            ));
+            self.current_screen = CurrentScreen::Pomodoro;
+        }
+    }
+
+    pub fn stop_pomodoro(&mut self) {
+        self.pomodoro = None;
+        self.current_screen = CurrentScreen::Normal;
+    }
+
+    /// Advances the focus session, counting finished work phases on its task and
+    /// ringing the terminal bell whenever a phase ends.
+    fn tick_pomodoro(&mut self) {
+        let Some(pomodoro) = &mut self.pomodoro else {
+            return;
+        };
+        let Some(finished) = pomodoro.tick(&Config::global().pomodoro, Instant::now()) else {
+            return;
+        };
+
+        if finished == PomodoroPhase::Work {
+            let task_id = pomodoro.task_id;
+            self.task_list.record_pomodoro(task_id);
+            self.auto_save();
+        }
+
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+    }
+
+    pub fn toggle_task(&mut self) {
+        if let Some(task) = self.task_list.get_selected_task()
+            && task.is_open()
+        {
+            self.set_selected_status(Status::Completed, None);
+            return;
+        }
+
+        match self.task_list.toggle_status() {
+            Ok(_) => self.auto_save(),
+            Err(error) => self.error_state.set_error(error),
+        }
+    }
+
     pub fn delete_selected_task(&mut self) {
         self.task_list.delete_selected_task();
         self.show_delete_popup = false;
@@ -247,6 +826,26 @@ impl App {
     /// needs to be updated at a fixed frame rate. E.g. polling a server, updating an animation.
     pub fn tick(&mut self) {
         self.error_state.update();
+        self.now = now_timestamp();
+        self.tick_pomodoro();
+        self.check_reminders();
+        self.commit_if_due();
+    }
+
+    fn commit_if_due(&mut self) {
+        if let Some(auto_commit) = &mut self.auto_commit
+            && let Err(error) = auto_commit.commit_if_due(Instant::now())
+        {
+            self.error_state.set_error(error);
+        }
+    }
+
+    /// Commits saves still waiting for their commit.
+    pub fn commit_pending(&mut self) -> AppResult<()> {
+        match &mut self.auto_commit {
+            Some(auto_commit) => auto_commit.commit_pending().map(|_| ()),
+            None => Ok(()),
+        }
     }
 
     /// Set running to false to quit the application.
@@ -0,0 +1,551 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Read},
+};
+
+use crate::{
+    app::App,
+    core::{
+        Storage,
+        error::AppError,
+        formats::{Format, FormatOptions, merge_imported},
+        quick_add::QuickAdd,
+        report::{ReportGrouping, ReportOptions, build_html, resolve_since},
+        sync::{
+            caldav::{self, CaldavClient, SyncState},
+            git::{CONFLICT_PREFIX, GitRepo, auto_commit, merge_task_files},
+        },
+        task::{SmartView, Task, task::Status},
+        validation::TaskValidator,
+    },
+    utils::{
+        config::{CaldavConfig, Config},
+        date::get_today_with_fallbacks,
+    },
+};
+
+const USAGE: &str = "Usage:
+  delibird                          Start the interactive todo list
+  delibird add [--dry-run] <line>   Add a task from a quick-add line, e.g.
+                                    delibird add Write report due:fri +work #urgent !high
+  delibird import --format <format> [--dry-run] [--map <header>=<column>]... <file>
+                                    Add the tasks in a file (- reads standard input)
+  delibird export --format <format> [--columns <column>,...] [<file>]
+                                    Write all tasks to a file or standard output
+  delibird report [--view <view>] [--open] [--since <date>] [--group-by <group|status>]
+                  [--title <text>] [<file>]
+                                    Write an HTML report of a view to a file or standard output
+  delibird sync [--remote <remote>] Sync tasks with the CalDAV server, then commit, pull,
+                                    merge and push the task file with git
+  delibird help                     Show this message
+
+Formats: todotxt, ics, markdown, csv, taskwarrior, org (export only)
+--map and --columns only apply to csv and override the csv section of the config
+Views: all, overdue, today, upcoming, no-date, completed
+--since takes a date, week (since Monday) or month
+--remote only applies to git";
+
+/// A non-interactive command given on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Add {
+        line: String,
+        dry_run: bool,
+    },
+    Import {
+        format: Format,
+        options: FormatOptions,
+        path: String,
+        dry_run: bool,
+    },
+    Export {
+        format: Format,
+        options: FormatOptions,
+        path: Option<String>,
+    },
+    Report {
+        options: ReportOptions,
+        path: Option<String>,
+    },
+    Sync {
+        remote: Option<String>,
+    },
+    /// Run by git to merge two versions of the task file; not meant to be typed.
+    MergeDriver {
+        base: String,
+        ours: String,
+        theirs: String,
+    },
+    Help,
+}
+
+impl Command {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let Some((command, rest)) = args.split_first() else {
+            return Ok(Command::Help);
+        };
+
+        match command.as_str() {
+            "add" => {
+                let dry_run = rest.iter().any(|arg| arg == "--dry-run" || arg == "-n");
+                let words: Vec<&str> = rest
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|arg| *arg != "--dry-run" && *arg != "-n")
+                    .collect();
+
+                if words.is_empty() {
+                    return Err(format!("Nothing to add\n\n{}", USAGE));
+                }
+
+                Ok(Command::Add {
+                    line: words.join(" "),
+                    dry_run,
+                })
+            }
+            "import" => {
+                let mut rest = rest.to_vec();
+                let format = take_format(&mut rest)?;
+                if !format.can_import() {
+                    return Err(AppError::ImportUnsupported {
+                        format: format.name().to_string(),
+                    }
+                    .user_message());
+                }
+                let options = take_format_options(&mut rest, format)?;
+                let dry_run = take_flag(&mut rest, &["--dry-run", "-n"]);
+
+                match rest.as_slice() {
+                    [path] => Ok(Command::Import {
+                        format,
+                        options,
+                        path: path.clone(),
+                        dry_run,
+                    }),
+                    [] => Err(format!("Nothing to import\n\n{}", USAGE)),
+                    _ => Err(format!("Import takes a single file\n\n{}", USAGE)),
+                }
+            }
+            "export" => {
+                let mut rest = rest.to_vec();
+                let format = take_format(&mut rest)?;
+                let options = take_format_options(&mut rest, format)?;
+
+                match rest.as_slice() {
+                    [] => Ok(Command::Export {
+                        format,
+                        options,
+                        path: None,
+                    }),
+                    [path] => Ok(Command::Export {
+                        format,
+                        options,
+                        path: Some(path.clone()),
+                    }),
+                    _ => Err(format!("Export takes a single file\n\n{}", USAGE)),
+                }
+            }
+            "report" => {
+                let mut rest = rest.to_vec();
+                let options = take_report_options(&mut rest)?;
+
+                match rest.as_slice() {
+                    [] => Ok(Command::Report {
+                        options,
+                        path: None,
+                    }),
+                    [path] => Ok(Command::Report {
+                        options,
+                        path: Some(path.clone()),
+                    }),
+                    _ => Err(format!("Report takes a single file\n\n{}", USAGE)),
+                }
+            }
+            "sync" => {
+                let mut rest = rest.to_vec();
+                let remote = take_option(&mut rest, "--remote")?;
+
+                match rest.as_slice() {
+                    [] => Ok(Command::Sync { remote }),
+                    _ => Err(format!("Sync takes no arguments\n\n{}", USAGE)),
+                }
+            }
+            "merge-driver" => match rest {
+                [base, ours, theirs] => Ok(Command::MergeDriver {
+                    base: base.clone(),
+                    ours: ours.clone(),
+                    theirs: theirs.clone(),
+                }),
+                _ => Err("merge-driver takes the base, ours and theirs files".to_string()),
+            },
+            "help" | "--help" | "-h" => Ok(Command::Help),
+            other => Err(format!("Unknown command '{}'\n\n{}", other, USAGE)),
+        }
+    }
+}
+
+/// Runs a command against the task file without starting the TUI.
+pub fn run(args: &[String]) -> Result<(), String> {
+    match Command::parse(args)? {
+        Command::Add { line, dry_run } => add(&line, dry_run),
+        Command::Import {
+            format,
+            options,
+            path,
+            dry_run,
+        } => import(format, &options, &path, dry_run),
+        Command::Export {
+            format,
+            options,
+            path,
+        } => export(format, &options, path.as_deref()),
+        Command::Report { options, path } => report(&options, path.as_deref()),
+        Command::Sync { remote } => sync(remote),
+        Command::MergeDriver { base, ours, theirs } => merge_driver(&base, &ours, &theirs),
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+    }
+}
+
+/// Removes any of `names` from `args`, returning whether one was given.
+fn take_flag(args: &mut Vec<String>, names: &[&str]) -> bool {
+    let before = args.len();
+    args.retain(|arg| !names.contains(&arg.as_str()));
+    args.len() != before
+}
+
+/// Removes `--name value` or `--name=value` from `args` and returns the value.
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    let prefix = format!("{}=", name);
+
+    if let Some(index) = args.iter().position(|arg| arg.starts_with(&prefix)) {
+        let arg = args.remove(index);
+        return Ok(Some(arg[prefix.len()..].to_string()));
+    }
+
+    let Some(index) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    if index + 1 >= args.len() {
+        return Err(format!("{} needs a value\n\n{}", name, USAGE));
+    }
+    args.remove(index);
+    Ok(Some(args.remove(index)))
+}
+
+/// Removes every `--name value` or `--name=value` from `args`, in order.
+fn take_all_options(args: &mut Vec<String>, name: &str) -> Result<Vec<String>, String> {
+    let mut values = Vec::new();
+    while let Some(value) = take_option(args, name)? {
+        values.push(value);
+    }
+    Ok(values)
+}
+
+fn take_format(args: &mut Vec<String>) -> Result<Format, String> {
+    let names = Format::ALL.map(|format| format.name()).join(", ");
+    let name = take_option(args, "--format")?
+        .ok_or_else(|| format!("--format is required (one of: {})", names))?;
+
+    Format::parse(&name).ok_or_else(|| format!("Unknown format '{}' (one of: {})", name, names))
+}
+
+/// The configured options for `format`, overridden by `--columns` and `--map`.
+fn take_format_options(args: &mut Vec<String>, format: Format) -> Result<FormatOptions, String> {
+    let columns = take_option(args, "--columns")?;
+    let mappings = take_all_options(args, "--map")?;
+
+    let mut options = FormatOptions::from_config();
+    if !format.has_options() {
+        if columns.is_some() || !mappings.is_empty() {
+            return Err(format!(
+                "--columns and --map only apply to csv\n\n{}",
+                USAGE
+            ));
+        }
+        return Ok(options);
+    }
+
+    if let Some(columns) = columns {
+        options.columns = columns
+            .split(',')
+            .map(|column| column.trim().to_string())
+            .filter(|column| !column.is_empty())
+            .collect();
+    }
+    for mapping in mappings {
+        let (header, column) = mapping
+            .split_once('=')
+            .ok_or_else(|| format!("--map expects <header>=<column>, found '{}'", mapping))?;
+        options
+            .mapping
+            .insert(header.trim().to_string(), column.trim().to_string());
+    }
+
+    Ok(options)
+}
+
+fn take_report_options(args: &mut Vec<String>) -> Result<ReportOptions, String> {
+    let mut options = ReportOptions {
+        open_only: take_flag(args, &["--open"]),
+        title: take_option(args, "--title")?,
+        ..ReportOptions::default()
+    };
+
+    if let Some(view) = take_option(args, "--view")? {
+        options.view = SmartView::parse(&view).ok_or_else(|| {
+            format!(
+                "Unknown view '{}' (one of: all, overdue, today, upcoming, no-date, completed)",
+                view
+            )
+        })?;
+    }
+    if let Some(since) = take_option(args, "--since")? {
+        let today = get_today_with_fallbacks().0;
+        options.closed_since =
+            Some(resolve_since(&since, today).map_err(|err| err.user_message())?);
+    }
+    if let Some(grouping) = take_option(args, "--group-by")? {
+        options.grouping = ReportGrouping::parse(&grouping).ok_or_else(|| {
+            format!(
+                "Unknown grouping '{}' (one of: group, status, none)",
+                grouping
+            )
+        })?;
+    }
+
+    Ok(options)
+}
+
+fn open_storage() -> Result<Storage, String> {
+    let file_path = App::initialize_storage()?;
+    Ok(Storage::new(file_path))
+}
+
+/// Saves the tasks, rewrites the Org export and, with git sync on, commits them.
+fn save(storage: &Storage, tasks: &[Task]) -> Result<(), String> {
+    storage.save(tasks).map_err(|err| err.user_message())?;
+    auto_commit(&storage.file_path).map_err(|err| err.user_message())?;
+    Ok(())
+}
+
+fn add(line: &str, dry_run: bool) -> Result<(), String> {
+    let quick_add = QuickAdd::parse(line).map_err(|err| err.user_message())?;
+
+    if dry_run {
+        println!("Would add: {}", quick_add.summary());
+        return Ok(());
+    }
+
+    let storage = open_storage()?;
+    let mut tasks = storage.load().map_err(|err| err.user_message())?;
+    let summary = quick_add.summary();
+    let due_date = quick_add.due_date;
+
+    tasks.push(quick_add.into_task());
+    save(&storage, &tasks)?;
+
+    println!("Added: {}", summary);
+    if let Some(warning) = due_date.and_then(TaskValidator::due_date_warning) {
+        println!("Warning: {}", warning.user_message());
+    }
+    Ok(())
+}
+
+fn import(
+    format: Format,
+    options: &FormatOptions,
+    path: &str,
+    dry_run: bool,
+) -> Result<(), String> {
+    let mut contents = String::new();
+    let read = if path == "-" {
+        io::stdin().read_to_string(&mut contents).map(|_| ())
+    } else {
+        fs::read_to_string(path).map(|text| contents = text)
+    };
+    read.map_err(|err| {
+        AppError::LoadFailed {
+            path: path.to_string(),
+            reason: err.to_string(),
+        }
+        .user_message()
+    })?;
+
+    let imported = format.import(&contents, options).map_err(|errors| {
+        errors
+            .iter()
+            .map(AppError::user_message)
+            .collect::<Vec<_>>()
+            .join("\n")
+    })?;
+
+    let storage = open_storage()?;
+    let mut tasks = storage.load().map_err(|err| err.user_message())?;
+    let existing = tasks.len();
+    let summary = merge_imported(&mut tasks, imported, format.matches_titles());
+
+    if dry_run {
+        let added = &tasks[existing..];
+        for task in added {
+            println!("Would import: {} ({})", task.title, task.status.label());
+        }
+
+        let mut by_status: BTreeMap<Status, usize> = BTreeMap::new();
+        for task in added {
+            *by_status.entry(task.status).or_default() += 1;
+        }
+        let counts: Vec<String> = by_status
+            .iter()
+            .map(|(status, count)| format!("{} {}", count, status.label()))
+            .collect();
+        if counts.is_empty() {
+            println!("Would import 0 tasks");
+        } else {
+            println!(
+                "Would import {} tasks: {}",
+                summary.added,
+                counts.join(", ")
+            );
+        }
+        if summary.skipped > 0 {
+            println!(
+                "Would skip {} tasks that are already there",
+                summary.skipped
+            );
+        }
+        return Ok(());
+    }
+
+    save(&storage, &tasks)?;
+
+    println!("Imported {} tasks", summary.added);
+    if summary.skipped > 0 {
+        println!("Skipped {} tasks that were already there", summary.skipped);
+    }
+    Ok(())
+}
+
+fn export(format: Format, options: &FormatOptions, path: Option<&str>) -> Result<(), String> {
+    let storage = open_storage()?;
+    let tasks = storage.load().map_err(|err| err.user_message())?;
+    let output = format
+        .export(&tasks, options)
+        .map_err(|err| err.user_message())?;
+
+    write_output(path, &output)
+}
+
+/// Writes `output` to the file at `path`, or standard output without one.
+fn write_output(path: Option<&str>, output: &str) -> Result<(), String> {
+    match path {
+        Some(path) => fs::write(path, output).map_err(|err| {
+            AppError::SaveFailed {
+                path: path.to_string(),
+                reason: err.to_string(),
+            }
+            .user_message()
+        }),
+        None => {
+            print!("{}", output);
+            Ok(())
+        }
+    }
+}
+
+fn report(options: &ReportOptions, path: Option<&str>) -> Result<(), String> {
+    let storage = open_storage()?;
+    let tasks = storage.load().map_err(|err| err.user_message())?;
+    let output = build_html(&tasks, options, get_today_with_fallbacks().0);
+
+    write_output(path, &output)
+}
+
+/// Syncs with the CalDAV server and then git, whichever are configured.
+fn sync(remote: Option<String>) -> Result<(), String> {
+    let config = Config::global();
+    if !config.caldav.is_enabled() && !config.git.enabled {
+        return Err(
+            "Sync is off. Set caldav.url or git.enabled in config.json to turn it on".to_string(),
+        );
+    }
+
+    let storage = open_storage()?;
+    if config.caldav.is_enabled() {
+        sync_caldav(&storage, &config.caldav)?;
+    }
+    if config.git.enabled {
+        let remote = remote.unwrap_or_else(|| config.git.remote.clone());
+        sync_git(&storage, &remote)?;
+    }
+    Ok(())
+}
+
+fn sync_caldav(storage: &Storage, config: &CaldavConfig) -> Result<(), String> {
+    let state_path = SyncState::file_path(&storage.file_path);
+    let mut state = SyncState::load(&state_path, &config.url).map_err(|err| err.user_message())?;
+    let mut tasks = storage.load().map_err(|err| err.user_message())?;
+
+    let client = CaldavClient::new(config);
+    let report = caldav::sync(&mut tasks, &mut state, &client, config.on_conflict)
+        .map_err(|err| err.user_message())?;
+    save(storage, &tasks)?;
+    state.save(&state_path).map_err(|err| err.user_message())?;
+
+    println!(
+        "CalDAV: {} uploaded, {} downloaded, {} deleted here, {} deleted on the server",
+        report.uploaded, report.downloaded, report.deleted_here, report.deleted_on_server
+    );
+    for conflict in &report.conflicts {
+        println!("Conflict: {}", conflict);
+    }
+    Ok(())
+}
+
+fn sync_git(storage: &Storage, remote: &str) -> Result<(), String> {
+    let repo = GitRepo::open(&storage.file_path).map_err(|err| err.user_message())?;
+    let report = repo.sync(remote).map_err(|err| err.user_message())?;
+
+    if let Some(message) = report.committed {
+        println!("Committed: {}", message.lines().next().unwrap_or_default());
+    }
+    if report.pulled {
+        storage.export_org().map_err(|err| err.user_message())?;
+        println!("Merged changes from {}", remote);
+    } else {
+        println!("Already up to date with {}", remote);
+    }
+    for conflict in &report.conflicts {
+        println!("Conflict: {}", conflict);
+    }
+    if report.pushed {
+        println!("Pushed to {}", remote);
+    }
+    Ok(())
+}
+
+/// Merges `theirs` into `ours` as git expects: the result replaces `ours`.
+fn merge_driver(base: &str, ours: &str, theirs: &str) -> Result<(), String> {
+    let read = |path: &str| {
+        fs::read_to_string(path).map_err(|err| {
+            AppError::LoadFailed {
+                path: path.to_string(),
+                reason: err.to_string(),
+            }
+            .user_message()
+        })
+    };
+
+    let outcome = merge_task_files(&read(base)?, &read(ours)?, &read(theirs)?)
+        .map_err(|err| err.user_message())?;
+    for conflict in &outcome.conflicts {
+        eprintln!("{}{}", CONFLICT_PREFIX, conflict);
+    }
+
+    Storage::new(ours.into())
+        .write(&outcome.tasks)
+        .map_err(|err| err.user_message())
+}
@@ -1,6 +1,12 @@
 pub mod error;
 pub mod events;
+pub mod formats;
+pub mod pomodoro;
+pub mod quick_add;
+pub mod reminders;
+pub mod report;
 pub mod storage;
+pub mod sync;
 pub mod task;
 pub mod validation;
 
@@ -1,5 +1,6 @@
 use std::time::{Duration, Instant};
 
+use time::Date;
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -30,9 +31,66 @@ pub enum AppError {
     InvalidDescription {
         reason: String,
     },
+    PastDueDate {
+        date: Date,
+    },
+    InvalidStatusTransition {
+        from: String,
+        to: String,
+    },
+    InvalidGroup {
+        reason: String,
+    },
+    InvalidTag {
+        reason: String,
+    },
+    InvalidPriority {
+        input: String,
+    },
     TaskNotFound {
         id: Uuid,
     },
+    DependencyCycle {
+        titles: Vec<String>,
+    },
+    InvalidDuration {
+        input: String,
+        reason: String,
+    },
+    InvalidEstimate {
+        input: String,
+    },
+    InvalidReminder {
+        input: String,
+        reason: String,
+    },
+    ReminderCommandFailed {
+        command: String,
+        reason: String,
+    },
+
+    // Import Errors
+    MalformedLine {
+        format: String,
+        line: usize,
+        reason: String,
+    },
+    UnknownColumn {
+        name: String,
+        known: Vec<&'static str>,
+    },
+    ImportUnsupported {
+        format: String,
+    },
+    ExportFailed {
+        path: String,
+        reason: String,
+    },
+
+    // Sync Errors
+    SyncFailed {
+        reason: String,
+    },
 
     // System Errors
     TerminalUnavailable,
@@ -54,7 +112,22 @@ impl AppError {
             AppError::EmptyTaskTitle
             | AppError::InvalidTitle { .. }
             | AppError::InvalidDate { .. }
-            | AppError::InvalidDescription { .. } => ErrorSeverity::Warning,
+            | AppError::InvalidDescription { .. }
+            | AppError::InvalidGroup { .. }
+            | AppError::InvalidTag { .. }
+            | AppError::InvalidPriority { .. }
+            | AppError::PastDueDate { .. }
+            | AppError::InvalidStatusTransition { .. }
+            | AppError::DependencyCycle { .. }
+            | AppError::InvalidDuration { .. }
+            | AppError::InvalidEstimate { .. }
+            | AppError::InvalidReminder { .. }
+            | AppError::ReminderCommandFailed { .. }
+            | AppError::MalformedLine { .. }
+            | AppError::UnknownColumn { .. }
+            | AppError::ImportUnsupported { .. }
+            | AppError::ExportFailed { .. }
+            | AppError::SyncFailed { .. } => ErrorSeverity::Warning,
             AppError::SaveFailed { .. }
             | AppError::LoadFailed { .. }
             | AppError::FilePermissions { .. } => ErrorSeverity::Error,
@@ -95,9 +168,65 @@ impl AppError {
             AppError::InvalidDescription { reason } => {
                 format!("Invalid description: {}", reason)
             }
+            AppError::PastDueDate { date } => {
+                format!("Due date {} is already in the past", date)
+            }
+            AppError::InvalidStatusTransition { from, to } => {
+                format!("Cannot move a task from {} to {}", from, to)
+            }
+            AppError::InvalidGroup { reason } => {
+                format!("Invalid group: {}", reason)
+            }
+            AppError::InvalidTag { reason } => {
+                format!("Invalid tag: {}", reason)
+            }
+            AppError::InvalidPriority { input } => {
+                format!("Invalid priority '{}'. Expected high, medium or low", input)
+            }
             AppError::TaskNotFound { id } => {
                 format!("Task not found: {}", id)
             }
+            AppError::DependencyCycle { titles } => {
+                format!("Dependency cycle: {}", titles.join(" → "))
+            }
+            AppError::InvalidDuration { input, reason } => {
+                format!("Invalid duration '{}': {}", input, reason)
+            }
+            AppError::InvalidEstimate { input } => {
+                format!(
+                    "Invalid estimate '{}'. Expected e.g. '45m', '1h30m' or '3pt'",
+                    input
+                )
+            }
+            AppError::InvalidReminder { input, reason } => {
+                format!("Invalid reminder '{}': {}", input, reason)
+            }
+            AppError::ReminderCommandFailed { command, reason } => {
+                format!("Reminder command '{}' failed: {}", command, reason)
+            }
+            AppError::MalformedLine {
+                format,
+                line,
+                reason,
+            } => {
+                format!("{} line {}: {}", format, line, reason)
+            }
+            AppError::UnknownColumn { name, known } => {
+                format!(
+                    "Unknown column '{}'. Expected one of: {}",
+                    name,
+                    known.join(", ")
+                )
+            }
+            AppError::ImportUnsupported { format } => {
+                format!("{} files can only be exported", format)
+            }
+            AppError::ExportFailed { path, reason } => {
+                format!("Failed to export tasks to {}: {}", path, reason)
+            }
+            AppError::SyncFailed { reason } => {
+                format!("Sync failed: {}", reason)
+            }
             AppError::TerminalUnavailable => {
                 "Terminal is not available - cannot continue".to_string()
             }
@@ -121,6 +250,8 @@ impl AppError {
                 | AppError::LoadFailed { .. }
                 | AppError::ConfigDirCreation(_)
                 | AppError::FilePermissions { .. }
+                | AppError::ExportFailed { .. }
+                | AppError::SyncFailed { .. }
         )
     }
 
@@ -130,6 +261,13 @@ impl AppError {
             AppError::EmptyTaskTitle
                 | AppError::InvalidDate { .. }
                 | AppError::InvalidDescription { .. }
+                | AppError::InvalidGroup { .. }
+                | AppError::InvalidTag { .. }
+                | AppError::InvalidPriority { .. }
+                | AppError::DependencyCycle { .. }
+                | AppError::InvalidDuration { .. }
+                | AppError::InvalidEstimate { .. }
+                | AppError::InvalidReminder { .. }
         )
     }
 
@@ -198,10 +336,10 @@ impl ErrorState {
     }
 
     pub fn should_auto_dismiss(&self) -> bool {
-        if let (Some(error), Some(timestamp)) = (&self.current_error, self.error_timestamp) {
-            if error.severity() == ErrorSeverity::Warning {
-                return timestamp.elapsed() > Duration::from_secs(5);
-            }
+        if let (Some(error), Some(timestamp)) = (&self.current_error, self.error_timestamp)
+            && error.severity() == ErrorSeverity::Warning
+        {
+            return timestamp.elapsed() > Duration::from_secs(5);
         }
         false
     }
@@ -223,10 +361,10 @@ impl ErrorState {
     }
 
     pub fn dismiss(&mut self) {
-        if let Some(error) = &self.current_error {
-            if !error.is_fatal() {
-                self.clear_error();
-            }
+        if let Some(error) = &self.current_error
+            && !error.is_fatal()
+        {
+            self.clear_error();
         }
     }
 }
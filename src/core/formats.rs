@@ -0,0 +1,229 @@
+use std::collections::{BTreeMap, HashSet};
+
+use super::{error::AppError, task::Task};
+use crate::utils::config::Config;
+
+pub mod csv;
+pub mod icalendar;
+pub mod markdown;
+pub mod org;
+pub mod taskwarrior;
+pub mod todotxt;
+
+/// A file format tasks can be imported from and exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    TodoTxt,
+    ICalendar,
+    Markdown,
+    Csv,
+    Taskwarrior,
+    Org,
+}
+
+impl Format {
+    pub const ALL: [Format; 6] = [
+        Format::TodoTxt,
+        Format::ICalendar,
+        Format::Markdown,
+        Format::Csv,
+        Format::Taskwarrior,
+        Format::Org,
+    ];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "todotxt" | "todo.txt" => Some(Format::TodoTxt),
+            "ics" | "ical" | "icalendar" => Some(Format::ICalendar),
+            "markdown" | "md" => Some(Format::Markdown),
+            "csv" => Some(Format::Csv),
+            "taskwarrior" | "tw" => Some(Format::Taskwarrior),
+            "org" => Some(Format::Org),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::TodoTxt => "todotxt",
+            Format::ICalendar => "ics",
+            Format::Markdown => "markdown",
+            Format::Csv => "csv",
+            Format::Taskwarrior => "taskwarrior",
+            Format::Org => "org",
+        }
+    }
+
+    /// Reads every task in `input`. Fails with one error per malformed line, so
+    /// all of them can be fixed in one go.
+    pub fn import(&self, input: &str, options: &FormatOptions) -> Result<Vec<Task>, Vec<AppError>> {
+        match self {
+            Format::TodoTxt => todotxt::import(input),
+            Format::ICalendar => icalendar::import(input),
+            Format::Markdown => markdown::import(input),
+            Format::Csv => csv::import(input, &options.mapping),
+            Format::Taskwarrior => taskwarrior::import(input),
+            Format::Org => Err(vec![AppError::ImportUnsupported {
+                format: self.name().to_string(),
+            }]),
+        }
+    }
+
+    /// Whether tasks can be read from the format, not only written to it.
+    pub fn can_import(&self) -> bool {
+        !matches!(self, Format::Org)
+    }
+
+    /// Whether imported tasks also match existing ones by title. Checklists are
+    /// often typed by hand, so their items rarely carry an id.
+    pub fn matches_titles(&self) -> bool {
+        matches!(self, Format::Markdown)
+    }
+
+    /// Whether the format reads [`FormatOptions`].
+    pub fn has_options(&self) -> bool {
+        matches!(self, Format::Csv)
+    }
+
+    pub fn export(&self, tasks: &[Task], options: &FormatOptions) -> Result<String, AppError> {
+        match self {
+            Format::TodoTxt => Ok(todotxt::export(tasks)),
+            Format::ICalendar => Ok(icalendar::export(tasks)),
+            Format::Markdown => Ok(markdown::export(tasks)),
+            Format::Csv => csv::export(tasks, &options.columns),
+            Format::Taskwarrior => Ok(taskwarrior::export(tasks)),
+            Format::Org => Ok(org::export(tasks)),
+        }
+    }
+}
+
+/// Settings for formats with configurable layouts, starting from the `csv`
+/// section of the configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Columns to export, in order. Empty exports all of them.
+    pub columns: Vec<String>,
+    /// Header names on import and the column each one stands for.
+    pub mapping: BTreeMap<String, String>,
+}
+
+impl FormatOptions {
+    pub fn from_config() -> Self {
+        let csv = &Config::global().csv;
+        Self {
+            columns: csv.columns.clone(),
+            mapping: csv.mapping.clone(),
+        }
+    }
+}
+
+/// How many imported tasks were added and how many were already there.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub skipped: usize,
+}
+
+/// Adds the imported tasks to `tasks`, skipping any whose id is already present so
+/// importing the same file twice does not duplicate tasks. With `match_titles`, a
+/// task with the same title, ignoring case, counts as present too.
+pub fn merge_imported(
+    tasks: &mut Vec<Task>,
+    imported: Vec<Task>,
+    match_titles: bool,
+) -> ImportSummary {
+    let mut known: HashSet<_> = tasks.iter().map(|task| task.id).collect();
+    let mut titles: HashSet<_> = tasks.iter().map(|task| task.title.to_lowercase()).collect();
+    let mut summary = ImportSummary::default();
+
+    for task in imported {
+        let title = task.title.to_lowercase();
+        if match_titles && titles.contains(&title) {
+            summary.skipped += 1;
+        } else if known.insert(task.id) {
+            titles.insert(title);
+            tasks.push(task);
+            summary.added += 1;
+        } else {
+            summary.skipped += 1;
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(title: &str) -> Task {
+        Task::new(title.to_string(), Vec::new(), None)
+    }
+
+    fn titles(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|task| task.title.as_str()).collect()
+    }
+
+    #[test]
+    fn tasks_already_present_by_id_are_skipped() {
+        let existing = task("Write report");
+        let mut tasks = vec![existing.clone()];
+
+        let summary = merge_imported(&mut tasks, vec![existing, task("Buy milk")], false);
+
+        assert_eq!(
+            summary,
+            ImportSummary {
+                added: 1,
+                skipped: 1
+            }
+        );
+        assert_eq!(titles(&tasks), ["Write report", "Buy milk"]);
+    }
+
+    #[test]
+    fn titles_only_match_when_asked_to() {
+        let imported = || vec![task("write REPORT"), task("Buy milk")];
+
+        let mut tasks = vec![task("Write report")];
+        let summary = merge_imported(&mut tasks, imported(), false);
+        assert_eq!(
+            summary,
+            ImportSummary {
+                added: 2,
+                skipped: 0
+            }
+        );
+
+        let mut tasks = vec![task("Write report")];
+        let summary = merge_imported(&mut tasks, imported(), true);
+        assert_eq!(
+            summary,
+            ImportSummary {
+                added: 1,
+                skipped: 1
+            }
+        );
+        assert_eq!(titles(&tasks), ["Write report", "Buy milk"]);
+    }
+
+    #[test]
+    fn repeated_titles_within_one_import_are_added_once() {
+        let mut tasks = Vec::new();
+
+        let summary = merge_imported(
+            &mut tasks,
+            vec![task("Buy milk"), task("Pay rent"), task("buy milk")],
+            true,
+        );
+
+        assert_eq!(
+            summary,
+            ImportSummary {
+                added: 2,
+                skipped: 1
+            }
+        );
+        assert_eq!(titles(&tasks), ["Buy milk", "Pay rent"]);
+    }
+}
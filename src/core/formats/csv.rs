@@ -0,0 +1,736 @@
+//! Comma separated values (RFC 4180) with a header row.
+//!
+//! Export writes the chosen columns, quoting fields that contain commas, quotes
+//! or line breaks, so multi-line descriptions stay in one cell. Import matches
+//! each header against the column names, or against a mapping for files from
+//! other tools, ignores unknown columns and runs every value through the same
+//! validation as the task form.
+
+use std::collections::BTreeMap;
+
+use strum::{EnumIter, IntoEnumIterator};
+use time::{Date, macros::format_description};
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        error::AppError,
+        task::{
+            Task,
+            task::{Priority, Status},
+        },
+        validation::{DateValidator, TaskValidator},
+    },
+    utils::date::{get_today_with_fallbacks, now_timestamp},
+};
+
+const FORMAT_NAME: &str = "CSV";
+
+/// A task field as a CSV column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum Column {
+    Id,
+    Title,
+    Description,
+    Status,
+    Priority,
+    Group,
+    Tags,
+    Favorite,
+    Due,
+    Created,
+    Completed,
+    DeferUntil,
+    Estimate,
+    BlockedReason,
+    DependsOn,
+    Pomodoros,
+    /// Total tracked time in minutes. Only exported; the entries behind it are not.
+    TrackedMinutes,
+}
+
+impl Column {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::Title => "title",
+            Column::Description => "description",
+            Column::Status => "status",
+            Column::Priority => "priority",
+            Column::Group => "group",
+            Column::Tags => "tags",
+            Column::Favorite => "favorite",
+            Column::Due => "due",
+            Column::Created => "created",
+            Column::Completed => "completed",
+            Column::DeferUntil => "deferUntil",
+            Column::Estimate => "estimate",
+            Column::BlockedReason => "blockedReason",
+            Column::DependsOn => "dependsOn",
+            Column::Pomodoros => "pomodoros",
+            Column::TrackedMinutes => "trackedMinutes",
+        }
+    }
+
+    /// Matches a column name ignoring case, spaces, dashes and underscores, so
+    /// `Due`, `defer until` and `blocked_reason` are all understood.
+    pub fn parse(name: &str) -> Option<Self> {
+        let key: String = name
+            .chars()
+            .filter(|ch| !matches!(ch, ' ' | '-' | '_'))
+            .collect::<String>()
+            .to_lowercase();
+
+        Column::iter().find(|column| column.name().to_lowercase() == key)
+    }
+
+    fn parse_required(name: &str) -> Result<Self, AppError> {
+        Self::parse(name).ok_or_else(|| AppError::UnknownColumn {
+            name: name.to_string(),
+            known: Column::iter().map(|column| column.name()).collect(),
+        })
+    }
+
+    fn value(&self, task: &Task) -> String {
+        match self {
+            Column::Id => task.id.to_string(),
+            Column::Title => task.title.clone(),
+            Column::Description => task.description.join("\n"),
+            Column::Status => task.status.label().to_string(),
+            Column::Priority => task
+                .priority
+                .map(|priority| priority.to_string().to_lowercase())
+                .unwrap_or_default(),
+            Column::Group => task.group.clone().unwrap_or_default(),
+            Column::Tags => task.tags.join(" "),
+            Column::Favorite => if task.is_favorite { "yes" } else { "" }.to_string(),
+            Column::Due => format_date(task.due_date),
+            Column::Created => format_date(Some(task.created_at)),
+            Column::Completed => format_date(task.completed_at),
+            Column::DeferUntil => format_date(task.defer_until),
+            Column::Estimate => task
+                .estimate
+                .map(|estimate| estimate.compact())
+                .unwrap_or_default(),
+            Column::BlockedReason => task.blocked_reason.clone().unwrap_or_default(),
+            Column::DependsOn => task
+                .depends_on
+                .iter()
+                .map(Uuid::to_string)
+                .collect::<Vec<_>>()
+                .join(" "),
+            Column::Pomodoros => task.pomodoros.to_string(),
+            Column::TrackedMinutes => task
+                .tracked_time(now_timestamp())
+                .whole_minutes()
+                .to_string(),
+        }
+    }
+}
+
+/// Writes `columns` for every task, or all columns when none are given.
+pub fn export(tasks: &[Task], columns: &[String]) -> Result<String, AppError> {
+    let columns: Vec<Column> = if columns.is_empty() {
+        Column::iter().collect()
+    } else {
+        columns
+            .iter()
+            .map(|name| Column::parse_required(name))
+            .collect::<Result<_, _>>()?
+    };
+
+    let mut output = write_record(columns.iter().map(|column| column.name().to_string()));
+    for task in tasks {
+        output.push_str(&write_record(
+            columns.iter().map(|column| column.value(task)),
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Reads tasks from `input`. `mapping` names the column behind headers that are
+/// not column names themselves; headers matching neither are skipped.
+pub fn import(input: &str, mapping: &BTreeMap<String, String>) -> Result<Vec<Task>, Vec<AppError>> {
+    let malformed = |line: usize, reason: String| AppError::MalformedLine {
+        format: FORMAT_NAME.to_string(),
+        line,
+        reason,
+    };
+
+    let records = parse_records(input.trim_start_matches('\u{feff}'))
+        .map_err(|(line, reason)| vec![malformed(line, reason)])?;
+    let Some(((header_line, header), rows)) = records.split_first() else {
+        return Ok(Vec::new());
+    };
+
+    let columns = map_header(header, mapping).map_err(|error| vec![error])?;
+    if !columns.contains(&Some(Column::Title)) {
+        return Err(vec![malformed(
+            *header_line,
+            "no column holds the title; add a mapping for it".to_string(),
+        )]);
+    }
+
+    let today = get_today_with_fallbacks().0;
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+
+    for (line, row) in rows {
+        if row.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+
+        match parse_row(&columns, row, today) {
+            Ok(task) => tasks.push(task),
+            Err(reason) => errors.push(malformed(*line, reason)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(tasks)
+    } else {
+        Err(errors)
+    }
+}
+
+/// The column behind each header, using `mapping` first. Mapping keys match
+/// headers ignoring case.
+fn map_header(
+    header: &[String],
+    mapping: &BTreeMap<String, String>,
+) -> Result<Vec<Option<Column>>, AppError> {
+    header
+        .iter()
+        .map(|name| {
+            let mapped = mapping
+                .iter()
+                .find(|(from, _)| from.trim().eq_ignore_ascii_case(name.trim()));
+
+            match mapped {
+                Some((_, column)) => Column::parse_required(column).map(Some),
+                None => Ok(Column::parse(name.trim())),
+            }
+        })
+        .collect()
+}
+
+/// Builds a task from one row, failing on the first value the validation rejects.
+fn parse_row(columns: &[Option<Column>], row: &[String], today: Date) -> Result<Task, String> {
+    let mut task = Task::new(String::new(), Vec::new(), None);
+    let mut status = None;
+    let mut completed_at = None;
+    let mut blocked_reason = None;
+
+    for (column, value) in columns.iter().zip(row) {
+        let Some(column) = column else {
+            continue;
+        };
+        let value = value.trim();
+        let reason = |error: AppError| format!("{}: {}", column.name(), error.user_message());
+
+        match column {
+            Column::Title => task.title = TaskValidator::validate_title(value).map_err(reason)?,
+            Column::Description => {
+                let lines: Vec<String> = value.lines().map(str::to_string).collect();
+                task.description = TaskValidator::validate_description(&lines).map_err(reason)?;
+            }
+            _ if value.is_empty() => {}
+            Column::Id => {
+                task.id = Uuid::parse_str(value)
+                    .map_err(|_| format!("id: '{}' is not a valid id", value))?
+            }
+            Column::Status => {
+                status = Some(
+                    Status::parse(value)
+                        .ok_or_else(|| format!("status: unknown status '{}'", value))?,
+                )
+            }
+            Column::Priority => {
+                task.priority = Some(Priority::parse(value).ok_or_else(|| {
+                    reason(AppError::InvalidPriority {
+                        input: value.to_string(),
+                    })
+                })?)
+            }
+            Column::Group => {
+                task.group = Some(TaskValidator::validate_group(value).map_err(reason)?)
+            }
+            Column::Tags => {
+                for tag in value.split([',', ' ']).filter(|tag| !tag.is_empty()) {
+                    let tag = TaskValidator::validate_tag(tag).map_err(reason)?;
+                    if !task.tags.contains(&tag) {
+                        task.tags.push(tag);
+                    }
+                }
+            }
+            Column::Favorite => {
+                task.is_favorite = match value.to_lowercase().as_str() {
+                    "yes" | "y" | "true" | "1" | "x" => true,
+                    "no" | "n" | "false" | "0" => false,
+                    _ => return Err(format!("favorite: expected yes or no, found '{}'", value)),
+                }
+            }
+            Column::Due => task.due_date = Some(parse_date(value).map_err(reason)?),
+            Column::Created => task.created_at = parse_date(value).map_err(reason)?,
+            Column::Completed => completed_at = Some(parse_date(value).map_err(reason)?),
+            Column::DeferUntil => task.defer_until = Some(parse_date(value).map_err(reason)?),
+            Column::Estimate => {
+                task.estimate = TaskValidator::validate_estimate(value).map_err(reason)?
+            }
+            Column::BlockedReason => blocked_reason = Some(value.to_string()),
+            Column::DependsOn => {
+                task.depends_on = value
+                    .split([',', ' '])
+                    .filter(|id| !id.is_empty())
+                    .map(|id| {
+                        Uuid::parse_str(id)
+                            .map_err(|_| format!("dependsOn: '{}' is not a valid id", id))
+                    })
+                    .collect::<Result<_, _>>()?
+            }
+            Column::Pomodoros => {
+                task.pomodoros = value
+                    .parse()
+                    .map_err(|_| format!("pomodoros: expected a number, found '{}'", value))?
+            }
+            Column::TrackedMinutes => {}
+        }
+    }
+
+    if task.title.is_empty() {
+        return Err(AppError::EmptyTaskTitle.user_message());
+    }
+
+    // Without a status, a completion date is enough to count the task as done
+    task.status = match status {
+        Some(status) => status,
+        None if completed_at.is_some() => Status::Completed,
+        None => Status::Todo,
+    };
+    task.completed_at = completed_at;
+    if task.status == Status::Completed && task.completed_at.is_none() {
+        task.completed_at = Some(today);
+    }
+    if task.status == Status::Blocked {
+        task.blocked_reason = blocked_reason;
+    }
+
+    Ok(task)
+}
+
+/// Dates may be ISO or anything the due date field understands, such as `fri`.
+fn parse_date(value: &str) -> Result<Date, AppError> {
+    DateValidator::resolve_date_input(value)
+}
+
+fn format_date(date: Option<Date>) -> String {
+    date.and_then(|date| {
+        date.format(format_description!("[year]-[month]-[day]"))
+            .ok()
+    })
+    .unwrap_or_default()
+}
+
+/// One record ending in CRLF, quoting fields that need it.
+fn write_record(fields: impl Iterator<Item = String>) -> String {
+    let fields: Vec<String> = fields
+        .map(|field| {
+            if field.contains([',', '"', '\r', '\n']) || field.trim() != field {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect();
+
+    format!("{}\r\n", fields.join(","))
+}
+
+/// A record's fields and the line it starts on.
+type Record = (usize, Vec<String>);
+
+/// Splits `input` into records of fields, each with the line it starts on.
+/// Quoted fields may hold commas, doubled quotes and line breaks.
+fn parse_records(input: &str) -> Result<Vec<Record>, (usize, String)> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut quote_line = None;
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if quote_line.is_some() {
+            match ch {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quote_line = None,
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' => {
+                    line += 1;
+                    field.push('\n');
+                }
+                ch => field.push(ch),
+            }
+            continue;
+        }
+
+        match ch {
+            '"' if field.is_empty() => quote_line = Some(line),
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push((record_line, std::mem::take(&mut record)));
+                line += 1;
+                record_line = line;
+            }
+            ch => field.push(ch),
+        }
+    }
+
+    if let Some(quote_line) = quote_line {
+        return Err((quote_line, "quoted field is never closed".to_string()));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((record_line, record));
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+    use crate::core::task::task::Estimate;
+
+    const TODAY: Date = date!(2026 - 10 - 18);
+
+    fn fields(records: &[Record]) -> Vec<Vec<&str>> {
+        records
+            .iter()
+            .map(|(_, record)| record.iter().map(String::as_str).collect())
+            .collect()
+    }
+
+    fn columns(names: &[&str]) -> Vec<Option<Column>> {
+        names.iter().map(|name| Column::parse(name)).collect()
+    }
+
+    fn row(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn error_lines(errors: Vec<AppError>) -> Vec<usize> {
+        errors
+            .into_iter()
+            .map(|error| match error {
+                AppError::MalformedLine { line, .. } => line,
+                other => panic!("unexpected error {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn quoted_fields_hold_commas_quotes_and_empty_values() {
+        let records =
+            parse_records("title,tags\r\n\"Pay rent, water\",\"say \"\"hi\"\"\"\r\n,\r\n\"\",x")
+                .unwrap();
+
+        assert_eq!(
+            fields(&records),
+            [
+                vec!["title", "tags"],
+                vec!["Pay rent, water", "say \"hi\""],
+                vec!["", ""],
+                vec!["", "x"],
+            ]
+        );
+    }
+
+    #[test]
+    fn quotes_inside_an_unquoted_field_are_literal() {
+        let records = parse_records("5\" nails,a\"b\"\n").unwrap();
+        assert_eq!(fields(&records), [vec!["5\" nails", "a\"b\""]]);
+    }
+
+    #[test]
+    fn multi_line_cells_keep_their_lines_and_later_records_their_line_numbers() {
+        let input =
+            "title,description\n\"Report\",\"Outline first.\r\n\r\nThen numbers.\"\nNext,\n";
+        let records = parse_records(input).unwrap();
+
+        assert_eq!(
+            fields(&records),
+            [
+                vec!["title", "description"],
+                vec!["Report", "Outline first.\n\nThen numbers."],
+                vec!["Next", ""],
+            ]
+        );
+        let lines: Vec<usize> = records.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, [1, 2, 5]);
+    }
+
+    #[test]
+    fn an_unclosed_quote_is_reported_where_it_opens() {
+        assert_eq!(
+            parse_records("title\nfine\n\"never\nclosed\n")
+                .unwrap_err()
+                .0,
+            3
+        );
+    }
+
+    #[test]
+    fn write_record_quotes_only_fields_that_need_it() {
+        let record = write_record(
+            ["plain", "a, b", "say \"hi\"", "two\nlines", " padded", ""]
+                .into_iter()
+                .map(String::from),
+        );
+
+        assert_eq!(
+            record,
+            "plain,\"a, b\",\"say \"\"hi\"\"\",\"two\nlines\",\" padded\",\r\n"
+        );
+    }
+
+    #[test]
+    fn written_records_parse_back_unchanged() {
+        let values = ["", "a,b", "\"", "\"\"", "line\r\nbreak", "  both  ", "é,ü"];
+        let record = write_record(values.iter().map(|value| value.to_string()));
+        let records = parse_records(&record).unwrap();
+
+        // Line breaks inside a cell are read back as plain newlines
+        let expected: Vec<String> = values
+            .iter()
+            .map(|value| value.replace("\r\n", "\n"))
+            .collect();
+        assert_eq!(records, [(1, expected)]);
+    }
+
+    #[test]
+    fn parse_row_reads_every_column() {
+        let id = Uuid::new_v4();
+        let dependency = Uuid::new_v4();
+        let task = parse_row(
+            &columns(&[
+                "id",
+                "Title",
+                "description",
+                "status",
+                "priority",
+                "group",
+                "tags",
+                "favorite",
+                "due",
+                "created",
+                "defer until",
+                "estimate",
+                "blocked_reason",
+                "depends-on",
+                "pomodoros",
+                "trackedMinutes",
+            ]),
+            &row(&[
+                &id.to_string(),
+                " Write report ",
+                "Outline first.\n\nThen numbers.",
+                "blocked",
+                "H",
+                "work",
+                "urgent, office urgent",
+                "yes",
+                "2026-11-01",
+                "2026-10-01",
+                "2026-10-20",
+                "90m",
+                "Waiting on data",
+                &dependency.to_string(),
+                "3",
+                "45",
+            ]),
+            TODAY,
+        )
+        .unwrap();
+
+        assert_eq!(task.id, id);
+        assert_eq!(task.title, "Write report");
+        assert_eq!(task.description, ["Outline first.", "", "Then numbers."]);
+        assert_eq!(task.status, Status::Blocked);
+        assert_eq!(task.priority, Some(Priority::High));
+        assert_eq!(task.group.as_deref(), Some("work"));
+        assert_eq!(task.tags, ["urgent", "office"]);
+        assert!(task.is_favorite);
+        assert_eq!(task.due_date, Some(date!(2026 - 11 - 01)));
+        assert_eq!(task.created_at, date!(2026 - 10 - 01));
+        assert_eq!(task.defer_until, Some(date!(2026 - 10 - 20)));
+        assert_eq!(task.estimate, Some(Estimate::Minutes(90)));
+        assert_eq!(task.blocked_reason.as_deref(), Some("Waiting on data"));
+        assert_eq!(task.depends_on, [dependency]);
+        assert_eq!(task.pomodoros, 3);
+        assert!(task.time_entries.is_empty());
+    }
+
+    #[test]
+    fn parse_row_fills_in_status_and_completion() {
+        let columns = columns(&["title", "status", "completed", "blockedReason"]);
+        let parse = |values: &[&str]| parse_row(&columns, &row(values), TODAY).unwrap();
+
+        let task = parse(&["Done by date", "", "2026-10-10", ""]);
+        assert_eq!(task.status, Status::Completed);
+        assert_eq!(task.completed_at, Some(date!(2026 - 10 - 10)));
+
+        let task = parse(&["Done today", "done", "", ""]);
+        assert_eq!(task.completed_at, Some(TODAY));
+
+        let task = parse(&["Open", "", "", "Not blocked"]);
+        assert_eq!(task.status, Status::Todo);
+        assert_eq!(task.blocked_reason, None);
+
+        // Values in unknown columns are skipped, and missing cells count as empty
+        let task = parse_row(&[None, Some(Column::Title)], &row(&["ignored"]), TODAY);
+        assert!(task.is_err());
+    }
+
+    #[test]
+    fn parse_row_rejects_invalid_values() {
+        for (column, value) in [
+            ("id", "not-an-id"),
+            ("status", "someday"),
+            ("priority", "urgent"),
+            ("group", "two words"),
+            ("favorite", "maybe"),
+            ("due", "2026-13-01"),
+            ("estimate", "lots"),
+            ("dependsOn", "abc"),
+            ("pomodoros", "-1"),
+        ] {
+            let error =
+                parse_row(&columns(&["title", column]), &row(&["Task", value]), TODAY).unwrap_err();
+            assert!(error.starts_with(column), "{column}: {error}");
+        }
+
+        assert!(parse_row(&columns(&["title"]), &row(&["  "]), TODAY).is_err());
+        assert!(parse_row(&columns(&["description"]), &row(&["No title"]), TODAY).is_err());
+    }
+
+    #[test]
+    fn import_strips_a_byte_order_mark_and_skips_blank_rows() {
+        let tasks = import(
+            "\u{feff}title,due\r\nPay rent,2026-11-01\r\n,\r\n\r\n",
+            &BTreeMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Pay rent");
+        assert_eq!(tasks[0].due_date, Some(date!(2026 - 11 - 01)));
+        assert!(import("", &BTreeMap::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn import_maps_headers_from_other_tools() {
+        let mapping = BTreeMap::from([
+            ("Task Name".to_string(), "title".to_string()),
+            ("notes".to_string(), "description".to_string()),
+        ]);
+        let input = "TASK NAME,Notes,Due,Unknown\nWrite report,\"Outline\nNumbers\",2026-11-01,x\n";
+
+        let tasks = import(input, &mapping).unwrap();
+
+        assert_eq!(tasks[0].title, "Write report");
+        assert_eq!(tasks[0].description, ["Outline", "Numbers"]);
+        assert_eq!(tasks[0].due_date, Some(date!(2026 - 11 - 01)));
+    }
+
+    #[test]
+    fn import_needs_a_title_column_and_known_mapping_targets() {
+        let errors = import("name,due\nWrite report,\n", &BTreeMap::new()).unwrap_err();
+        assert_eq!(error_lines(errors), [1]);
+
+        let mapping = BTreeMap::from([("name".to_string(), "heading".to_string())]);
+        let errors = import("name\nWrite report\n", &mapping).unwrap_err();
+        assert!(matches!(&errors[..], [AppError::UnknownColumn { name, .. }] if name == "heading"));
+    }
+
+    #[test]
+    fn import_reports_every_invalid_row_by_its_first_line() {
+        let input = "title,description,priority\n\
+                     Fine,,low\n\
+                     Bad,\"spans\ntwo lines\",urgent\n\
+                     ,,high\n\
+                     Also bad,,0\n";
+
+        let errors = import(input, &BTreeMap::new()).unwrap_err();
+
+        assert_eq!(error_lines(errors), [3, 5, 6]);
+    }
+
+    #[test]
+    fn export_and_import_keep_every_field() {
+        let mut task = Task::new(
+            "Pay rent, water and \"power\"".to_string(),
+            vec![
+                "First line".to_string(),
+                String::new(),
+                " indented".to_string(),
+            ],
+            Some(date!(2026 - 11 - 01)),
+        );
+        task.created_at = date!(2026 - 10 - 01);
+        task.status = Status::Blocked;
+        task.blocked_reason = Some("Waiting, still".to_string());
+        task.priority = Some(Priority::Low);
+        task.group = Some("home".to_string());
+        task.tags = vec!["bills".to_string(), "monthly".to_string()];
+        task.is_favorite = true;
+        task.defer_until = Some(date!(2026 - 10 - 25));
+        task.estimate = Some(Estimate::Points(3));
+        task.depends_on = vec![Uuid::new_v4()];
+        task.pomodoros = 2;
+
+        let exported = export(std::slice::from_ref(&task), &[]).unwrap();
+        let imported = import(&exported, &BTreeMap::new()).unwrap();
+
+        assert_eq!(imported.len(), 1);
+        let read = &imported[0];
+        assert_eq!(read.id, task.id);
+        assert_eq!(read.title, task.title);
+        assert_eq!(read.description, task.description);
+        assert_eq!(read.status, task.status);
+        assert_eq!(read.blocked_reason, task.blocked_reason);
+        assert_eq!(read.priority, task.priority);
+        assert_eq!(read.group, task.group);
+        assert_eq!(read.tags, task.tags);
+        assert_eq!(read.is_favorite, task.is_favorite);
+        assert_eq!(read.due_date, task.due_date);
+        assert_eq!(read.created_at, task.created_at);
+        assert_eq!(read.completed_at, task.completed_at);
+        assert_eq!(read.defer_until, task.defer_until);
+        assert_eq!(read.estimate, task.estimate);
+        assert_eq!(read.depends_on, task.depends_on);
+        assert_eq!(read.pomodoros, task.pomodoros);
+    }
+
+    #[test]
+    fn export_writes_chosen_columns_and_rejects_unknown_ones() {
+        let task = Task::new("Buy milk".to_string(), Vec::new(), None);
+        let columns = ["Title".to_string(), "due".to_string()];
+        assert_eq!(
+            export(std::slice::from_ref(&task), &columns).unwrap(),
+            "title,due\r\nBuy milk,\r\n"
+        );
+
+        let error = export(&[task], &["heading".to_string()]).unwrap_err();
+        assert!(matches!(error, AppError::UnknownColumn { name, .. } if name == "heading"));
+    }
+}
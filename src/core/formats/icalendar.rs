@@ -0,0 +1,570 @@
+//! iCalendar (RFC 5545) files with one VTODO component per task.
+//!
+//! Tasks are written with `UID`, `SUMMARY`, `DESCRIPTION`, `DUE`, `STATUS`,
+//! `COMPLETED`, `PRIORITY` and `CATEGORIES` (the tags), plus `X-DELIBIRD-GROUP`
+//! and `X-DELIBIRD-STATUS` for what iCalendar has no property for. Importing
+//! reads the same properties from every VTODO and ignores other components.
+//! UIDs that are not UUIDs, as written by other apps, are turned into a stable
+//! UUID so importing the same file twice still finds the existing tasks.
+
+use time::{Date, OffsetDateTime, UtcOffset, macros::format_description};
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        error::AppError,
+        task::{
+            Task,
+            task::{Priority, Status},
+        },
+        validation::TaskValidator,
+    },
+    utils::date::{get_today_with_fallbacks, now_timestamp},
+};
+
+const FORMAT_NAME: &str = "iCalendar";
+
+/// Longest content line in octets before it is folded, not counting the line break.
+const MAX_LINE_OCTETS: usize = 75;
+
+pub fn export(tasks: &[Task]) -> String {
+    let stamp = format_date_time(now_timestamp());
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//delibird//delibird//EN".to_string(),
+    ];
+
+    for task in tasks {
+        lines.push("BEGIN:VTODO".to_string());
+        lines.push(format!("UID:{}", task.id));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!(
+            "CREATED:{}",
+            format_date_time(midnight(task.created_at))
+        ));
+        lines.push(format!("SUMMARY:{}", escape_text(&task.title)));
+
+        if !task.description.is_empty() {
+            lines.push(format!(
+                "DESCRIPTION:{}",
+                escape_text(&task.description.join("\n"))
+            ));
+        }
+        if let Some(due_date) = task.due_date {
+            lines.push(format!("DUE;VALUE=DATE:{}", format_date(due_date)));
+        }
+
+        lines.push(format!("STATUS:{}", status_value(task.status)));
+        if task.status == Status::Blocked {
+            lines.push("X-DELIBIRD-STATUS:BLOCKED".to_string());
+        }
+        if task.status == Status::Completed
+            && let Some(completed_at) = task.completed_at
+        {
+            lines.push(format!(
+                "COMPLETED:{}",
+                format_date_time(midnight(completed_at))
+            ));
+        }
+        if let Some(priority) = task.priority {
+            lines.push(format!("PRIORITY:{}", priority_value(priority)));
+        }
+        if !task.tags.is_empty() {
+            let tags: Vec<String> = task.tags.iter().map(|tag| escape_text(tag)).collect();
+            lines.push(format!("CATEGORIES:{}", tags.join(",")));
+        }
+        if let Some(group) = &task.group {
+            lines.push(format!("X-DELIBIRD-GROUP:{}", escape_text(group)));
+        }
+
+        lines.push("END:VTODO".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+pub fn import(input: &str) -> Result<Vec<Task>, Vec<AppError>> {
+    let today = get_today_with_fallbacks().0;
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+    let malformed = |line: usize, reason: String| AppError::MalformedLine {
+        format: FORMAT_NAME.to_string(),
+        line,
+        reason,
+    };
+
+    // The VTODO being read: the line it began on and its properties
+    let mut todo: Option<(usize, Vec<ContentLine>)> = None;
+    // Components nested inside the VTODO, such as VALARM, are skipped
+    let mut nested = 0;
+
+    for (line_number, line) in unfold(input) {
+        let content = match ContentLine::parse(&line, line_number) {
+            Ok(content) => content,
+            Err(reason) => {
+                errors.push(malformed(line_number, reason));
+                continue;
+            }
+        };
+
+        match (content.name.as_str(), &mut todo) {
+            ("BEGIN", None) if content.value.eq_ignore_ascii_case("VTODO") => {
+                todo = Some((line_number, Vec::new()));
+            }
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some((start, properties))) if content.value.eq_ignore_ascii_case("VTODO") => {
+                match parse_todo(properties, today) {
+                    Ok(task) => tasks.push(task),
+                    Err((line, reason)) => errors.push(malformed(line.unwrap_or(*start), reason)),
+                }
+                todo = None;
+            }
+            (_, Some(_)) if nested > 0 => {}
+            (_, Some((_, properties))) => properties.push(content),
+            (_, None) => {}
+        }
+    }
+
+    if let Some((start, _)) = todo {
+        errors.push(malformed(
+            start,
+            "VTODO is never closed with END:VTODO".to_string(),
+        ));
+    }
+
+    if errors.is_empty() {
+        Ok(tasks)
+    } else {
+        Err(errors)
+    }
+}
+
+/// One property: `NAME;PARAM=VALUE:value`, with its line number for errors.
+/// Parameters are not needed for the properties read and are dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ContentLine {
+    name: String,
+    value: String,
+    line: usize,
+}
+
+impl ContentLine {
+    fn parse(line: &str, line_number: usize) -> Result<Self, String> {
+        // The value starts at the first colon outside a quoted parameter value
+        let mut in_quotes = false;
+        let colon = line
+            .char_indices()
+            .find(|&(_, ch)| {
+                if ch == '"' {
+                    in_quotes = !in_quotes;
+                }
+                ch == ':' && !in_quotes
+            })
+            .map(|(index, _)| index)
+            .ok_or_else(|| format!("expected NAME:value, found '{}'", line))?;
+
+        let (head, value) = (&line[..colon], &line[colon + 1..]);
+        let name = head
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_uppercase();
+        if name.is_empty() {
+            return Err(format!("missing property name in '{}'", line));
+        }
+
+        Ok(Self {
+            name,
+            value: value.to_string(),
+            line: line_number,
+        })
+    }
+}
+
+/// Builds a task from a VTODO's properties. Errors carry the line of the property
+/// at fault, or `None` for problems with the component as a whole.
+fn parse_todo(properties: &[ContentLine], today: Date) -> Result<Task, (Option<usize>, String)> {
+    let mut task = Task::new(String::new(), Vec::new(), None);
+    let mut title = None;
+    let mut blocked = false;
+    let mut completed_at = None;
+    let mut status = None;
+
+    for property in properties {
+        let at_line = |reason: String| (Some(property.line), reason);
+        let value = property.value.as_str();
+
+        match property.name.as_str() {
+            "UID" => task.id = parse_uid(value),
+            "SUMMARY" => {
+                title = Some(
+                    TaskValidator::validate_title(&unescape_text(value))
+                        .map_err(|error| at_line(error.user_message()))?,
+                )
+            }
+            "DESCRIPTION" => {
+                task.description = unescape_text(value).lines().map(str::to_string).collect();
+            }
+            "DUE" => task.due_date = Some(parse_date(value).map_err(at_line)?),
+            "CREATED" => task.created_at = parse_date(value).map_err(at_line)?,
+            "COMPLETED" => completed_at = Some(parse_date(value).map_err(at_line)?),
+            "STATUS" => {
+                status = Some(
+                    parse_status(value)
+                        .ok_or_else(|| at_line(format!("unknown status '{}'", value)))?,
+                )
+            }
+            "X-DELIBIRD-STATUS" => blocked = value.eq_ignore_ascii_case("BLOCKED"),
+            "PRIORITY" => {
+                let level: u8 = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| at_line(format!("invalid priority '{}'", value)))?;
+                task.priority = parse_priority(level);
+            }
+            "CATEGORIES" => {
+                for category in split_list(value) {
+                    // Categories from other apps may contain spaces, which tags cannot
+                    let category = category.split_whitespace().collect::<Vec<_>>().join("-");
+                    if category.is_empty() {
+                        continue;
+                    }
+                    let tag = TaskValidator::validate_tag(&category)
+                        .map_err(|error| at_line(error.user_message()))?;
+                    if !task.tags.contains(&tag) {
+                        task.tags.push(tag);
+                    }
+                }
+            }
+            "X-DELIBIRD-GROUP" => {
+                task.group = Some(
+                    TaskValidator::validate_group(&unescape_text(value))
+                        .map_err(|error| at_line(error.user_message()))?,
+                )
+            }
+            _ => {}
+        }
+    }
+
+    task.title = title.ok_or((None, "VTODO has no SUMMARY".to_string()))?;
+
+    // Without a STATUS, a completion date is enough to count the task as done
+    task.status = match status {
+        Some(Status::Todo) if blocked => Status::Blocked,
+        Some(status) => status,
+        None if completed_at.is_some() => Status::Completed,
+        None => Status::Todo,
+    };
+    if task.status == Status::Completed {
+        task.completed_at = Some(completed_at.unwrap_or(today));
+    }
+
+    Ok(task)
+}
+
+/// Joins folded lines back together. Yields each logical line with the number of
+/// the physical line it starts on.
+fn unfold(input: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        match line.strip_prefix([' ', '\t']) {
+            Some(continuation) if !lines.is_empty() => {
+                if let Some((_, last)) = lines.last_mut() {
+                    last.push_str(continuation);
+                }
+            }
+            _ if line.trim().is_empty() => {}
+            _ => lines.push((index + 1, line.to_string())),
+        }
+    }
+
+    lines
+}
+
+/// Splits a line longer than 75 octets into a first line and continuation lines
+/// starting with a space, never inside a character. Ends every line with CRLF.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+
+    for ch in line.chars() {
+        let len = ch.len_utf8();
+        if width + len > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the continuation line's length
+            width = 1;
+        }
+        folded.push(ch);
+        width += len;
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            ch => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            unescaped.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+/// Splits a comma separated list of text values, leaving escaped commas alone.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                current.push(ch);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            ',' => items.push(unescape_text(&std::mem::take(&mut current))),
+            ch => current.push(ch),
+        }
+    }
+    items.push(unescape_text(&current));
+
+    items
+}
+
+/// Keeps a UUID as it is and derives a stable one from any other UID.
+fn parse_uid(value: &str) -> Uuid {
+    let value = value.trim();
+    Uuid::parse_str(value).unwrap_or_else(|_| Uuid::new_v5(&Uuid::NAMESPACE_OID, value.as_bytes()))
+}
+
+/// Reads the day from a DATE (`20261023`) or DATE-TIME (`20261023T090000Z`) value.
+fn parse_date(value: &str) -> Result<Date, String> {
+    let day = value.trim().get(..8).unwrap_or_default();
+
+    Date::parse(day, format_description!("[year][month][day]"))
+        .map_err(|_| format!("invalid date '{}', expected YYYYMMDD", value.trim()))
+}
+
+fn format_date(date: Date) -> String {
+    date.format(format_description!("[year][month][day]"))
+        .unwrap_or_default()
+}
+
+fn format_date_time(at: OffsetDateTime) -> String {
+    at.to_offset(UtcOffset::UTC)
+        .format(format_description!(
+            "[year][month][day]T[hour][minute][second]Z"
+        ))
+        .unwrap_or_default()
+}
+
+/// The start of `date` in UTC, so the date survives being read back in any zone.
+fn midnight(date: Date) -> OffsetDateTime {
+    date.midnight().assume_utc()
+}
+
+fn status_value(status: Status) -> &'static str {
+    match status {
+        Status::Todo | Status::Blocked => "NEEDS-ACTION",
+        Status::InProgress => "IN-PROCESS",
+        Status::Completed => "COMPLETED",
+        Status::Cancelled => "CANCELLED",
+    }
+}
+
+fn parse_status(value: &str) -> Option<Status> {
+    match value.trim().to_uppercase().as_str() {
+        "NEEDS-ACTION" => Some(Status::Todo),
+        "IN-PROCESS" => Some(Status::InProgress),
+        "COMPLETED" => Some(Status::Completed),
+        "CANCELLED" => Some(Status::Cancelled),
+        _ => None,
+    }
+}
+
+/// RFC 5545 ranks 1 highest and 9 lowest, with 5 in the middle.
+fn priority_value(priority: Priority) -> u8 {
+    match priority {
+        Priority::High => 1,
+        Priority::Medium => 5,
+        Priority::Low => 9,
+    }
+}
+
+fn parse_priority(level: u8) -> Option<Priority> {
+    match level {
+        1..=4 => Some(Priority::High),
+        5 => Some(Priority::Medium),
+        6..=9 => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    fn task_with_every_field(status: Status) -> Task {
+        let mut task = Task::new(
+            "Pay rent, water; and \\ power".to_string(),
+            vec!["First line".to_string(), "Second, with; marks".to_string()],
+            Some(date!(2026 - 11 - 01)),
+        );
+        task.created_at = date!(2026 - 10 - 01);
+        task.status = status;
+        if status == Status::Completed {
+            task.completed_at = Some(date!(2026 - 10 - 20));
+        }
+        task.priority = Some(Priority::Medium);
+        task.tags = vec!["home".to_string(), "bills".to_string()];
+        task.group = Some("flat,shared".to_string());
+        task
+    }
+
+    #[test]
+    fn export_and_import_keep_every_field() {
+        for status in [
+            Status::Todo,
+            Status::InProgress,
+            Status::Blocked,
+            Status::Completed,
+            Status::Cancelled,
+        ] {
+            let task = task_with_every_field(status);
+            let imported = import(&export(std::slice::from_ref(&task))).unwrap();
+
+            assert_eq!(imported.len(), 1);
+            let imported = &imported[0];
+            assert_eq!(imported.id, task.id);
+            assert_eq!(imported.title, task.title);
+            assert_eq!(imported.description, task.description);
+            assert_eq!(imported.due_date, task.due_date);
+            assert_eq!(imported.created_at, task.created_at);
+            assert_eq!(imported.status, task.status);
+            assert_eq!(imported.completed_at, task.completed_at);
+            assert_eq!(imported.priority, task.priority);
+            assert_eq!(imported.tags, task.tags);
+            assert_eq!(imported.group, task.group);
+        }
+    }
+
+    #[test]
+    fn every_priority_survives_a_round_trip() {
+        for priority in [Priority::High, Priority::Medium, Priority::Low] {
+            assert_eq!(parse_priority(priority_value(priority)), Some(priority));
+        }
+    }
+
+    #[test]
+    fn fold_line_never_splits_a_character() {
+        // The two-byte character would end at octet 76, so it starts the next line
+        let line = format!("{}é", "a".repeat(74));
+        assert_eq!(fold_line(&line), format!("{}\r\n é\r\n", "a".repeat(74)));
+
+        let line = format!("SUMMARY:{}", "日本語".repeat(20));
+        let folded = fold_line(&line);
+        for physical in folded.split("\r\n").filter(|line| !line.is_empty()) {
+            assert!(physical.len() <= MAX_LINE_OCTETS, "{:?}", physical);
+        }
+        let unfolded: Vec<String> = unfold(&folded).into_iter().map(|(_, line)| line).collect();
+        assert_eq!(unfolded, vec![line]);
+    }
+
+    #[test]
+    fn fold_line_keeps_short_lines_whole() {
+        let line = "a".repeat(MAX_LINE_OCTETS);
+        assert_eq!(fold_line(&line), format!("{}\r\n", line));
+    }
+
+    #[test]
+    fn escape_text_escapes_backslashes_separators_and_newlines() {
+        let text = "a\\b;c,d\ne";
+        assert_eq!(escape_text(text), r"a\\b\;c\,d\ne");
+        assert_eq!(unescape_text(&escape_text(text)), text);
+    }
+
+    #[test]
+    fn unescape_text_reads_an_upper_case_newline_and_a_trailing_backslash() {
+        assert_eq!(unescape_text("one\\Ntwo"), "one\ntwo");
+        assert_eq!(unescape_text("end\\"), "end\\");
+    }
+
+    #[test]
+    fn split_list_keeps_escaped_commas() {
+        assert_eq!(
+            split_list("work,rent\\, water,home"),
+            vec!["work", "rent, water", "home"]
+        );
+        assert_eq!(split_list("single"), vec!["single"]);
+    }
+
+    #[test]
+    fn import_skips_a_nested_alarm() {
+        let input = "BEGIN:VCALENDAR\r\n\
+                     BEGIN:VTODO\r\n\
+                     UID:phone-task-1\r\n\
+                     SUMMARY:Call the dentist\r\n\
+                     BEGIN:VALARM\r\n\
+                     ACTION:DISPLAY\r\n\
+                     DESCRIPTION:Reminder\r\n\
+                     SUMMARY:Alarm title\r\n\
+                     TRIGGER:-PT15M\r\n\
+                     END:VALARM\r\n\
+                     DUE;VALUE=DATE:20261105\r\n\
+                     END:VTODO\r\n\
+                     END:VCALENDAR\r\n";
+
+        let tasks = import(input).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Call the dentist");
+        assert!(tasks[0].description.is_empty());
+        assert_eq!(tasks[0].due_date, Some(date!(2026 - 11 - 05)));
+        assert_eq!(tasks[0].id, parse_uid("phone-task-1"));
+    }
+
+    #[test]
+    fn import_reports_an_unclosed_todo() {
+        let errors = import("BEGIN:VTODO\r\nSUMMARY:Never closed\r\n").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], AppError::MalformedLine { line: 1, .. }));
+    }
+}
@@ -0,0 +1,440 @@
+//! Markdown checklists, grouped under a heading per group:
+//!
+//! ```markdown
+//! - [ ] Buy milk <!-- id:… -->
+//!
+//! ## work
+//!
+//! - [ ] Write report (due 2026-11-01) <!-- id:… -->
+//!   Outline first, then the numbers.
+//! - [x] Send invoice <!-- id:… -->
+//! - [x] ~~Book venue~~ <!-- id:… -->
+//! ```
+//!
+//! Checked items are completed and struck-through ones cancelled. A title that
+//! starts with `~` or `\` is escaped with a backslash, so a completed `~~draft~~`
+//! does not come back cancelled. Description lines are indented beneath their
+//! item. Any heading level sets the group of the items below it; other Markdown is
+//! ignored on import.
+
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use time::{Date, macros::format_description};
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        error::AppError,
+        task::{Task, task::Status},
+        validation::TaskValidator,
+    },
+    utils::date::get_today_with_fallbacks,
+};
+
+const FORMAT_NAME: &str = "Markdown";
+
+pub fn export(tasks: &[Task]) -> String {
+    let mut groups: BTreeMap<Option<&str>, Vec<&Task>> = BTreeMap::new();
+    for task in tasks {
+        groups.entry(task.group.as_deref()).or_default().push(task);
+    }
+
+    // Ungrouped tasks come first, before any heading
+    groups
+        .into_iter()
+        .map(|(group, tasks)| {
+            let items: String = tasks.into_iter().map(to_item).collect();
+            match group {
+                Some(group) => format!("## {}\n\n{}", group, items),
+                None => items,
+            }
+        })
+        .join("\n")
+}
+
+pub fn import(input: &str) -> Result<Vec<Task>, Vec<AppError>> {
+    let today = get_today_with_fallbacks().0;
+    let mut tasks: Vec<Task> = Vec::new();
+    let mut errors = Vec::new();
+    let mut group = None;
+    // Whether indented lines belong to the last item, and blank lines seen since
+    let mut in_item = false;
+    let mut blank_lines = 0;
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let malformed = |reason: String| AppError::MalformedLine {
+            format: FORMAT_NAME.to_string(),
+            line: line_number,
+            reason,
+        };
+
+        if line.trim().is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+
+        if let Some(heading) = parse_heading(line) {
+            in_item = false;
+            group = match heading_group(heading) {
+                Ok(group) => group,
+                Err(reason) => {
+                    errors.push(malformed(reason));
+                    None
+                }
+            };
+            continue;
+        }
+
+        if let Some(item) = line
+            .strip_prefix(['-', '*', '+'])
+            .and_then(|rest| rest.strip_prefix(' '))
+            && item.trim_start().starts_with('[')
+        {
+            in_item = false;
+            blank_lines = 0;
+            match parse_item(item.trim_start(), today) {
+                Ok(mut task) => {
+                    task.group = group.clone();
+                    tasks.push(task);
+                    in_item = true;
+                }
+                Err(reason) => errors.push(malformed(reason)),
+            }
+            continue;
+        }
+
+        if in_item
+            && line.starts_with([' ', '\t'])
+            && let Some(task) = tasks.last_mut()
+        {
+            if !task.description.is_empty() {
+                task.description
+                    .extend(std::iter::repeat_n(String::new(), blank_lines));
+            }
+            task.description.push(unindent(line).to_string());
+            blank_lines = 0;
+            continue;
+        }
+
+        // Anything else, such as paragraphs between lists, ends the item
+        in_item = false;
+    }
+
+    if errors.is_empty() {
+        Ok(tasks)
+    } else {
+        Err(errors)
+    }
+}
+
+fn to_item(task: &Task) -> String {
+    let checkbox = if task.status.is_closed() { "x" } else { " " };
+    let title = if task.status == Status::Cancelled {
+        format!("~~{}~~", task.title)
+    } else if task.title.starts_with(['~', '\\']) {
+        format!("\\{}", task.title)
+    } else {
+        task.title.clone()
+    };
+    let due = task
+        .due_date
+        .map(|date| format!(" (due {})", format_date(date)))
+        .unwrap_or_default();
+
+    let mut item = format!(
+        "- [{}] {}{} <!-- id:{} -->\n",
+        checkbox, title, due, task.id
+    );
+    for line in &task.description {
+        if line.is_empty() {
+            item.push('\n');
+        } else {
+            item.push_str(&format!("  {}\n", line));
+        }
+    }
+    item
+}
+
+/// Reads an item after its list marker, e.g. `[x] Title (due 2026-11-01) <!-- id:… -->`.
+fn parse_item(item: &str, today: Date) -> Result<Task, String> {
+    let (checkbox, rest) = item
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .ok_or_else(|| format!("expected a checkbox, found '{}'", item))?;
+    let checked = match checkbox {
+        " " | "" => false,
+        "x" | "X" => true,
+        other => return Err(format!("unknown checkbox '[{}]'", other)),
+    };
+
+    let mut rest = rest.trim();
+    let mut task = Task::new(String::new(), Vec::new(), None);
+
+    if let Some((before, comment)) = rest.rsplit_once("<!--")
+        && let Some(id) = comment
+            .trim()
+            .strip_suffix("-->")
+            .and_then(|comment| comment.trim().strip_prefix("id:"))
+    {
+        task.id =
+            Uuid::parse_str(id.trim()).map_err(|_| format!("'{}' is not a valid id", id.trim()))?;
+        rest = before.trim_end();
+    }
+
+    if let Some((before, due)) = rest.rsplit_once("(due ")
+        && let Some(due) = due.strip_suffix(')')
+    {
+        task.due_date = Some(
+            Date::parse(due.trim(), format_description!("[year]-[month]-[day]"))
+                .map_err(|_| format!("invalid due date '{}', expected YYYY-MM-DD", due.trim()))?,
+        );
+        rest = before.trim_end();
+    }
+
+    let cancelled = checked && rest.len() > 4 && rest.starts_with("~~") && rest.ends_with("~~");
+    if cancelled {
+        rest = &rest[2..rest.len() - 2];
+    } else if rest.starts_with("\\~") || rest.starts_with("\\\\") {
+        rest = &rest[1..];
+    }
+
+    task.title = TaskValidator::validate_title(rest).map_err(|error| error.user_message())?;
+    task.status = match (checked, cancelled) {
+        (true, true) => Status::Cancelled,
+        (true, false) => Status::Completed,
+        (false, _) => Status::Todo,
+    };
+    if task.status == Status::Completed {
+        task.completed_at = Some(today);
+    }
+
+    Ok(task)
+}
+
+/// The text of a `#` to `######` heading.
+fn parse_heading(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    let level = line.len() - text.len();
+    ((1..=6).contains(&level) && (text.is_empty() || text.starts_with(' '))).then(|| text.trim())
+}
+
+/// Headings may contain spaces, which groups cannot; they become dashes.
+fn heading_group(heading: &str) -> Result<Option<String>, String> {
+    if heading.is_empty() {
+        return Ok(None);
+    }
+
+    TaskValidator::validate_group(&heading.split_whitespace().join("-"))
+        .map(Some)
+        .map_err(|error| error.user_message())
+}
+
+/// Drops the two spaces or tab that put a description line under its item.
+fn unindent(line: &str) -> &str {
+    line.strip_prefix('\t')
+        .or_else(|| line.strip_prefix("  "))
+        .unwrap_or(line.trim_start())
+}
+
+fn format_date(date: Date) -> String {
+    date.format(format_description!("[year]-[month]-[day]"))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    const ID: &str = "6f1c2a52-8d1e-4a56-9a0b-2f3c4d5e6f70";
+
+    fn import_one(input: &str) -> Task {
+        let mut tasks = import(input).unwrap();
+        assert_eq!(tasks.len(), 1);
+        tasks.remove(0)
+    }
+
+    fn error_lines(input: &str) -> Vec<usize> {
+        import(input)
+            .unwrap_err()
+            .into_iter()
+            .map(|error| match error {
+                AppError::MalformedLine { line, .. } => line,
+                other => panic!("unexpected error {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn checkboxes_set_the_status() {
+        let tasks = import("- [ ] Open\n* [x] Done\n+ [X] Also done\n- [] Bare\n").unwrap();
+
+        let statuses: Vec<_> = tasks.iter().map(|task| task.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                Status::Todo,
+                Status::Completed,
+                Status::Completed,
+                Status::Todo
+            ]
+        );
+        assert!(tasks[0].completed_at.is_none());
+        assert!(tasks[1].completed_at.is_some());
+        assert_eq!(tasks[2].title, "Also done");
+    }
+
+    #[test]
+    fn struck_through_checked_items_are_cancelled() {
+        let task = import_one("- [x] ~~Book venue~~\n");
+        assert_eq!(task.status, Status::Cancelled);
+        assert_eq!(task.title, "Book venue");
+        assert!(task.completed_at.is_none());
+
+        // Only checked items can be cancelled, and the markers need a title between them
+        let task = import_one("- [ ] ~~Book venue~~\n");
+        assert_eq!(task.status, Status::Todo);
+        assert_eq!(task.title, "~~Book venue~~");
+
+        let task = import_one("- [x] ~~~~\n");
+        assert_eq!(task.status, Status::Completed);
+        assert_eq!(task.title, "~~~~");
+    }
+
+    #[test]
+    fn due_date_and_id_are_read_from_the_end_of_the_item() {
+        let task = import_one(&format!(
+            "- [ ] Write report (due 2026-11-01) <!-- id:{ID} -->\n"
+        ));
+        assert_eq!(task.title, "Write report");
+        assert_eq!(task.due_date, Some(date!(2026 - 11 - 01)));
+        assert_eq!(task.id, Uuid::parse_str(ID).unwrap());
+
+        let task = import_one("- [ ] Hand-written item\n");
+        assert_eq!(task.title, "Hand-written item");
+        assert_eq!(task.due_date, None);
+        assert_ne!(task.id, Uuid::parse_str(ID).unwrap());
+    }
+
+    #[test]
+    fn descriptions_keep_blank_lines_between_indented_lines() {
+        let task = import_one(
+            "- [ ] Write report\n\n  Outline first.\n\n\n\tThen the numbers.\n    Indented more.\n\n",
+        );
+        assert_eq!(
+            task.description,
+            [
+                "Outline first.",
+                "",
+                "",
+                "Then the numbers.",
+                "  Indented more."
+            ]
+        );
+    }
+
+    #[test]
+    fn other_markdown_ends_an_item() {
+        let tasks = import(
+            "- [ ] First\n  Its description.\nA paragraph.\n  Not a description.\n\
+             - [ ] Second\n### Heading\n  Not one either.\n",
+        )
+        .unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].description, ["Its description."]);
+        assert!(tasks[1].description.is_empty());
+    }
+
+    #[test]
+    fn headings_become_groups() {
+        let tasks = import(
+            "- [ ] Loose\n\n# Work stuff\n\n- [ ] Report\n\n### home\n- [ ] Dishes\n\
+             #notaheading\n- [ ] Laundry\n#\n- [ ] Ungrouped again\n",
+        )
+        .unwrap();
+
+        let groups: Vec<_> = tasks.iter().map(|task| task.group.as_deref()).collect();
+        assert_eq!(
+            groups,
+            [None, Some("Work-stuff"), Some("home"), Some("home"), None]
+        );
+    }
+
+    #[test]
+    fn malformed_items_are_reported_by_line() {
+        let input = "- [?] Unknown box\n- [ ] Fine\n- [ ] Bad date (due 2026-13-01)\n\
+                     - [ ] Bad id <!-- id:nope -->\n- [ ]   \n";
+        assert_eq!(error_lines(input), [1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn tilde_titles_keep_their_status_through_a_round_trip() {
+        let mut tasks = Vec::new();
+        for (title, status) in [
+            ("~~draft~~", Status::Completed),
+            ("~~draft~~", Status::Todo),
+            ("~~draft~~", Status::Cancelled),
+            ("~approx", Status::Completed),
+            ("\\path\\to", Status::Completed),
+            ("\\~literal", Status::Todo),
+        ] {
+            let mut task = Task::new(title.to_string(), Vec::new(), None);
+            task.status = status;
+            tasks.push(task);
+        }
+
+        let exported = export(&tasks);
+        assert!(exported.starts_with("- [x] \\~~draft~~ <!--"));
+        let imported = import(&exported).unwrap();
+
+        assert_eq!(imported.len(), tasks.len());
+        for (task, imported) in tasks.iter().zip(&imported) {
+            assert_eq!(imported.title, task.title);
+            assert_eq!(imported.status, task.status, "{}", task.title);
+        }
+    }
+
+    #[test]
+    fn export_and_import_keep_every_field() {
+        let mut report = Task::new(
+            "Write report".to_string(),
+            vec![
+                "Outline first.".to_string(),
+                String::new(),
+                "Then numbers.".to_string(),
+            ],
+            Some(date!(2026 - 11 - 01)),
+        );
+        report.group = Some("work".to_string());
+        let mut venue = Task::new("Book venue".to_string(), Vec::new(), None);
+        venue.status = Status::Cancelled;
+        venue.group = Some("work".to_string());
+        let milk = Task::new("Buy milk".to_string(), Vec::new(), None);
+        let tasks = vec![report, venue, milk];
+
+        let exported = export(&tasks);
+        assert_eq!(
+            exported,
+            format!(
+                "- [ ] Buy milk <!-- id:{} -->\n\n## work\n\n\
+                 - [ ] Write report (due 2026-11-01) <!-- id:{} -->\n  Outline first.\n\n  Then numbers.\n\
+                 - [x] ~~Book venue~~ <!-- id:{} -->\n",
+                tasks[2].id, tasks[0].id, tasks[1].id
+            )
+        );
+
+        let imported = import(&exported).unwrap();
+        assert_eq!(imported.len(), 3);
+        for task in &tasks {
+            let read = imported.iter().find(|read| read.id == task.id).unwrap();
+            assert_eq!(read.title, task.title);
+            assert_eq!(read.status, task.status);
+            assert_eq!(read.due_date, task.due_date);
+            assert_eq!(read.description, task.description);
+            assert_eq!(read.group, task.group);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! [Org mode](https://orgmode.org) outlines, for mirroring tasks into Emacs:
+//!
+//! ```org
+//! #+TODO: TODO STARTED WAITING | DONE CANCELLED
+//!
+//! * TODO Buy milk
+//!   :PROPERTIES:
+//!   :ID:       …
+//!   :END:
+//! * work
+//! ** TODO [#A] Write report                                          :urgent:
+//!    DEADLINE: <2026-11-01 Sun>
+//!    :PROPERTIES:
+//!    :ID:       …
+//!    :END:
+//!    Outline first, then the numbers.
+//! ** DONE Send invoice
+//!    CLOSED: [2026-10-18 Sun]
+//! ```
+//!
+//! Ungrouped tasks come first as top-level headlines, then a headline per group
+//! with its tasks beneath. The file is only written, never read back.
+
+use std::collections::BTreeMap;
+
+use time::{Date, macros::format_description};
+
+use crate::core::task::{
+    Task,
+    task::{Priority, Status},
+};
+
+/// Column that headline tags are right-aligned to, as Org does by default.
+const TAGS_COLUMN: usize = 77;
+
+pub fn export(tasks: &[Task]) -> String {
+    let mut groups: BTreeMap<Option<&str>, Vec<&Task>> = BTreeMap::new();
+    for task in tasks {
+        groups.entry(task.group.as_deref()).or_default().push(task);
+    }
+
+    let mut output = String::from("#+TODO: TODO STARTED WAITING | DONE CANCELLED\n\n");
+    for (group, tasks) in groups {
+        let level = match group {
+            Some(group) => {
+                output.push_str(&format!("* {}\n", group));
+                2
+            }
+            None => 1,
+        };
+        for task in tasks {
+            output.push_str(&to_entry(task, level));
+        }
+    }
+
+    output
+}
+
+fn to_entry(task: &Task, level: usize) -> String {
+    let indent = " ".repeat(level + 1);
+
+    let mut headline = format!("{} {} ", "*".repeat(level), keyword(task.status));
+    if let Some(priority) = task.priority {
+        headline.push_str(&format!("[#{}] ", priority_cookie(priority)));
+    }
+    headline.push_str(&task.title);
+    if !task.tags.is_empty() {
+        let tags: Vec<String> = task.tags.iter().map(|tag| tag_name(tag)).collect();
+        let tags = format!(":{}:", tags.join(":"));
+        let padding = TAGS_COLUMN.saturating_sub(headline.chars().count() + tags.chars().count());
+        headline.push_str(&" ".repeat(padding.max(1)));
+        headline.push_str(&tags);
+    }
+
+    let mut entry = format!("{}\n", headline);
+
+    let mut planning = Vec::new();
+    if let Some(closed_on) = task.status.is_closed().then(|| task.closed_on()).flatten() {
+        planning.push(format!("CLOSED: [{}]", format_date(closed_on)));
+    }
+    if let Some(due_date) = task.due_date {
+        planning.push(format!("DEADLINE: <{}>", format_date(due_date)));
+    }
+    if let Some(defer_until) = task.defer_until {
+        planning.push(format!("SCHEDULED: <{}>", format_date(defer_until)));
+    }
+    if !planning.is_empty() {
+        entry.push_str(&format!("{}{}\n", indent, planning.join(" ")));
+    }
+
+    entry.push_str(&format!("{}:PROPERTIES:\n", indent));
+    entry.push_str(&format!("{}:ID:       {}\n", indent, task.id));
+    if let Some(reason) = &task.blocked_reason {
+        entry.push_str(&format!("{}:BLOCKED:  {}\n", indent, reason));
+    }
+    entry.push_str(&format!("{}:END:\n", indent));
+
+    // Indenting the body keeps lines starting with `*` from becoming headlines
+    for line in &task.description {
+        if line.trim().is_empty() {
+            entry.push('\n');
+        } else {
+            entry.push_str(&format!("{}{}\n", indent, line));
+        }
+    }
+
+    entry
+}
+
+fn keyword(status: Status) -> &'static str {
+    match status {
+        Status::Todo => "TODO",
+        Status::InProgress => "STARTED",
+        Status::Blocked => "WAITING",
+        Status::Completed => "DONE",
+        Status::Cancelled => "CANCELLED",
+    }
+}
+
+fn priority_cookie(priority: Priority) -> char {
+    match priority {
+        Priority::High => 'A',
+        Priority::Medium => 'B',
+        Priority::Low => 'C',
+    }
+}
+
+/// Org tags may only hold letters, digits, `_`, `@`, `#` and `%`.
+fn tag_name(tag: &str) -> String {
+    tag.chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || matches!(ch, '_' | '@' | '#' | '%') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// A date with its weekday, as in `2026-11-01 Sun`.
+fn format_date(date: Date) -> String {
+    date.format(format_description!(
+        "[year]-[month]-[day] [weekday repr:short]"
+    ))
+    .unwrap_or_default()
+}
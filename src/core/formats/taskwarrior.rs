@@ -0,0 +1,615 @@
+//! [Taskwarrior](https://taskwarrior.org) JSON, as written by `task export` and
+//! read by `task import`.
+//!
+//! ```json
+//! [
+//! {"uuid":"…","description":"Write report","status":"pending","entry":"20261005T080000Z","due":"20261022T220000Z","project":"work","tags":["urgent"],"priority":"H"}
+//! ]
+//! ```
+//!
+//! Taskwarrior keeps every date as a UTC timestamp. Dates are read in the local
+//! timezone, so a task due at local midnight stays due on that day, and written as
+//! local midnight. Annotations hold the description, one per line. `deleted` tasks
+//! become cancelled ones, `waiting` tasks are deferred until their wait date and
+//! started tasks are in progress. Recurring templates are skipped since Taskwarrior
+//! exports their instances as separate tasks.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::{
+    Date, OffsetDateTime, PrimitiveDateTime, UtcOffset, format_description::well_known::Rfc3339,
+    macros::format_description,
+};
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        error::AppError,
+        task::{
+            Task,
+            task::{Priority, Status, StatusChange},
+        },
+        validation::TaskValidator,
+    },
+    utils::date::{get_today_with_fallbacks, local_offset_at, now_timestamp},
+};
+
+const FORMAT_NAME: &str = "Taskwarrior";
+
+/// One task as Taskwarrior exports it. Attributes delibird has no use for, such
+/// as `urgency` or user-defined ones, are ignored.
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uuid: Option<String>,
+    description: String,
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modified: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wait: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    depends: Option<Depends>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Annotation {
+    entry: String,
+    description: String,
+}
+
+/// Taskwarrior 2.6 and later write dependencies as a list, earlier versions as
+/// one comma-separated string.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Depends {
+    List(Vec<String>),
+    Joined(String),
+}
+
+pub fn export(tasks: &[Task]) -> String {
+    let modified = format_timestamp(now_timestamp());
+
+    // Like `task export`, one task per line inside the array
+    let records: Vec<String> = tasks
+        .iter()
+        .map(|task| serde_json::to_string(&to_record(task, &modified)).unwrap_or_default())
+        .collect();
+
+    format!("[\n{}\n]\n", records.join(",\n"))
+}
+
+pub fn import(input: &str) -> Result<Vec<Task>, Vec<AppError>> {
+    let malformed = |line: usize, reason: String| AppError::MalformedLine {
+        format: FORMAT_NAME.to_string(),
+        line,
+        reason,
+    };
+
+    let values = split_values(input).map_err(|(line, reason)| vec![malformed(line, reason)])?;
+    let today = get_today_with_fallbacks().0;
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+
+    for (line, value) in values {
+        let record: Record = match serde_json::from_value(value) {
+            Ok(record) => record,
+            Err(error) => {
+                errors.push(malformed(line, error.to_string()));
+                continue;
+            }
+        };
+        if record.status == "recurring" {
+            continue;
+        }
+
+        match from_record(record, today) {
+            Ok(task) => tasks.push(task),
+            Err(reason) => errors.push(malformed(line, reason)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(tasks)
+    } else {
+        Err(errors)
+    }
+}
+
+fn to_record(task: &Task, modified: &str) -> Record {
+    let entry = format_date(task.created_at);
+    let status = match task.status {
+        Status::Completed => "completed",
+        Status::Cancelled => "deleted",
+        _ if task.defer_until.is_some() => "waiting",
+        _ => "pending",
+    };
+
+    // When the task was last started, falling back to its creation
+    let start = (task.status == Status::InProgress).then(|| {
+        task.status_changes
+            .iter()
+            .rev()
+            .find(|change| change.status == Status::InProgress)
+            .map(|change| format_timestamp(change.at))
+            .unwrap_or_else(|| entry.clone())
+    });
+    let end = task
+        .status
+        .is_closed()
+        .then(|| format_date(task.closed_on().unwrap_or(task.created_at)));
+
+    Record {
+        uuid: Some(task.id.to_string()),
+        description: task.title.clone(),
+        status: status.to_string(),
+        entry: Some(entry.clone()),
+        modified: Some(modified.to_string()),
+        start,
+        end,
+        due: task.due_date.map(format_date),
+        wait: task.defer_until.map(format_date),
+        project: task.group.clone(),
+        tags: task.tags.clone(),
+        priority: task.priority.map(|priority| {
+            match priority {
+                Priority::High => "H",
+                Priority::Medium => "M",
+                Priority::Low => "L",
+            }
+            .to_string()
+        }),
+        depends: (!task.depends_on.is_empty())
+            .then(|| Depends::List(task.depends_on.iter().map(Uuid::to_string).collect())),
+        annotations: task
+            .description
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Annotation {
+                entry: entry.clone(),
+                description: line.clone(),
+            })
+            .collect(),
+    }
+}
+
+fn from_record(record: Record, today: Date) -> Result<Task, String> {
+    let reason = |error: AppError| error.user_message();
+    let mut task = Task::new(String::new(), Vec::new(), None);
+
+    if let Some(uuid) = &record.uuid {
+        task.id = Uuid::parse_str(uuid).map_err(|_| format!("'{}' is not a valid uuid", uuid))?;
+    }
+    task.title = TaskValidator::validate_title(&record.description).map_err(reason)?;
+    task.description = TaskValidator::validate_description(
+        &record
+            .annotations
+            .iter()
+            .map(|annotation| annotation.description.clone())
+            .collect::<Vec<_>>(),
+    )
+    .map_err(reason)?;
+
+    if let Some(entry) = &record.entry {
+        task.created_at = parse_date(entry)?;
+    }
+    task.due_date = record.due.as_deref().map(parse_date).transpose()?;
+    let end = record.end.as_deref().map(parse_date).transpose()?;
+
+    task.status = match record.status.as_str() {
+        "pending" | "waiting" if record.start.is_some() => Status::InProgress,
+        "pending" | "waiting" => Status::Todo,
+        "completed" => Status::Completed,
+        "deleted" => Status::Cancelled,
+        other => return Err(format!("unknown status '{}'", other)),
+    };
+    match task.status {
+        Status::Completed => task.completed_at = Some(end.unwrap_or(today)),
+        // Cancelled tasks only keep their date in the status history
+        Status::Cancelled => task.status_changes.push(StatusChange {
+            status: Status::Cancelled,
+            at: local_midnight(end.unwrap_or(today)),
+        }),
+        _ => {}
+    }
+
+    if task.is_open() {
+        task.defer_until = record.wait.as_deref().map(parse_date).transpose()?;
+    }
+
+    if let Some(project) = &record.project {
+        task.group = Some(TaskValidator::validate_group(project).map_err(reason)?);
+    }
+    for tag in &record.tags {
+        let tag = TaskValidator::validate_tag(tag).map_err(reason)?;
+        if !task.tags.contains(&tag) {
+            task.tags.push(tag);
+        }
+    }
+    task.priority = match record.priority.as_deref() {
+        None | Some("") => None,
+        Some("H") => Some(Priority::High),
+        Some("M") => Some(Priority::Medium),
+        Some("L") => Some(Priority::Low),
+        Some(other) => return Err(format!("unknown priority '{}'", other)),
+    };
+
+    let depends = match record.depends {
+        Some(Depends::List(ids)) => ids,
+        Some(Depends::Joined(ids)) => ids.split(',').map(str::to_string).collect(),
+        None => Vec::new(),
+    };
+    task.depends_on = depends
+        .iter()
+        .map(|id| id.trim())
+        .filter(|id| !id.is_empty())
+        .map(|id| Uuid::parse_str(id).map_err(|_| format!("'{}' is not a valid uuid", id)))
+        .collect::<Result<_, _>>()?;
+
+    Ok(task)
+}
+
+/// Splits a JSON array, or objects one after another as older versions of
+/// `task export` print them, into values and the line each starts on.
+fn split_values(input: &str) -> Result<Vec<(usize, Value)>, (usize, String)> {
+    let line_at = |offset: usize| input[..offset].matches('\n').count() + 1;
+    let skip = |offset: usize, separators: &[char]| {
+        offset + input[offset..].len()
+            - input[offset..]
+                .trim_start_matches(|ch: char| ch.is_whitespace() || separators.contains(&ch))
+                .len()
+    };
+
+    let mut offset = skip(0, &[]);
+    let in_array = input[offset..].starts_with('[');
+    if in_array {
+        offset += 1;
+    }
+
+    let mut values = Vec::new();
+    loop {
+        offset = skip(offset, if in_array { &[','] } else { &[] });
+
+        if in_array && input[offset..].starts_with(']') {
+            let rest = offset + 1;
+            if !input[rest..].trim().is_empty() {
+                return Err((line_at(skip(rest, &[])), "text after the array".to_string()));
+            }
+            return Ok(values);
+        }
+        if offset == input.len() {
+            return if in_array {
+                Err((line_at(offset), "the array is never closed".to_string()))
+            } else {
+                Ok(values)
+            };
+        }
+
+        let mut stream = serde_json::Deserializer::from_str(&input[offset..]).into_iter::<Value>();
+        match stream.next() {
+            Some(Ok(value)) => {
+                values.push((line_at(offset), value));
+                offset += stream.byte_offset();
+            }
+            Some(Err(error)) => {
+                let line = line_at(offset) + error.line().saturating_sub(1);
+                // The position is already in the line number
+                let message = error.to_string();
+                let message = message
+                    .rsplit_once(" at line ")
+                    .map_or(message.as_str(), |(message, _)| message);
+                return Err((line, format!("invalid JSON: {}", message)));
+            }
+            None => return Ok(values),
+        }
+    }
+}
+
+/// Reads the local date of a timestamp such as `20261022T220000Z`. ISO 8601
+/// timestamps and plain dates, which `task import` also accepts, work too.
+fn parse_date(value: &str) -> Result<Date, String> {
+    parse_date_in(value, local_offset_at)
+}
+
+/// [`parse_date`] in the timezone whose offset at a moment is `offset_at`.
+fn parse_date_in(value: &str, offset_at: fn(OffsetDateTime) -> UtcOffset) -> Result<Date, String> {
+    let value = value.trim();
+    let at = PrimitiveDateTime::parse(
+        value,
+        format_description!("[year][month][day]T[hour][minute][second]Z"),
+    )
+    .map(PrimitiveDateTime::assume_utc)
+    .or_else(|_| OffsetDateTime::parse(value, &Rfc3339));
+
+    match at {
+        Ok(at) => Ok(at.to_offset(offset_at(at)).date()),
+        Err(_) => Date::parse(value, format_description!("[year]-[month]-[day]"))
+            .map_err(|_| format!("invalid date '{}', expected e.g. 20261023T000000Z", value)),
+    }
+}
+
+/// Local midnight of `date` as a UTC timestamp, which is how Taskwarrior stores
+/// a date given without a time.
+fn format_date(date: Date) -> String {
+    format_timestamp(local_midnight(date))
+}
+
+/// Midnight of `date` in the offset the local timezone has on that day.
+fn local_midnight(date: Date) -> OffsetDateTime {
+    midnight_in(date, local_offset_at)
+}
+
+fn midnight_in(date: Date, offset_at: fn(OffsetDateTime) -> UtcOffset) -> OffsetDateTime {
+    let midnight = date.midnight();
+    midnight.assume_offset(offset_at(midnight.assume_utc()))
+}
+
+fn format_timestamp(at: OffsetDateTime) -> String {
+    at.to_offset(UtcOffset::UTC)
+        .format(format_description!(
+            "[year][month][day]T[hour][minute][second]Z"
+        ))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{date, datetime, offset};
+
+    use super::*;
+
+    const FIRST: &str = "6f1c2a52-8d1e-4a56-9a0b-2f3c4d5e6f70";
+    const SECOND: &str = "0b7e4c1d-2f3a-4b5c-8d6e-7f8091a2b3c4";
+
+    /// Central European time in 2026: UTC+2 from 29 March to 25 October, UTC+1
+    /// otherwise.
+    fn central_europe(at: OffsetDateTime) -> UtcOffset {
+        let summer = datetime!(2026-03-29 01:00 UTC)..datetime!(2026-10-25 01:00 UTC);
+        if summer.contains(&at) {
+            offset!(+2)
+        } else {
+            offset!(+1)
+        }
+    }
+
+    fn error_lines(errors: Vec<AppError>) -> Vec<usize> {
+        errors
+            .into_iter()
+            .map(|error| match error {
+                AppError::MalformedLine { line, .. } => line,
+                other => panic!("unexpected error {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_the_array_written_by_task_export() {
+        let input = format!(
+            "[\n\
+             {{\"uuid\":\"{FIRST}\",\"description\":\"Write report\",\"status\":\"pending\",\
+             \"entry\":\"20261005T120000Z\",\"due\":\"20261022T120000Z\",\"project\":\"work\",\
+             \"tags\":[\"urgent\",\"urgent\"],\"priority\":\"H\",\"urgency\":8.2,\
+             \"annotations\":[{{\"entry\":\"20261005T120000Z\",\"description\":\"Outline first\"}}]}},\n\
+             {{\"description\":\"Send invoice\",\"status\":\"completed\",\"end\":\"20261010T120000Z\"}}\n\
+             ]\n"
+        );
+
+        let tasks = import(&input).unwrap();
+
+        assert_eq!(tasks.len(), 2);
+        let report = &tasks[0];
+        assert_eq!(report.id, Uuid::parse_str(FIRST).unwrap());
+        assert_eq!(report.title, "Write report");
+        assert_eq!(report.status, Status::Todo);
+        assert_eq!(report.created_at, date!(2026 - 10 - 05));
+        assert_eq!(report.due_date, Some(date!(2026 - 10 - 22)));
+        assert_eq!(report.group.as_deref(), Some("work"));
+        assert_eq!(report.tags, ["urgent"]);
+        assert_eq!(report.priority, Some(Priority::High));
+        assert_eq!(report.description, ["Outline first"]);
+
+        assert_eq!(tasks[1].status, Status::Completed);
+        assert_eq!(tasks[1].completed_at, Some(date!(2026 - 10 - 10)));
+    }
+
+    #[test]
+    fn reads_one_object_per_line_from_older_versions() {
+        let input = format!(
+            "{{\"uuid\":\"{FIRST}\",\"description\":\"First\",\"status\":\"pending\"}}\n\
+             {{\"uuid\":\"{SECOND}\",\"description\":\"Second\",\"status\":\"pending\"}}\n\n"
+        );
+
+        let tasks = import(&input).unwrap();
+
+        let titles: Vec<_> = tasks.iter().map(|task| task.title.as_str()).collect();
+        assert_eq!(titles, ["First", "Second"]);
+        assert_eq!(tasks[1].id, Uuid::parse_str(SECOND).unwrap());
+        assert!(import("").unwrap().is_empty());
+        assert!(import("[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn reads_dependencies_as_a_list_or_a_joined_string() {
+        let first = Uuid::parse_str(FIRST).unwrap();
+        let second = Uuid::parse_str(SECOND).unwrap();
+        let input = format!(
+            "[{{\"description\":\"List\",\"status\":\"pending\",\"depends\":[\"{FIRST}\",\"{SECOND}\"]}},\n\
+             {{\"description\":\"Joined\",\"status\":\"pending\",\"depends\":\"{FIRST}, {SECOND}\"}},\n\
+             {{\"description\":\"Empty\",\"status\":\"pending\",\"depends\":\"\"}}]"
+        );
+
+        let tasks = import(&input).unwrap();
+
+        assert_eq!(tasks[0].depends_on, [first, second]);
+        assert_eq!(tasks[1].depends_on, [first, second]);
+        assert!(tasks[2].depends_on.is_empty());
+    }
+
+    #[test]
+    fn skips_recurring_templates() {
+        let input = "[{\"description\":\"Water plants\",\"status\":\"recurring\",\"recur\":\"weekly\"},\n\
+                     {\"description\":\"Water plants\",\"status\":\"pending\",\"parent\":\"x\"}]";
+
+        let tasks = import(input).unwrap();
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].status, Status::Todo);
+    }
+
+    #[test]
+    fn maps_statuses() {
+        let input = "{\"description\":\"Started\",\"status\":\"pending\",\"start\":\"20261012T120000Z\"}\n\
+                     {\"description\":\"Waiting\",\"status\":\"waiting\",\"wait\":\"20261030T120000Z\"}\n\
+                     {\"description\":\"Deleted\",\"status\":\"deleted\",\"end\":\"20261011T120000Z\",\
+                     \"wait\":\"20261030T120000Z\"}\n\
+                     {\"description\":\"Done\",\"status\":\"completed\"}\n";
+
+        let tasks = import(input).unwrap();
+
+        assert_eq!(tasks[0].status, Status::InProgress);
+        assert_eq!(tasks[1].status, Status::Todo);
+        assert_eq!(tasks[1].defer_until, Some(date!(2026 - 10 - 30)));
+        assert_eq!(tasks[2].status, Status::Cancelled);
+        assert_eq!(tasks[2].defer_until, None);
+        assert_eq!(tasks[2].closed_on(), Some(date!(2026 - 10 - 11)));
+        assert_eq!(tasks[3].status, Status::Completed);
+        assert!(tasks[3].completed_at.is_some());
+    }
+
+    #[test]
+    fn reports_invalid_records_by_the_line_they_start_on() {
+        let input = "[\n\
+                     {\"description\":\"Fine\",\"status\":\"pending\"},\n\
+                     {\"description\":\"Odd\",\"status\":\"someday\"},\n\
+                     {\"description\":\"Loud\",\"status\":\"pending\",\"priority\":\"X\"},\n\
+                     {\"status\":\"pending\"},\n\
+                     {\"description\":\"Late\",\"status\":\"pending\",\"due\":\"tomorrow-ish\"},\n\
+                     {\"uuid\":\"nope\",\"description\":\"Lost\",\"status\":\"pending\"}\n\
+                     ]";
+
+        assert_eq!(error_lines(import(input).unwrap_err()), [3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn rejects_broken_json() {
+        let input = "[\n{\"description\":\"Fine\",\"status\":\"pending\"},\n{\"description\":\n";
+        assert_eq!(error_lines(import(input).unwrap_err()), [4]);
+
+        let input = "[{\"description\":\"Fine\",\"status\":\"pending\"}\n";
+        assert_eq!(error_lines(import(input).unwrap_err()), [2]);
+
+        let input = "[{\"description\":\"Fine\",\"status\":\"pending\"}]\n\ntrailing";
+        assert_eq!(error_lines(import(input).unwrap_err()), [3]);
+    }
+
+    #[test]
+    fn timestamps_are_read_on_the_local_day_with_that_days_offset() {
+        for (value, expected) in [
+            // Local midnight in summer and in winter
+            ("20260714T220000Z", date!(2026 - 07 - 15)),
+            ("20260714T215959Z", date!(2026 - 07 - 14)),
+            ("20260113T230000Z", date!(2026 - 01 - 14)),
+            ("20260113T225959Z", date!(2026 - 01 - 13)),
+            ("2026-07-15T00:30:00+02:00", date!(2026 - 07 - 15)),
+            ("2026-07-14T23:30:00Z", date!(2026 - 07 - 15)),
+            ("2026-07-15", date!(2026 - 07 - 15)),
+        ] {
+            assert_eq!(
+                parse_date_in(value, central_europe),
+                Ok(expected),
+                "{value}"
+            );
+        }
+
+        for value in ["", "20261301T000000Z", "2026-07-15T25:00:00Z", "tomorrow"] {
+            assert!(parse_date_in(value, central_europe).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn dates_are_written_as_local_midnight_across_daylight_saving_changes() {
+        assert_eq!(
+            format_timestamp(midnight_in(date!(2026 - 07 - 15), central_europe)),
+            "20260714T220000Z"
+        );
+        assert_eq!(
+            format_timestamp(midnight_in(date!(2026 - 01 - 14), central_europe)),
+            "20260113T230000Z"
+        );
+
+        let around_changes = [
+            date!(2026 - 03 - 28),
+            date!(2026 - 03 - 29),
+            date!(2026 - 03 - 30),
+            date!(2026 - 10 - 24),
+            date!(2026 - 10 - 25),
+            date!(2026 - 10 - 26),
+        ];
+        for date in around_changes {
+            let written = format_timestamp(midnight_in(date, central_europe));
+            assert_eq!(
+                parse_date_in(&written, central_europe),
+                Ok(date),
+                "{written}"
+            );
+        }
+    }
+
+    #[test]
+    fn export_and_import_keep_every_field() {
+        let mut task = Task::new(
+            "Write report".to_string(),
+            vec!["Outline first".to_string(), "Then numbers".to_string()],
+            Some(date!(2026 - 11 - 01)),
+        );
+        task.created_at = date!(2026 - 10 - 01);
+        task.defer_until = Some(date!(2026 - 10 - 25));
+        task.group = Some("work".to_string());
+        task.tags = vec!["urgent".to_string(), "office".to_string()];
+        task.priority = Some(Priority::Medium);
+        task.depends_on = vec![Uuid::new_v4(), Uuid::new_v4()];
+
+        let mut done = Task::new("Send invoice".to_string(), Vec::new(), None);
+        done.status = Status::Completed;
+        done.completed_at = Some(date!(2026 - 10 - 10));
+        let mut dropped = Task::new("Book venue".to_string(), Vec::new(), None);
+        dropped.status = Status::Cancelled;
+
+        let tasks = [task, done, dropped];
+        let exported = export(&tasks);
+        assert!(exported.starts_with("[\n{\"uuid\":"));
+        let imported = import(&exported).unwrap();
+
+        assert_eq!(imported.len(), tasks.len());
+        for (task, read) in tasks.iter().zip(&imported) {
+            assert_eq!(read.id, task.id);
+            assert_eq!(read.title, task.title);
+            assert_eq!(read.description, task.description);
+            assert_eq!(read.status, task.status);
+            assert_eq!(read.created_at, task.created_at);
+            assert_eq!(read.due_date, task.due_date);
+            assert_eq!(read.completed_at, task.completed_at);
+            assert_eq!(read.defer_until, task.defer_until);
+            assert_eq!(read.group, task.group);
+            assert_eq!(read.tags, task.tags);
+            assert_eq!(read.priority, task.priority);
+            assert_eq!(read.depends_on, task.depends_on);
+        }
+    }
+}
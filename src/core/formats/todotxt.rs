@@ -0,0 +1,417 @@
+//! The [todo.txt](https://github.com/todotxt/todo.txt) format: one task per line.
+//!
+//! ```text
+//! x 2026-10-18 2026-10-01 Call the bank +home @phone pri:A id:…
+//! (B) 2026-10-05 Write report +work @urgent due:2026-10-23 est:90m id:…
+//! ```
+//!
+//! - `x` marks a closed task, followed by its completion and creation dates
+//! - `(A)`, `(B)` and `(C)` are high, medium and low priority; `pri:` keeps it on
+//!   closed tasks
+//! - `+project` is the group and `@context` a tag; projects after the first become tags
+//! - `due:`, `t:` (deferred until), `est:`, `status:` and `id:` carry the rest
+//!
+//! Descriptions, history, time entries and reminders have no place in a line and
+//! are not exported.
+
+use time::{Date, macros::format_description};
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        error::AppError,
+        task::{
+            Task,
+            task::{Estimate, Priority, Status, StatusChange},
+        },
+        validation::TaskValidator,
+    },
+    utils::date::{get_today_with_fallbacks, local_offset},
+};
+
+const FORMAT_NAME: &str = "todo.txt";
+
+pub fn export(tasks: &[Task]) -> String {
+    tasks
+        .iter()
+        .map(|task| format!("{}\n", to_line(task)))
+        .collect()
+}
+
+pub fn import(input: &str) -> Result<Vec<Task>, Vec<AppError>> {
+    let today = get_today_with_fallbacks().0;
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match parse_line(line, today) {
+            Ok(task) => tasks.push(task),
+            Err(reason) => errors.push(AppError::MalformedLine {
+                format: FORMAT_NAME.to_string(),
+                line: index + 1,
+                reason,
+            }),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(tasks)
+    } else {
+        Err(errors)
+    }
+}
+
+pub fn to_line(task: &Task) -> String {
+    let mut words = Vec::new();
+
+    if task.status.is_closed() {
+        words.push("x".to_string());
+        words.push(format_date(task.closed_on().unwrap_or(task.created_at)));
+    } else if let Some(priority) = task.priority {
+        words.push(format!("({})", priority_letter(priority)));
+    }
+    words.push(format_date(task.created_at));
+
+    words.push(task.title.clone());
+
+    if let Some(group) = &task.group {
+        words.push(format!("+{}", group));
+    }
+    words.extend(task.tags.iter().map(|tag| format!("@{}", tag)));
+
+    if let Some(due_date) = task.due_date {
+        words.push(format!("due:{}", format_date(due_date)));
+    }
+    if let Some(defer_until) = task.defer_until {
+        words.push(format!("t:{}", format_date(defer_until)));
+    }
+    if let Some(estimate) = task.estimate {
+        words.push(format!("est:{}", estimate.compact()));
+    }
+    if task.status.is_closed()
+        && let Some(priority) = task.priority
+    {
+        words.push(format!("pri:{}", priority_letter(priority)));
+    }
+    if !matches!(task.status, Status::Todo | Status::Completed) {
+        words.push(format!("status:{}", status_key(task.status)));
+    }
+    words.push(format!("id:{}", task.id));
+
+    words.join(" ")
+}
+
+/// Reads one line. Tasks without a creation or completion date get `today`.
+pub fn parse_line(line: &str, today: Date) -> Result<Task, String> {
+    let mut words = line.split_whitespace().peekable();
+
+    let completed = words.next_if_eq(&"x").is_some();
+    let mut priority = None;
+    let mut completed_at = None;
+
+    if completed {
+        completed_at = words
+            .next_if(|word| looks_like_date(word))
+            .map(parse_date)
+            .transpose()?;
+    } else if let Some(word) = words.next_if(|word| parse_priority_marker(word).is_some()) {
+        priority = parse_priority_marker(word);
+    }
+
+    let created_at = words
+        .next_if(|word| looks_like_date(word))
+        .map(parse_date)
+        .transpose()?;
+
+    let mut task = Task::new(String::new(), Vec::new(), None);
+    let mut title_words = Vec::new();
+    let mut status = None;
+
+    for word in words {
+        if let Some(project) = word.strip_prefix('+').filter(|name| !name.is_empty()) {
+            let project = TaskValidator::validate_group(project).map_err(reason)?;
+            if task.group.is_none() {
+                task.group = Some(project);
+            } else if !task.tags.contains(&project) {
+                task.tags.push(project);
+            }
+        } else if let Some(context) = word.strip_prefix('@').filter(|name| !name.is_empty()) {
+            let tag = TaskValidator::validate_tag(context).map_err(reason)?;
+            if !task.tags.contains(&tag) {
+                task.tags.push(tag);
+            }
+        } else if let Some((key, value)) = word.split_once(':')
+            && !value.is_empty()
+            && is_known_key(key)
+        {
+            match key {
+                "due" => task.due_date = Some(parse_date(value)?),
+                "t" => task.defer_until = Some(parse_date(value)?),
+                "est" => {
+                    task.estimate = Some(
+                        Estimate::parse(value)
+                            .ok_or_else(|| format!("invalid estimate '{}'", value))?,
+                    )
+                }
+                "pri" => {
+                    priority = Some(
+                        parse_priority_letter(value)
+                            .ok_or_else(|| format!("invalid priority '{}'", value))?,
+                    )
+                }
+                "status" => {
+                    status = Some(
+                        Status::parse(value)
+                            .ok_or_else(|| format!("unknown status '{}'", value))?,
+                    )
+                }
+                "id" => {
+                    task.id = Uuid::parse_str(value)
+                        .map_err(|_| format!("'{}' is not a valid id", value))?
+                }
+                _ => unreachable!("is_known_key covers every key"),
+            }
+        } else {
+            title_words.push(word);
+        }
+    }
+
+    task.title = TaskValidator::validate_title(&title_words.join(" ")).map_err(reason)?;
+    task.priority = priority;
+    task.created_at = created_at.unwrap_or(today);
+    task.status = match status {
+        Some(Status::Cancelled) => Status::Cancelled,
+        _ if completed => Status::Completed,
+        Some(Status::Completed) | None => Status::Todo,
+        Some(status) => status,
+    };
+    match task.status {
+        Status::Completed => task.completed_at = Some(completed_at.unwrap_or(today)),
+        // Cancelled tasks only keep their date in the status history
+        Status::Cancelled => task.status_changes.push(StatusChange {
+            status: Status::Cancelled,
+            at: completed_at
+                .unwrap_or(today)
+                .midnight()
+                .assume_offset(local_offset()),
+        }),
+        _ => {}
+    }
+
+    Ok(task)
+}
+
+fn reason(error: AppError) -> String {
+    error.user_message()
+}
+
+fn is_known_key(key: &str) -> bool {
+    matches!(key, "due" | "t" | "est" | "pri" | "status" | "id")
+}
+
+fn looks_like_date(word: &str) -> bool {
+    let bytes = word.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(index, byte)| match index {
+            4 | 7 => *byte == b'-',
+            _ => byte.is_ascii_digit(),
+        })
+}
+
+fn parse_date(input: &str) -> Result<Date, String> {
+    Date::parse(input, format_description!("[year]-[month]-[day]"))
+        .map_err(|_| format!("invalid date '{}', expected YYYY-MM-DD", input))
+}
+
+fn format_date(date: Date) -> String {
+    date.format(format_description!("[year]-[month]-[day]"))
+        .unwrap_or_default()
+}
+
+fn priority_letter(priority: Priority) -> char {
+    match priority {
+        Priority::High => 'A',
+        Priority::Medium => 'B',
+        Priority::Low => 'C',
+    }
+}
+
+/// `A` and `B` map onto high and medium; `C` and everything below are low.
+fn parse_priority_letter(input: &str) -> Option<Priority> {
+    match input.as_bytes() {
+        [b'A'] => Some(Priority::High),
+        [b'B'] => Some(Priority::Medium),
+        [b'C'..=b'Z'] => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+fn parse_priority_marker(word: &str) -> Option<Priority> {
+    word.strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .and_then(parse_priority_letter)
+}
+
+fn status_key(status: Status) -> &'static str {
+    match status {
+        Status::Todo => "todo",
+        Status::InProgress => "inprogress",
+        Status::Blocked => "blocked",
+        Status::Completed => "done",
+        Status::Cancelled => "cancelled",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    const TODAY: Date = date!(2026 - 10 - 18);
+
+    fn open_task() -> Task {
+        let mut task = Task::new(
+            "Write report".to_string(),
+            Vec::new(),
+            Some(date!(2026 - 10 - 23)),
+        );
+        task.created_at = date!(2026 - 10 - 05);
+        task.priority = Some(Priority::Medium);
+        task.group = Some("work".to_string());
+        task.tags = vec!["urgent".to_string(), "office".to_string()];
+        task.defer_until = Some(date!(2026 - 10 - 20));
+        task.estimate = Some(Estimate::Minutes(90));
+        task
+    }
+
+    fn round_trip(task: &Task) -> Task {
+        parse_line(&to_line(task), TODAY).unwrap()
+    }
+
+    #[test]
+    fn open_task_survives_a_round_trip() {
+        let task = open_task();
+        let line = to_line(&task);
+        assert_eq!(
+            line,
+            format!(
+                "(B) 2026-10-05 Write report +work @urgent @office due:2026-10-23 \
+                 t:2026-10-20 est:90m id:{}",
+                task.id
+            )
+        );
+
+        let read = round_trip(&task);
+        assert_eq!(read.id, task.id);
+        assert_eq!(read.title, task.title);
+        assert_eq!(read.status, Status::Todo);
+        assert_eq!(read.priority, task.priority);
+        assert_eq!(read.created_at, task.created_at);
+        assert_eq!(read.group, task.group);
+        assert_eq!(read.tags, task.tags);
+        assert_eq!(read.due_date, task.due_date);
+        assert_eq!(read.defer_until, task.defer_until);
+        assert_eq!(read.estimate, task.estimate);
+    }
+
+    #[test]
+    fn every_status_survives_a_round_trip() {
+        for status in [Status::Todo, Status::InProgress, Status::Blocked] {
+            let mut task = open_task();
+            task.status = status;
+            assert_eq!(round_trip(&task).status, status);
+        }
+
+        let mut completed = open_task();
+        completed.set_status(Status::Completed, None);
+        completed.completed_at = Some(date!(2026 - 10 - 17));
+        let read = round_trip(&completed);
+        assert_eq!(read.status, Status::Completed);
+        assert_eq!(read.completed_at, completed.completed_at);
+        // Closed tasks keep their priority in `pri:`
+        assert_eq!(read.priority, Some(Priority::Medium));
+
+        let mut cancelled = open_task();
+        cancelled.set_status(Status::Cancelled, None);
+        let read = round_trip(&cancelled);
+        assert_eq!(read.status, Status::Cancelled);
+        assert_eq!(read.closed_on(), cancelled.closed_on());
+    }
+
+    #[test]
+    fn story_points_survive_a_round_trip() {
+        let mut task = open_task();
+        task.estimate = Some(Estimate::Points(3));
+        assert_eq!(round_trip(&task).estimate, Some(Estimate::Points(3)));
+    }
+
+    #[test]
+    fn parse_line_reads_a_plain_line() {
+        let task = parse_line("Call the bank", TODAY).unwrap();
+        assert_eq!(task.title, "Call the bank");
+        assert_eq!(task.status, Status::Todo);
+        assert_eq!(task.created_at, TODAY);
+        assert_eq!(task.priority, None);
+    }
+
+    #[test]
+    fn parse_line_reads_a_completed_line_from_other_apps() {
+        let task = parse_line("x 2026-10-18 2026-10-01 Call the bank +home @phone", TODAY).unwrap();
+        assert_eq!(task.status, Status::Completed);
+        assert_eq!(task.completed_at, Some(date!(2026 - 10 - 18)));
+        assert_eq!(task.created_at, date!(2026 - 10 - 01));
+        assert_eq!(task.group.as_deref(), Some("home"));
+        assert_eq!(task.tags, vec!["phone"]);
+    }
+
+    #[test]
+    fn parse_line_turns_extra_projects_into_tags_and_keeps_unknown_keys() {
+        let task = parse_line("Plan trip +travel +family note:later", TODAY).unwrap();
+        assert_eq!(task.group.as_deref(), Some("travel"));
+        assert_eq!(task.tags, vec!["family"]);
+        assert_eq!(task.title, "Plan trip note:later");
+    }
+
+    #[test]
+    fn parse_line_rejects_malformed_lines() {
+        for line in [
+            "Pay rent due:2026-13-01",
+            "Pay rent due:tomorrow",
+            "Pay rent t:2026-02-30",
+            "Pay rent est:soon",
+            "Pay rent pri:1",
+            "Pay rent status:someday",
+            "Pay rent id:not-a-uuid",
+            "x 2026-10-18 +work",
+            "(A) 2026-10-01",
+        ] {
+            assert!(parse_line(line, TODAY).is_err(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn import_reports_the_line_of_each_error() {
+        let errors = import("Good task\n\nBad due:someday\nAlso bad est:x\n").unwrap_err();
+        let lines: Vec<usize> = errors
+            .iter()
+            .map(|error| match error {
+                AppError::MalformedLine { line, .. } => *line,
+                other => panic!("unexpected error {:?}", other),
+            })
+            .collect();
+        assert_eq!(lines, vec![3, 4]);
+    }
+
+    #[test]
+    fn export_writes_one_line_per_task() {
+        let tasks = vec![open_task(), open_task()];
+        let exported = export(&tasks);
+        assert_eq!(exported.lines().count(), 2);
+        assert_eq!(import(&exported).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,108 @@
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::utils::config::PomodoroConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PomodoroPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl PomodoroPhase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PomodoroPhase::Work => "Focus",
+            PomodoroPhase::ShortBreak => "Short break",
+            PomodoroPhase::LongBreak => "Long break",
+        }
+    }
+
+    fn length(&self, config: &PomodoroConfig) -> Duration {
+        let minutes = match self {
+            PomodoroPhase::Work => config.work_minutes,
+            PomodoroPhase::ShortBreak => config.short_break_minutes,
+            PomodoroPhase::LongBreak => config.long_break_minutes,
+        };
+        Duration::from_secs(u64::from(minutes.max(1)) * 60)
+    }
+}
+
+/// A running focus session: work and break phases counted down against one task.
+#[derive(Debug, Clone)]
+pub struct Pomodoro {
+    pub task_id: Uuid,
+    pub phase: PomodoroPhase,
+    /// Work phases finished in this session.
+    pub completed: u32,
+    pub paused: bool,
+    remaining: Duration,
+    last_tick: Instant,
+}
+
+impl Pomodoro {
+    pub fn start(task_id: Uuid, config: &PomodoroConfig, now: Instant) -> Self {
+        Self {
+            task_id,
+            phase: PomodoroPhase::Work,
+            completed: 0,
+            paused: false,
+            remaining: PomodoroPhase::Work.length(config),
+            last_tick: now,
+        }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.remaining
+    }
+
+    pub fn toggle_pause(&mut self, now: Instant) {
+        self.paused = !self.paused;
+        self.last_tick = now;
+    }
+
+    /// Counts down the time since the last tick. Returns the phase that just ended,
+    /// if any; the next phase has already started by then.
+    pub fn tick(&mut self, config: &PomodoroConfig, now: Instant) -> Option<PomodoroPhase> {
+        if !self.paused {
+            self.remaining = self
+                .remaining
+                .saturating_sub(now.duration_since(self.last_tick));
+        }
+        self.last_tick = now;
+
+        if !self.remaining.is_zero() {
+            return None;
+        }
+
+        let finished = self.phase;
+        if finished == PomodoroPhase::Work {
+            self.completed += 1;
+        }
+        self.advance(config);
+        Some(finished)
+    }
+
+    /// Ends the current phase early without counting it.
+    pub fn skip(&mut self, config: &PomodoroConfig) {
+        self.advance(config);
+    }
+
+    fn advance(&mut self, config: &PomodoroConfig) {
+        self.phase = match self.phase {
+            PomodoroPhase::Work
+                if self.completed > 0
+                    && self
+                        .completed
+                        .is_multiple_of(config.long_break_every.max(1)) =>
+            {
+                PomodoroPhase::LongBreak
+            }
+            PomodoroPhase::Work => PomodoroPhase::ShortBreak,
+            PomodoroPhase::ShortBreak | PomodoroPhase::LongBreak => PomodoroPhase::Work,
+        };
+        self.remaining = self.phase.length(config);
+    }
+}
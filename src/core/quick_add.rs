@@ -0,0 +1,133 @@
+use time::{Date, macros::format_description};
+
+use super::{
+    error::AppError,
+    task::{Task, task::Priority},
+    validation::{DateValidator, TaskValidator, ValidationResult},
+};
+
+/// Longest natural-language phrase `due:` will swallow, e.g. `due:in 3 days`.
+const MAX_DUE_WORDS: usize = 3;
+
+/// A task described on a single line, e.g. `Write report due:fri +work #urgent !high`.
+///
+/// - `due:<date>` sets the due date (ISO or any phrase the due date field accepts)
+/// - `+group` sets the group
+/// - `#tag` adds a tag
+/// - `!high`, `!medium`, `!low` (or `!1`..`!3`) set the priority
+///
+/// Every other word becomes part of the title.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickAdd {
+    pub title: String,
+    pub due_date: Option<Date>,
+    pub group: Option<String>,
+    pub tags: Vec<String>,
+    pub priority: Option<Priority>,
+}
+
+impl QuickAdd {
+    pub fn parse(input: &str) -> ValidationResult<Self> {
+        let words: Vec<&str> = input.split_whitespace().collect();
+
+        let mut title_words = Vec::new();
+        let mut due_date = None;
+        let mut group = None;
+        let mut tags = Vec::new();
+        let mut priority = None;
+
+        let mut index = 0;
+        while index < words.len() {
+            let word = words[index];
+            index += 1;
+
+            if let Some(due) = word.strip_prefix("due:") {
+                let (date, consumed) = Self::parse_due(due, &words[index..])?;
+                due_date = Some(TaskValidator::validate_due_date(date)?);
+                index += consumed;
+            } else if let Some(name) = word.strip_prefix('+').filter(|name| !name.is_empty()) {
+                group = Some(TaskValidator::validate_group(name)?);
+            } else if let Some(tag) = word.strip_prefix('#').filter(|tag| !tag.is_empty()) {
+                let tag = TaskValidator::validate_tag(tag)?;
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            } else if let Some(level) = word.strip_prefix('!').filter(|level| !level.is_empty()) {
+                priority =
+                    Some(
+                        Priority::parse(level).ok_or_else(|| AppError::InvalidPriority {
+                            input: level.to_string(),
+                        })?,
+                    );
+            } else {
+                title_words.push(word);
+            }
+        }
+
+        let title = TaskValidator::validate_title(&title_words.join(" "))?;
+
+        Ok(Self {
+            title,
+            due_date,
+            group,
+            tags,
+            priority,
+        })
+    }
+
+    /// Resolves the `due:` value, borrowing following words for phrases like
+    /// `due:next monday`. Returns the date and how many extra words were used.
+    fn parse_due(first: &str, rest: &[&str]) -> ValidationResult<(Date, usize)> {
+        let mut phrase = first.to_string();
+        let first_attempt = DateValidator::resolve_date_input(&phrase);
+
+        if let Ok(date) = first_attempt {
+            return Ok((date, 0));
+        }
+
+        for (consumed, word) in rest.iter().take(MAX_DUE_WORDS - 1).enumerate() {
+            phrase.push(' ');
+            phrase.push_str(word);
+
+            if let Ok(date) = DateValidator::resolve_date_input(&phrase) {
+                return Ok((date, consumed + 1));
+            }
+        }
+
+        first_attempt.map(|date| (date, 0))
+    }
+
+    pub fn into_task(self) -> Task {
+        let mut task = Task::new(self.title, Vec::new(), self.due_date);
+        task.group = self.group;
+        task.tags = self.tags;
+        task.priority = self.priority;
+        task
+    }
+
+    /// One-line summary of how the input was understood, shown before confirming.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!("\"{}\"", self.title)];
+
+        if let Some(date) = self.due_date {
+            let date = date
+                .format(format_description!(
+                    "[weekday repr:short], [year]-[month]-[day]"
+                ))
+                .unwrap_or_default();
+            parts.push(format!("due {}", date));
+        }
+        if let Some(group) = &self.group {
+            parts.push(format!("group {}", group));
+        }
+        if !self.tags.is_empty() {
+            let tags: Vec<String> = self.tags.iter().map(|tag| format!("#{}", tag)).collect();
+            parts.push(format!("tags {}", tags.join(" ")));
+        }
+        if let Some(priority) = self.priority {
+            parts.push(format!("priority {}", priority));
+        }
+
+        parts.join(" · ")
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use time::{OffsetDateTime, Time};
+
+use super::{
+    error::{AppError, AppResult},
+    task::Task,
+};
+
+/// How long the notification banner stays up before it dismisses itself.
+const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A reminder that has just gone off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FiredReminder {
+    pub title: String,
+    pub message: String,
+}
+
+/// Marks every reminder on an open task whose time is at or before `now` as fired
+/// and returns them. Reminders missed while the app was closed go off on the next
+/// check.
+pub fn fire_due_reminders(
+    tasks: &mut [Task],
+    now: OffsetDateTime,
+    due_time: Time,
+) -> Vec<FiredReminder> {
+    let mut fired = Vec::new();
+
+    for task in tasks.iter_mut().filter(|task| task.is_open()) {
+        let mut goes_off = false;
+
+        for index in 0..task.reminders.len() {
+            let reminder = &task.reminders[index];
+            if reminder.fired {
+                continue;
+            }
+            if let Some(at) = task.reminder_at(reminder, due_time)
+                && at <= now
+            {
+                task.reminders[index].fired = true;
+                goes_off = true;
+            }
+        }
+
+        if goes_off {
+            let message = match task.due_date {
+                Some(due_date) => format!("{} (due {})", task.title, due_date),
+                None => task.title.clone(),
+            };
+            fired.push(FiredReminder {
+                title: task.title.clone(),
+                message,
+            });
+        }
+    }
+
+    fired
+}
+
+/// Runs the configured reminder command without waiting for it. `{title}` and
+/// `{message}` in the arguments are replaced with the reminder's.
+pub fn run_reminder_command(command: &[String], reminder: &FiredReminder) -> AppResult<()> {
+    let Some((program, args)) = command.split_first() else {
+        return Ok(());
+    };
+
+    let args = args.iter().map(|arg| {
+        arg.replace("{title}", &reminder.title)
+            .replace("{message}", &reminder.message)
+    });
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|error| AppError::ReminderCommandFailed {
+            command: program.clone(),
+            reason: error.to_string(),
+        })?;
+
+    // Reap the child in the background so it does not linger as a zombie
+    thread::spawn(move || child.wait());
+
+    Ok(())
+}
+
+/// The banner shown in the TUI when reminders go off.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub message: String,
+    pub shown_at: Instant,
+}
+
+impl Notification {
+    pub fn new(fired: &[FiredReminder]) -> Option<Self> {
+        let message = match fired {
+            [] => return None,
+            [reminder] => reminder.message.clone(),
+            reminders => format!(
+                "{} reminders: {}",
+                reminders.len(),
+                reminders
+                    .iter()
+                    .map(|reminder| reminder.title.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+
+        Some(Self {
+            message,
+            shown_at: Instant::now(),
+        })
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.shown_at.elapsed() >= NOTIFICATION_TIMEOUT
+    }
+}
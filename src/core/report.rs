@@ -0,0 +1,278 @@
+//! Self-contained HTML reports of a view, for pasting into status emails or
+//! printing. Tasks are picked and ordered the way the list shows them.
+
+use std::collections::BTreeMap;
+
+use time::{Date, Duration, macros::format_description};
+
+use super::{
+    error::AppResult,
+    task::{SmartView, Task, TaskList, task::Status},
+    validation::DateValidator,
+};
+
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; color: #1f2328; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; }
+h1 { font-size: 1.5rem; margin-bottom: 0.25rem; }
+h2 { font-size: 1.15rem; border-bottom: 1px solid #d0d7de; padding-bottom: 0.25rem; margin-top: 1.75rem; }
+.meta { color: #656d76; margin-top: 0; }
+ul { list-style: none; padding: 0; }
+li { margin: 0.6rem 0; }
+.title { font-weight: 600; }
+.closed .title { color: #656d76; }
+.cancelled .title { text-decoration: line-through; }
+.badge { display: inline-block; font-size: 0.75rem; font-weight: 600; padding: 0.05rem 0.45rem; border-radius: 0.6rem; margin-right: 0.35rem; color: #fff; }
+.todo { background: #0969da; }
+.in-progress { background: #bf8700; }
+.blocked { background: #cf222e; }
+.done { background: #1a7f37; }
+.cancelled-badge { background: #6e7781; }
+.details { color: #656d76; font-size: 0.85rem; margin-left: 0.35rem; }
+.overdue { color: #cf222e; font-weight: 600; }
+.description { margin: 0.25rem 0 0 0; color: #424a53; font-size: 0.9rem; }
+";
+
+/// How the tasks of a report are split under headings.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReportGrouping {
+    #[default]
+    None,
+    Group,
+    Status,
+}
+
+impl ReportGrouping {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "none" => Some(ReportGrouping::None),
+            "group" | "project" => Some(ReportGrouping::Group),
+            "status" => Some(ReportGrouping::Status),
+            _ => None,
+        }
+    }
+}
+
+/// Which tasks a report shows and how.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReportOptions {
+    pub view: SmartView,
+    /// Leaves out completed and cancelled tasks.
+    pub open_only: bool,
+    /// Only tasks completed or cancelled on or after this date.
+    pub closed_since: Option<Date>,
+    pub grouping: ReportGrouping,
+    /// The heading of the report. Defaults to a description of the filters.
+    pub title: Option<String>,
+}
+
+impl ReportOptions {
+    fn default_title(&self) -> String {
+        let mut title = if self.open_only {
+            format!("Open tasks: {}", self.view)
+        } else {
+            format!("Tasks: {}", self.view)
+        };
+        if let Some(since) = self.closed_since {
+            title.push_str(&format!(", closed since {}", format_date(since)));
+        }
+        match self.grouping {
+            ReportGrouping::None => {}
+            ReportGrouping::Group => title.push_str(", by group"),
+            ReportGrouping::Status => title.push_str(", by status"),
+        }
+        title
+    }
+}
+
+/// Resolves `--since` values: `week` is this Monday, `month` the first of this
+/// month, and anything else a date as the due date field reads it.
+pub fn resolve_since(input: &str, today: Date) -> AppResult<Date> {
+    let since = match input.trim().to_lowercase().as_str() {
+        "week" => today.checked_sub(Duration::days(
+            today.weekday().number_days_from_monday().into(),
+        )),
+        "month" => today.replace_day(1).ok(),
+        _ => None,
+    };
+
+    match since {
+        Some(since) => Ok(since),
+        None => DateValidator::resolve_date_input(input),
+    }
+}
+
+/// Writes the tasks matching `options` as a complete HTML page.
+pub fn build_html(tasks: &[Task], options: &ReportOptions, today: Date) -> String {
+    // The list decides which tasks a view holds and their order
+    let list = TaskList {
+        task_list: tasks.to_vec(),
+        view: options.view,
+        ..TaskList::default()
+    };
+    let tasks: Vec<&Task> = list
+        .get_sorted_tasks()
+        .into_iter()
+        .filter(|task| !options.open_only || task.is_open())
+        .filter(|task| match options.closed_since {
+            Some(since) => task.closed_on().is_some_and(|closed_on| closed_on >= since),
+            None => true,
+        })
+        .collect();
+
+    let title = options
+        .title
+        .clone()
+        .unwrap_or_else(|| options.default_title());
+    let count = match tasks.len() {
+        1 => "1 task".to_string(),
+        count => format!("{} tasks", count),
+    };
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape(&title)));
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+    html.push_str(&format!("<h1>{}</h1>\n", escape(&title)));
+    html.push_str(&format!(
+        "<p class=\"meta\">{} · generated {}</p>\n",
+        count,
+        format_date(today)
+    ));
+
+    for (heading, tasks) in sections(&tasks, options.grouping) {
+        if let Some(heading) = heading {
+            html.push_str(&format!("<h2>{}</h2>\n", escape(&heading)));
+        }
+        html.push_str("<ul>\n");
+        for task in tasks {
+            html.push_str(&to_item(task, today));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Splits the tasks under headings, keeping their order within each. Grouped
+/// tasks come by group name with ungrouped ones last; statuses in workflow order.
+fn sections<'a>(
+    tasks: &[&'a Task],
+    grouping: ReportGrouping,
+) -> Vec<(Option<String>, Vec<&'a Task>)> {
+    match grouping {
+        ReportGrouping::None => vec![(None, tasks.to_vec())],
+        ReportGrouping::Group => {
+            let mut groups: BTreeMap<(bool, &str), Vec<&Task>> = BTreeMap::new();
+            for task in tasks {
+                let key = match task.group.as_deref() {
+                    Some(group) => (false, group),
+                    None => (true, "No group"),
+                };
+                groups.entry(key).or_default().push(task);
+            }
+            groups
+                .into_iter()
+                .map(|((_, group), tasks)| (Some(group.to_string()), tasks))
+                .collect()
+        }
+        ReportGrouping::Status => {
+            let mut statuses: BTreeMap<Status, Vec<&Task>> = BTreeMap::new();
+            for task in tasks {
+                statuses.entry(task.status).or_default().push(task);
+            }
+            statuses
+                .into_iter()
+                .map(|(status, tasks)| (Some(status.label().to_string()), tasks))
+                .collect()
+        }
+    }
+}
+
+fn to_item(task: &Task, today: Date) -> String {
+    let mut classes = Vec::new();
+    if task.status.is_closed() {
+        classes.push("closed");
+    }
+    if task.status == Status::Cancelled {
+        classes.push("cancelled");
+    }
+
+    let mut details = Vec::new();
+    if let Some(due_date) = task.due_date {
+        let due = format!("due {}", format_date(due_date));
+        if task.is_overdue(today) {
+            details.push(format!("<span class=\"overdue\">{}</span>", due));
+        } else {
+            details.push(due);
+        }
+    }
+    if let Some(closed_on) = task.status.is_closed().then(|| task.closed_on()).flatten() {
+        details.push(format!("closed {}", format_date(closed_on)));
+    }
+    if let Some(priority) = task.priority {
+        details.push(format!("{} priority", priority.to_string().to_lowercase()));
+    }
+    if !task.tags.is_empty() {
+        let tags: Vec<String> = task
+            .tags
+            .iter()
+            .map(|tag| format!("#{}", escape(tag)))
+            .collect();
+        details.push(tags.join(" "));
+    }
+    if let Some(reason) = &task.blocked_reason {
+        details.push(format!("blocked: {}", escape(reason)));
+    }
+
+    let class = if classes.is_empty() {
+        String::new()
+    } else {
+        format!(" class=\"{}\"", classes.join(" "))
+    };
+    let mut item = format!(
+        "<li{}><span class=\"badge {}\">{}</span><span class=\"title\">{}</span>",
+        class,
+        badge_class(task.status),
+        task.status.label(),
+        escape(&task.title)
+    );
+    if !details.is_empty() {
+        item.push_str(&format!(
+            "<span class=\"details\">{}</span>",
+            details.join(" · ")
+        ));
+    }
+    if !task.description.is_empty() {
+        let lines: Vec<String> = task.description.iter().map(|line| escape(line)).collect();
+        item.push_str(&format!(
+            "<p class=\"description\">{}</p>",
+            lines.join("<br>")
+        ));
+    }
+    item.push_str("</li>\n");
+    item
+}
+
+fn badge_class(status: Status) -> &'static str {
+    match status {
+        Status::Todo => "todo",
+        Status::InProgress => "in-progress",
+        Status::Blocked => "blocked",
+        Status::Completed => "done",
+        Status::Cancelled => "cancelled-badge",
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn format_date(date: Date) -> String {
+    date.format(format_description!("[year]-[month]-[day]"))
+        .unwrap_or_default()
+}
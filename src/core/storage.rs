@@ -1,9 +1,19 @@
-use std::{fs, path::PathBuf};
+use std::{collections::HashSet, fs, path::PathBuf};
+
+use time::Duration;
+use uuid::Uuid;
 
 use super::{
     error::{AppError, AppResult},
+    formats::org,
+    sync::merge::record_changes,
     task::task::Task,
 };
+use crate::utils::{config::Config, date::now_timestamp};
+
+/// How long a tombstone is kept. Copies that sync less often than this may
+/// bring a deleted task back.
+const TOMBSTONE_LIFETIME: Duration = Duration::days(90);
 
 #[derive(Debug)]
 pub struct Storage {
@@ -15,7 +25,39 @@ impl Storage {
         Self { file_path }
     }
 
-    pub fn save(&self, tasks: &[Task]) -> AppResult<()> {
+    /// Saves tasks and the tombstones of deleted ones, recording what changed
+    /// since the saved copy. Tasks in the file that are not among `records`,
+    /// such as ones a sync added meanwhile, are kept, and tombstones older than
+    /// [`TOMBSTONE_LIFETIME`] are dropped. The Org file set in `org.autoExport`
+    /// is rewritten as well.
+    pub fn save(&self, records: &[Task]) -> AppResult<()> {
+        let previous = self.load_records().unwrap_or_default();
+        let now = now_timestamp();
+
+        let mut records = record_changes(&previous, records, now);
+        let saved: HashSet<Uuid> = records.iter().map(|task| task.id).collect();
+        records.extend(
+            previous
+                .into_iter()
+                .filter(|task| !saved.contains(&task.id)),
+        );
+        records.retain(|task| {
+            task.deleted_at
+                .is_none_or(|deleted_at| now - deleted_at < TOMBSTONE_LIFETIME)
+        });
+
+        self.write(&records)?;
+        export_org(&records)
+    }
+
+    /// Rewrites the Org file set in `org.autoExport` from the saved tasks, as
+    /// after the file changed underneath, e.g. by a git pull.
+    pub fn export_org(&self) -> AppResult<()> {
+        export_org(&self.load_records()?)
+    }
+
+    /// Writes tasks and tombstones as they are, as after a merge.
+    pub fn write(&self, records: &[Task]) -> AppResult<()> {
         if let Some(parent) = self.file_path.parent() {
             fs::create_dir_all(parent).map_err(|err| AppError::SaveFailed {
                 path: self.file_path.display().to_string(),
@@ -23,7 +65,7 @@ impl Storage {
             })?;
         }
 
-        let json = serde_json::to_string_pretty(tasks).map_err(|err| AppError::SaveFailed {
+        let json = serde_json::to_string_pretty(records).map_err(|err| AppError::SaveFailed {
             path: self.file_path.display().to_string(),
             reason: format!("Failed to serialize tasks: {}", err),
         })?;
@@ -34,7 +76,15 @@ impl Storage {
         })
     }
 
+    /// Loads the tasks, leaving out the tombstones of deleted ones.
     pub fn load(&self) -> AppResult<Vec<Task>> {
+        let mut tasks = self.load_records()?;
+        tasks.retain(|task| task.deleted_at.is_none());
+        Ok(tasks)
+    }
+
+    /// Loads the tasks and tombstones in the file.
+    pub fn load_records(&self) -> AppResult<Vec<Task>> {
         if !self.file_path.exists() {
             return Ok(Vec::new());
         }
@@ -50,3 +100,20 @@ impl Storage {
         })
     }
 }
+
+/// Rewrites the Org file set in `org.autoExport`, if any, with every task.
+fn export_org(records: &[Task]) -> AppResult<()> {
+    let Some(path) = Config::global().org.auto_export_path() else {
+        return Ok(());
+    };
+
+    let tasks: Vec<Task> = records
+        .iter()
+        .filter(|task| task.deleted_at.is_none())
+        .cloned()
+        .collect();
+    fs::write(&path, org::export(&tasks)).map_err(|err| AppError::ExportFailed {
+        path: path.display().to_string(),
+        reason: err.to_string(),
+    })
+}
@@ -0,0 +1,5 @@
+//! Keeping the task list in step across machines.
+
+pub mod caldav;
+pub mod git;
+pub mod merge;
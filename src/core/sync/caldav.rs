@@ -0,0 +1,580 @@
+//! Two-way sync of tasks with a CalDAV task calendar, such as one on Nextcloud
+//! or Radicale, so they show up on phones.
+//!
+//! Each task is stored on the server as one VTODO resource, matched to the task
+//! by its id. The ETag of every resource and a fingerprint of the task as last
+//! synced are kept in `caldav.json` next to the task file. A changed ETag means
+//! the task changed on the server, a changed fingerprint that it changed here.
+//! When both changed, the configured [`ConflictRule`] picks the side that wins.
+//! A task deleted on one side is deleted on the other, unless the other side
+//! changed it, in which case it is kept.
+//!
+//! Only what iCalendar holds is synced. Time entries, reminders, estimates and
+//! dependencies stay on this machine.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::{Deserialize, Serialize};
+use ureq::{Agent, AgentBuilder, OrAnyStatus, Response};
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        error::{AppError, AppResult},
+        formats::icalendar,
+        sync::merge::tombstone,
+        task::Task,
+    },
+    utils::{
+        config::{CaldavConfig, ConflictRule},
+        date::now_timestamp,
+    },
+};
+
+/// File next to the task file that remembers what was last synced.
+const STATE_FILE: &str = "caldav.json";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Lists the ETag of every VTODO in the collection.
+const TODO_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop><d:getetag/></d:prop>
+  <c:filter>
+    <c:comp-filter name="VCALENDAR"><c:comp-filter name="VTODO"/></c:comp-filter>
+  </c:filter>
+</c:calendar-query>"#;
+
+/// What is known about each synced task since the last sync.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncState {
+    /// The collection the entries belong to. Syncing another one starts over.
+    url: String,
+    entries: BTreeMap<Uuid, SyncEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncEntry {
+    /// Path of the resource on the server.
+    href: String,
+    etag: String,
+    /// The UID on the server, kept since other apps may not use the task's id.
+    uid: String,
+    /// [`fingerprint`] of the task when it was last synced.
+    fingerprint: String,
+}
+
+impl SyncState {
+    pub fn file_path(task_file: &Path) -> PathBuf {
+        task_file.with_file_name(STATE_FILE)
+    }
+
+    /// Reads the state for the collection at `url`. A missing file, or one for
+    /// another collection, gives an empty state.
+    pub fn load(path: &Path, url: &str) -> AppResult<Self> {
+        let empty = Self {
+            url: url.to_string(),
+            entries: BTreeMap::new(),
+        };
+        if !path.exists() {
+            return Ok(empty);
+        }
+
+        let contents = fs::read_to_string(path).map_err(|err| AppError::LoadFailed {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })?;
+        let state: Self = serde_json::from_str(&contents).map_err(|err| AppError::LoadFailed {
+            path: path.display().to_string(),
+            reason: format!("Invalid sync state: {}", err),
+        })?;
+
+        Ok(if state.url == url { state } else { empty })
+    }
+
+    pub fn save(&self, path: &Path) -> AppResult<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|err| AppError::SaveFailed {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })?;
+        fs::write(path, json).map_err(|err| AppError::SaveFailed {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })
+    }
+}
+
+/// What a sync changed on either side.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CaldavReport {
+    pub uploaded: usize,
+    pub downloaded: usize,
+    pub deleted_here: usize,
+    pub deleted_on_server: usize,
+    /// Tasks changed on both sides, and how each was settled.
+    pub conflicts: Vec<String>,
+}
+
+/// A resource in the collection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Resource {
+    href: String,
+    etag: String,
+}
+
+/// The result of storing a task on the server.
+enum Stored {
+    /// The new ETag, when the server sent one.
+    Saved(Option<String>),
+    /// The resource changed on the server since it was last seen.
+    Changed,
+}
+
+/// A connection to one CalDAV collection.
+pub struct CaldavClient {
+    agent: Agent,
+    /// The collection URL, ending in `/`.
+    url: String,
+    authorization: Option<String>,
+}
+
+impl CaldavClient {
+    pub fn new(config: &CaldavConfig) -> Self {
+        let mut url = config.url.trim().to_string();
+        if !url.ends_with('/') {
+            url.push('/');
+        }
+        let authorization = (!config.username.is_empty()).then(|| {
+            let credentials = format!("{}:{}", config.username, config.password);
+            format!("Basic {}", STANDARD.encode(credentials))
+        });
+
+        Self {
+            agent: AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            url,
+            authorization,
+        }
+    }
+
+    fn list(&self) -> AppResult<Vec<Resource>> {
+        let response = self.send(
+            self.request("REPORT", &self.url)
+                .set("Depth", "1")
+                .set("Content-Type", "application/xml; charset=utf-8"),
+            Some(TODO_QUERY),
+        )?;
+        if response.status() != 207 {
+            return Err(unexpected("list tasks", &response));
+        }
+
+        let body = read_body(response)?;
+        let collection = self.path_of(&self.url);
+        Ok(parse_multistatus(&body)
+            .into_iter()
+            .map(|resource| Resource {
+                href: self.path_of(&resource.href),
+                ..resource
+            })
+            .filter(|resource| resource.href != collection)
+            .collect())
+    }
+
+    /// The resource's body, or `None` if it is gone.
+    fn get(&self, href: &str) -> AppResult<Option<String>> {
+        let response = self.send(self.request("GET", &self.url_of(href)), None)?;
+        match response.status() {
+            200 => read_body(response).map(Some),
+            404 | 410 => Ok(None),
+            _ => Err(unexpected("download a task", &response)),
+        }
+    }
+
+    /// Stores `body` at `href`, only over the version with `etag` or, without
+    /// one, only if nothing is there yet.
+    fn put(&self, href: &str, body: &str, etag: Option<&str>) -> AppResult<Stored> {
+        let request = self
+            .request("PUT", &self.url_of(href))
+            .set("Content-Type", "text/calendar; charset=utf-8");
+        let request = match etag {
+            Some(etag) => request.set("If-Match", etag),
+            None => request.set("If-None-Match", "*"),
+        };
+
+        let response = self.send(request, Some(body))?;
+        match response.status() {
+            200..=299 => Ok(Stored::Saved(response.header("ETag").map(str::to_string))),
+            412 => Ok(Stored::Changed),
+            _ => Err(unexpected("upload a task", &response)),
+        }
+    }
+
+    /// Deletes the resource if it is still the version with `etag`. Returns
+    /// whether it is gone.
+    fn delete(&self, href: &str, etag: &str) -> AppResult<bool> {
+        let response = self.send(
+            self.request("DELETE", &self.url_of(href))
+                .set("If-Match", etag),
+            None,
+        )?;
+        match response.status() {
+            200..=299 | 404 | 410 => Ok(true),
+            412 => Ok(false),
+            _ => Err(unexpected("delete a task", &response)),
+        }
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        let request = self.agent.request(method, url);
+        match &self.authorization {
+            Some(authorization) => request.set("Authorization", authorization),
+            None => request,
+        }
+    }
+
+    fn send(&self, request: ureq::Request, body: Option<&str>) -> AppResult<Response> {
+        let result = match body {
+            Some(body) => request.send_string(body),
+            None => request.call(),
+        };
+        result.or_any_status().map_err(|err| AppError::SyncFailed {
+            reason: format!("cannot reach the server: {}", err),
+        })
+    }
+
+    /// The path of a URL or href, which is how resources are told apart.
+    fn path_of(&self, href: &str) -> String {
+        match href.split_once("://") {
+            Some((_, rest)) => rest
+                .find('/')
+                .map_or("/".to_string(), |start| rest[start..].to_string()),
+            None => href.to_string(),
+        }
+    }
+
+    fn url_of(&self, href: &str) -> String {
+        let origin_end = self
+            .url
+            .split_once("://")
+            .and_then(|(scheme, rest)| rest.find('/').map(|start| scheme.len() + 3 + start))
+            .unwrap_or(self.url.len());
+        format!("{}{}", &self.url[..origin_end], href)
+    }
+
+    /// Where a task new to the server is stored.
+    fn href_for(&self, id: Uuid) -> String {
+        format!("{}{}.ics", self.path_of(&self.url), id)
+    }
+}
+
+/// Syncs `tasks`, which holds no tombstones, with the server in both directions
+/// and records the result in `state`. Tasks deleted on the server are left in
+/// `tasks` as tombstones.
+pub fn sync(
+    tasks: &mut Vec<Task>,
+    state: &mut SyncState,
+    client: &CaldavClient,
+    rule: ConflictRule,
+) -> AppResult<CaldavReport> {
+    let mut report = CaldavReport::default();
+    let remote: HashMap<String, String> = client
+        .list()?
+        .into_iter()
+        .map(|resource| (resource.href, resource.etag))
+        .collect();
+    let tracked: HashMap<String, Uuid> = state
+        .entries
+        .iter()
+        .map(|(id, entry)| (entry.href.clone(), *id))
+        .collect();
+
+    // Tasks that are new or changed on the server
+    for (href, etag) in &remote {
+        let known = tracked.get(href).and_then(|id| state.entries.get(id));
+        if known.is_some_and(|entry| entry.etag == *etag) {
+            continue;
+        }
+
+        let Some(body) = client.get(href)? else {
+            continue;
+        };
+        let Some(remote_task) = parse_resource(&body) else {
+            report
+                .conflicts
+                .push(format!("{}: not a task delibird can read, skipped", href));
+            continue;
+        };
+        let id = remote_task.id;
+        let uid = read_uid(&body).unwrap_or_else(|| id.to_string());
+
+        let entry = match tasks.iter_mut().find(|task| task.id == id) {
+            Some(local) => {
+                let changed_here = match known {
+                    Some(entry) => fingerprint(local) != entry.fingerprint,
+                    // Already here before it was ever synced
+                    None => fingerprint(local) != fingerprint(&remote_task),
+                };
+                if changed_here && rule == ConflictRule::Local {
+                    report.conflicts.push(format!(
+                        "{}: changed on both sides, kept this machine's",
+                        local.title
+                    ));
+                    // Uploaded below over the server's version
+                    SyncEntry {
+                        href: href.clone(),
+                        etag: etag.clone(),
+                        uid,
+                        fingerprint: known
+                            .map(|entry| entry.fingerprint.clone())
+                            .unwrap_or_default(),
+                    }
+                } else {
+                    if changed_here {
+                        report.conflicts.push(format!(
+                            "{}: changed on both sides, kept the server's",
+                            local.title
+                        ));
+                    }
+                    let before = fingerprint(local);
+                    apply_remote(local, remote_task);
+                    // A new ETag can also just be the server storing our own upload
+                    if fingerprint(local) != before {
+                        report.downloaded += 1;
+                    }
+                    SyncEntry {
+                        href: href.clone(),
+                        etag: etag.clone(),
+                        uid,
+                        fingerprint: fingerprint(local),
+                    }
+                }
+            }
+            None => {
+                if known.is_some() {
+                    report.conflicts.push(format!(
+                        "{}: deleted here but changed on the server, restored",
+                        remote_task.title
+                    ));
+                }
+                let entry = SyncEntry {
+                    href: href.clone(),
+                    etag: etag.clone(),
+                    uid,
+                    fingerprint: fingerprint(&remote_task),
+                };
+                tasks.push(remote_task);
+                report.downloaded += 1;
+                entry
+            }
+        };
+        state.entries.insert(id, entry);
+    }
+
+    // Tasks that are new or changed here, or gone from the server
+    let mut deleted_here = HashSet::new();
+    for task in tasks.iter() {
+        let entry = state.entries.get(&task.id).cloned();
+        let changed_here = entry
+            .as_ref()
+            .is_none_or(|entry| fingerprint(task) != entry.fingerprint);
+
+        let (href, uid, etag) = match &entry {
+            None => (client.href_for(task.id), task.id.to_string(), None),
+            Some(entry) if !remote.contains_key(&entry.href) => {
+                if !changed_here {
+                    deleted_here.insert(task.id);
+                    continue;
+                }
+                report.conflicts.push(format!(
+                    "{}: deleted on the server but changed here, uploaded again",
+                    task.title
+                ));
+                (entry.href.clone(), entry.uid.clone(), None)
+            }
+            Some(_) if !changed_here => continue,
+            Some(entry) => (
+                entry.href.clone(),
+                entry.uid.clone(),
+                Some(entry.etag.clone()),
+            ),
+        };
+
+        match client.put(&href, &to_resource(task, &uid), etag.as_deref())? {
+            Stored::Saved(new_etag) => {
+                report.uploaded += 1;
+                state.entries.insert(
+                    task.id,
+                    SyncEntry {
+                        href,
+                        // Picked up on the next listing when the server sends none
+                        etag: new_etag.unwrap_or_default(),
+                        uid,
+                        fingerprint: fingerprint(task),
+                    },
+                );
+            }
+            Stored::Changed => report.conflicts.push(format!(
+                "{}: changed on the server during the sync, left for the next one",
+                task.title
+            )),
+        }
+    }
+
+    report.deleted_here = deleted_here.len();
+    let now = now_timestamp();
+    for task in tasks
+        .iter_mut()
+        .filter(|task| deleted_here.contains(&task.id))
+    {
+        *task = tombstone(task, now);
+    }
+    for id in deleted_here {
+        state.entries.remove(&id);
+    }
+
+    // Tasks deleted here since the last sync
+    let local: HashSet<Uuid> = tasks
+        .iter()
+        .filter(|task| task.deleted_at.is_none())
+        .map(|task| task.id)
+        .collect();
+    let gone: Vec<(Uuid, SyncEntry)> = state
+        .entries
+        .iter()
+        .filter(|(id, _)| !local.contains(id))
+        .map(|(id, entry)| (*id, entry.clone()))
+        .collect();
+    for (id, entry) in gone {
+        if let Some(etag) = remote.get(&entry.href)
+            && client.delete(&entry.href, etag)?
+        {
+            report.deleted_on_server += 1;
+        }
+        state.entries.remove(&id);
+    }
+
+    Ok(report)
+}
+
+/// Takes over what the server holds for a task, keeping what only lives here.
+fn apply_remote(local: &mut Task, remote: Task) {
+    if local.status != remote.status {
+        local.set_status(remote.status, local.blocked_reason.clone());
+    }
+    local.title = remote.title;
+    local.description = remote.description;
+    local.due_date = remote.due_date;
+    local.completed_at = remote.completed_at;
+    local.priority = remote.priority;
+    local.tags = remote.tags;
+    local.group = remote.group;
+}
+
+/// The task as a calendar resource with a single VTODO, under the server's UID.
+fn to_resource(task: &Task, uid: &str) -> String {
+    icalendar::export(std::slice::from_ref(task))
+        .replace(&format!("UID:{}", task.id), &format!("UID:{}", uid))
+}
+
+fn parse_resource(body: &str) -> Option<Task> {
+    icalendar::import(body).ok()?.into_iter().next()
+}
+
+fn read_uid(body: &str) -> Option<String> {
+    body.lines()
+        .find_map(|line| line.strip_prefix("UID:"))
+        .map(|uid| uid.trim().to_string())
+}
+
+/// Identifies what is synced of a task. The timestamp of the export is left
+/// out so the same task always gives the same fingerprint.
+fn fingerprint(task: &Task) -> String {
+    let resource: String = to_resource(task, &task.id.to_string())
+        .lines()
+        .filter(|line| !line.starts_with("DTSTAMP:"))
+        .collect();
+    Uuid::new_v5(&Uuid::NAMESPACE_OID, resource.as_bytes()).to_string()
+}
+
+/// Picks the href and ETag of each response out of a WebDAV multistatus body.
+/// Namespace prefixes differ between servers, so elements are matched by their
+/// local name.
+fn parse_multistatus(body: &str) -> Vec<Resource> {
+    element_texts(body, "response")
+        .into_iter()
+        .filter_map(|response| {
+            let href = element_texts(response, "href").into_iter().next()?;
+            let etag = element_texts(response, "getetag").into_iter().next()?;
+            Some(Resource {
+                href: unescape_xml(href.trim()),
+                etag: unescape_xml(etag.trim()),
+            })
+        })
+        .collect()
+}
+
+/// The contents of every `name` element in `xml`, whatever its prefix.
+fn element_texts<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let mut texts = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..tag_end];
+        let tag_name = tag.split_whitespace().next().unwrap_or_default();
+        let local_name = tag_name.rsplit(':').next().unwrap_or_default();
+        if local_name != name || tag.ends_with('/') {
+            continue;
+        }
+
+        let content = &rest[tag_end + 1..];
+        let closing = format!("</{}>", tag_name);
+        if let Some(end) = content.find(&closing) {
+            texts.push(&content[..end]);
+            rest = &content[end + closing.len()..];
+        }
+    }
+
+    texts
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn read_body(response: Response) -> AppResult<String> {
+    response.into_string().map_err(|err| AppError::SyncFailed {
+        reason: format!("cannot read the server's answer: {}", err),
+    })
+}
+
+fn unexpected(action: &str, response: &Response) -> AppError {
+    AppError::SyncFailed {
+        reason: match response.status() {
+            401 | 403 => format!(
+                "the server refused to {}; check caldav.username and caldav.password",
+                action
+            ),
+            status => format!(
+                "could not {}: {} {}",
+                action,
+                status,
+                response.status_text()
+            ),
+        },
+    }
+}
@@ -0,0 +1,391 @@
+//! Versioning the data directory with git.
+//!
+//! Every save is committed with a message describing what changed, such as
+//! `complete: Write report`. `delibird sync` pulls from and pushes to a remote,
+//! and a merge driver registered for the task file merges both sides task by
+//! task, keyed by id, so concurrent edits on two machines never leave conflict
+//! markers in the JSON.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    time::{Duration, Instant},
+};
+
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        error::{AppError, AppResult},
+        sync::merge::{MergeOutcome, changed_at, fields, merge, record_changes, record_deletions},
+        task::{Task, task::Status},
+    },
+    utils::{config::Config, date::now_timestamp},
+};
+
+/// How long the app waits after the last save before committing, so a burst
+/// of changes becomes one commit.
+const COMMIT_DELAY: Duration = Duration::from_secs(10);
+
+/// Name of the merge driver in the git configuration.
+const MERGE_DRIVER: &str = "delibird";
+
+/// Starts each conflict the merge driver prints, so a sync can pick them out of
+/// git's output.
+pub const CONFLICT_PREFIX: &str = "delibird merge: ";
+
+/// The git repository around the task file.
+#[derive(Debug)]
+pub struct GitRepo {
+    dir: PathBuf,
+    file_name: String,
+    /// `-c` options naming a committer when git has none configured.
+    identity: Vec<String>,
+}
+
+/// What a sync did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// The message of the commit made for uncommitted changes, if any.
+    pub committed: Option<String>,
+    /// Whether changes from the remote were merged in.
+    pub pulled: bool,
+    pub pushed: bool,
+    /// Conflicts the merge driver resolved while pulling.
+    pub conflicts: Vec<String>,
+}
+
+impl GitRepo {
+    /// Opens the repository holding `file_path`, creating it if needed, and
+    /// registers the merge driver for the task file.
+    pub fn open(file_path: &Path) -> AppResult<Self> {
+        let dir = file_path
+            .parent()
+            .ok_or_else(|| failed("the task file has no directory"))?
+            .to_path_buf();
+        let file_name = file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| failed("the task file has no name"))?
+            .to_string();
+
+        let mut repo = Self {
+            dir,
+            file_name,
+            identity: Vec::new(),
+        };
+
+        if !repo.dir.join(".git").exists() {
+            repo.git(&["init", "--quiet"])?;
+        }
+        if !repo.succeeds(&["config", "user.email"])? {
+            repo.identity = vec![
+                "-c".to_string(),
+                "user.name=delibird".to_string(),
+                "-c".to_string(),
+                "user.email=delibird@localhost".to_string(),
+            ];
+        }
+
+        let executable = std::env::current_exe()
+            .map_err(|err| failed(&format!("cannot find the delibird executable: {}", err)))?;
+        repo.set_config(
+            &format!("merge.{}.name", MERGE_DRIVER),
+            "delibird task merge",
+        )?;
+        repo.set_config(
+            &format!("merge.{}.driver", MERGE_DRIVER),
+            &format!("\"{}\" merge-driver %O %A %B", executable.display()),
+        )?;
+
+        // Attributes the user set for other files stay as they are
+        let attribute = format!("{} merge={}", repo.file_name, MERGE_DRIVER);
+        let attributes_path = repo.dir.join(".gitattributes");
+        let mut attributes = fs::read_to_string(&attributes_path).unwrap_or_default();
+        if !attributes.lines().any(|line| line.trim() == attribute) {
+            if !attributes.is_empty() && !attributes.ends_with('\n') {
+                attributes.push('\n');
+            }
+            attributes.push_str(&attribute);
+            attributes.push('\n');
+            fs::write(&attributes_path, attributes)
+                .map_err(|err| failed(&format!("cannot write .gitattributes: {}", err)))?;
+        }
+
+        Ok(repo)
+    }
+
+    /// Commits the task file if it changed since the last commit. Returns the
+    /// commit message.
+    pub fn commit_changes(&self) -> AppResult<Option<String>> {
+        // A new machine has no task file until its first sync pulls one
+        let mut paths = vec!["--", ".gitattributes"];
+        if self.dir.join(&self.file_name).exists() {
+            paths.push(&self.file_name);
+        }
+        self.git(&[&["add"], paths.as_slice()].concat())?;
+        if self.succeeds(&["diff", "--cached", "--quiet"])? {
+            return Ok(None);
+        }
+
+        let before = self
+            .git(&["show", &format!("HEAD:{}", self.file_name)])
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+            .unwrap_or_default();
+        let after = fs::read_to_string(self.dir.join(&self.file_name)).unwrap_or_default();
+        let message = commit_message(&parse_tasks(&before)?, &parse_tasks(&after)?);
+
+        self.git(&["commit", "--quiet", "-m", &message])?;
+        Ok(Some(message))
+    }
+
+    /// Commits pending changes, merges in the remote's and pushes the result.
+    pub fn sync(&self, remote: &str) -> AppResult<SyncReport> {
+        let mut report = SyncReport {
+            committed: self.commit_changes()?,
+            ..SyncReport::default()
+        };
+
+        let branch = self.stdout(&["symbolic-ref", "--short", "HEAD"])?;
+        let head = self
+            .stdout(&["rev-parse", "--verify", "--quiet", "HEAD"])
+            .ok();
+
+        // A new remote has nothing to pull yet
+        if self.succeeds(&["ls-remote", "--exit-code", "--heads", remote, &branch])? {
+            let output = self.git(&[
+                "pull",
+                "--quiet",
+                "--no-rebase",
+                "--no-edit",
+                "--allow-unrelated-histories",
+                remote,
+                &branch,
+            ])?;
+            report.pulled = self.stdout(&["rev-parse", "HEAD"]).ok() != head;
+            report.conflicts = String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .filter_map(|line| line.strip_prefix(CONFLICT_PREFIX))
+                .map(str::to_string)
+                .collect();
+        }
+
+        self.git(&["push", "--quiet", remote, &format!("HEAD:{}", branch)])?;
+        report.pushed = true;
+
+        Ok(report)
+    }
+
+    /// Sets a repository config entry unless it already holds `value`.
+    fn set_config(&self, key: &str, value: &str) -> AppResult<()> {
+        if self.stdout(&["config", "--get", key]).ok().as_deref() != Some(value) {
+            self.git(&["config", key, value])?;
+        }
+        Ok(())
+    }
+
+    /// Runs git, failing with its error output when it does not succeed.
+    fn git(&self, args: &[&str]) -> AppResult<Output> {
+        let output = self.run(args)?;
+        if output.status.success() {
+            Ok(output)
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(failed(&format!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                stderr.trim()
+            )))
+        }
+    }
+
+    fn stdout(&self, args: &[&str]) -> AppResult<String> {
+        self.git(args)
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Whether git exits successfully, for commands that answer through their status.
+    fn succeeds(&self, args: &[&str]) -> AppResult<bool> {
+        self.run(args).map(|output| output.status.success())
+    }
+
+    fn run(&self, args: &[&str]) -> AppResult<Output> {
+        Command::new("git")
+            .args(&self.identity)
+            .args(args)
+            .current_dir(&self.dir)
+            .output()
+            .map_err(|err| failed(&format!("cannot run git: {}", err)))
+    }
+}
+
+/// Commits the app's saves once they settle: a commit follows the last of a
+/// batch of saves after [`COMMIT_DELAY`], and pending saves are committed on quit.
+#[derive(Debug)]
+pub struct AutoCommit {
+    repo: GitRepo,
+    /// When the pending saves are due to be committed.
+    due: Option<Instant>,
+}
+
+impl AutoCommit {
+    /// Opens the repository for the task file at `file_path`, or gives `None`
+    /// when git sync is turned off.
+    pub fn open(file_path: &Path) -> AppResult<Option<Self>> {
+        if !Config::global().git.enabled {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            repo: GitRepo::open(file_path)?,
+            due: None,
+        }))
+    }
+
+    /// Notes a save at `now`, pushing the commit back.
+    pub fn saved(&mut self, now: Instant) {
+        self.due = Some(now + COMMIT_DELAY);
+    }
+
+    /// Commits the pending saves if they have settled by `now`.
+    pub fn commit_if_due(&mut self, now: Instant) -> AppResult<Option<String>> {
+        match self.due {
+            Some(due) if due <= now => self.commit_pending(),
+            _ => Ok(None),
+        }
+    }
+
+    /// Commits the pending saves right away, as on quit.
+    pub fn commit_pending(&mut self) -> AppResult<Option<String>> {
+        if self.due.take().is_none() {
+            return Ok(None);
+        }
+        self.repo.commit_changes()
+    }
+}
+
+/// Commits the task file at `file_path` when git sync is turned on.
+pub fn auto_commit(file_path: &Path) -> AppResult<Option<String>> {
+    if !Config::global().git.enabled {
+        return Ok(None);
+    }
+    GitRepo::open(file_path)?.commit_changes()
+}
+
+/// Describes the changes from `before` to `after`, one line per task, e.g.
+/// `complete: Write report`.
+pub fn describe_changes(before: &[Task], after: &[Task]) -> Vec<String> {
+    // Tombstones stand for tasks that are gone
+    let before: Vec<&Task> = before
+        .iter()
+        .filter(|task| task.deleted_at.is_none())
+        .collect();
+    let after: Vec<&Task> = after
+        .iter()
+        .filter(|task| task.deleted_at.is_none())
+        .collect();
+    let old: HashMap<Uuid, &Task> = before.iter().map(|task| (task.id, *task)).collect();
+    let mut changes = Vec::new();
+
+    for task in &after {
+        let verb = match old.get(&task.id) {
+            None => "add",
+            Some(previous) if fields(previous) == fields(task) => continue,
+            Some(previous) if previous.status == task.status => "edit",
+            Some(previous) if previous.status.is_closed() && task.is_open() => "reopen",
+            Some(_) => match task.status {
+                Status::Todo => "edit",
+                Status::InProgress => "start",
+                Status::Blocked => "block",
+                Status::Completed => "complete",
+                Status::Cancelled => "cancel",
+            },
+        };
+        changes.push(format!("{}: {}", verb, task.title));
+    }
+
+    let remaining: Vec<Uuid> = after.iter().map(|task| task.id).collect();
+    for task in before.iter().filter(|task| !remaining.contains(&task.id)) {
+        changes.push(format!("delete: {}", task.title));
+    }
+
+    changes
+}
+
+/// A single change becomes the subject; several get a summary with one line each below.
+fn commit_message(before: &[Task], after: &[Task]) -> String {
+    let changes = describe_changes(before, after);
+    match changes.as_slice() {
+        [] => "update tasks".to_string(),
+        [change] => change.clone(),
+        changes => format!("update {} tasks\n\n{}", changes.len(), changes.join("\n")),
+    }
+}
+
+/// Merges two versions of the task file that both started from `base`.
+///
+/// Changes from `base` that a side has no time for, as in versions saved
+/// before change times existed, count as made now, and tasks a side dropped
+/// count as deleted now. The versions then merge as
+/// [`merge`] does, and fields both sides changed are reported.
+pub fn merge_task_files(base: &str, ours: &str, theirs: &str) -> AppResult<MergeOutcome> {
+    let base = parse_tasks(base)?;
+    let now = now_timestamp();
+    let mut ours = record_changes(&base, &parse_tasks(ours)?, now);
+    let mut theirs = record_changes(&base, &parse_tasks(theirs)?, now);
+    record_deletions(&base, &mut ours, now);
+    record_deletions(&base, &mut theirs, now);
+
+    let mut conflicts = changed_on_both_sides(&base, &ours, &theirs);
+    let mut outcome = merge(&ours, &theirs);
+    conflicts.append(&mut outcome.conflicts);
+    outcome.conflicts = conflicts;
+    Ok(outcome)
+}
+
+/// Fields both sides changed from `base` to different values. The later change is kept.
+fn changed_on_both_sides(base: &[Task], ours: &[Task], theirs: &[Task]) -> Vec<String> {
+    let base: HashMap<Uuid, &Task> = base.iter().map(|task| (task.id, task)).collect();
+    let theirs: HashMap<Uuid, &Task> = theirs.iter().map(|task| (task.id, task)).collect();
+    let mut conflicts = Vec::new();
+
+    for task in ours {
+        let (Some(base), Some(other)) = (base.get(&task.id), theirs.get(&task.id)) else {
+            continue;
+        };
+        let (base_fields, their_fields) = (fields(base), fields(other));
+        for (field, value) in &fields(task) {
+            let their_value = their_fields.get(field);
+            let base_value = base_fields.get(field);
+            // The merge reports changes made at the same time itself
+            if their_value != Some(value)
+                && base_value != Some(value)
+                && their_value != base_value
+                && changed_at(task, field) != changed_at(other, field)
+            {
+                conflicts.push(format!(
+                    "{}: {} changed on both sides, kept the later change",
+                    task.title, field
+                ));
+            }
+        }
+    }
+
+    conflicts
+}
+
+/// Reads a version of the task file. Git passes an empty file for a side that
+/// did not have it.
+fn parse_tasks(contents: &str) -> AppResult<Vec<Task>> {
+    if contents.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(contents).map_err(|err| failed(&format!("invalid task file: {}", err)))
+}
+
+fn failed(reason: &str) -> AppError {
+    AppError::SyncFailed {
+        reason: reason.to_string(),
+    }
+}
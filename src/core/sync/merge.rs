@@ -0,0 +1,435 @@
+//! Merging copies of the task list edited on different devices.
+//!
+//! Every save records when each field of a task changed ([`record_changes`]),
+//! and a deleted task leaves a tombstone behind instead of disappearing
+//! ([`tombstone`]). Two copies then merge field by field, the later change
+//! winning ([`merge`]).
+//! Changes made at the same moment are settled by comparing the values, so
+//! merging in either direction, or in any order, gives the same tasks.
+//!
+//! A tombstone wins over changes made before the deletion. A change made at the
+//! same time or after it, on a device that had not seen the deletion yet, brings
+//! the task back, as does restoring it with the same id, e.g. by importing it.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde_json::{Map, Value};
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::core::task::{Task, task::FieldChange};
+
+/// Fields that describe the task's history rather than the task itself.
+const METADATA_FIELDS: [&str; 3] = ["id", "updatedAt", "fieldChanges"];
+
+/// The field a tombstone sets. It stays set after a merge only if no other
+/// field changed at or after the deletion.
+const DELETED_FIELD: &str = "deletedAt";
+
+/// The tasks two copies merge into.
+#[derive(Debug, Clone, Default)]
+pub struct MergeOutcome {
+    /// Tasks and tombstones, in the local order with remote-only ones last.
+    pub tasks: Vec<Task>,
+    /// Changes that could not both be kept, one line each.
+    pub conflicts: Vec<String>,
+}
+
+/// Stamps the changes from `previous` to `current` with `now`. Both may hold
+/// tombstones; a task missing from `current` is left out, not deleted.
+///
+/// A field that changed keeps the time already on it when that is newer than
+/// the previous one, as after a merge; otherwise it changed at `now`.
+pub fn record_changes(previous: &[Task], current: &[Task], now: OffsetDateTime) -> Vec<Task> {
+    let old: HashMap<Uuid, &Task> = previous.iter().map(|task| (task.id, task)).collect();
+    current
+        .iter()
+        .map(|task| match old.get(&task.id) {
+            Some(before) => stamp(before, task, now),
+            None => {
+                let mut task = task.clone();
+                task.updated_at.get_or_insert(now);
+                task
+            }
+        })
+        .collect()
+}
+
+/// Adds tombstones for the tasks of `previous` that are missing from `records`,
+/// for copies that dropped deleted tasks instead of keeping their tombstones.
+pub fn record_deletions(previous: &[Task], records: &mut Vec<Task>, now: OffsetDateTime) {
+    let remaining: HashSet<Uuid> = records.iter().map(|task| task.id).collect();
+    for task in previous.iter().filter(|task| !remaining.contains(&task.id)) {
+        records.push(tombstone(task, now));
+    }
+}
+
+/// The tombstone `task` leaves when deleted at `now`. A tombstone stays one.
+pub fn tombstone(task: &Task, now: OffsetDateTime) -> Task {
+    let mut task = task.clone();
+    if task.deleted_at.is_none() {
+        let mut times = change_times(&task);
+        let deleted_at = after(times.values().max().copied(), now);
+        task.deleted_at = Some(deleted_at);
+        times.insert(DELETED_FIELD.to_string(), deleted_at);
+        set_change_times(&mut task, times);
+    }
+    task
+}
+
+/// Merges two copies of the task list. Tasks are matched by id, fields take
+/// the later change and tombstones follow the rules in the module docs.
+pub fn merge(local: &[Task], remote: &[Task]) -> MergeOutcome {
+    let theirs: HashMap<Uuid, &Task> = remote.iter().map(|task| (task.id, task)).collect();
+    let mut outcome = MergeOutcome::default();
+
+    for task in local {
+        let merged = match theirs.get(&task.id) {
+            Some(other) => merge_task(task, other, &mut outcome.conflicts),
+            None => task.clone(),
+        };
+        outcome.tasks.push(merged);
+    }
+
+    let ours: HashSet<Uuid> = local.iter().map(|task| task.id).collect();
+    outcome.tasks.extend(
+        remote
+            .iter()
+            .filter(|task| !ours.contains(&task.id))
+            .cloned(),
+    );
+
+    outcome
+}
+
+/// When `field` of `task` last changed. Fields without a recorded change have
+/// not changed since the task was created, before any recorded change.
+pub fn changed_at(task: &Task, field: &str) -> OffsetDateTime {
+    task.field_changes
+        .iter()
+        .find(|change| change.field == field)
+        .map_or(OffsetDateTime::UNIX_EPOCH, |change| change.at)
+}
+
+/// The task's fields by their names in the task file, without its history.
+pub fn fields(task: &Task) -> Map<String, Value> {
+    let mut fields = match serde_json::to_value(task) {
+        Ok(Value::Object(fields)) => fields,
+        _ => Map::new(),
+    };
+    for field in METADATA_FIELDS {
+        fields.remove(field);
+    }
+    fields
+}
+
+fn stamp(before: &Task, task: &Task, now: OffsetDateTime) -> Task {
+    let (old_fields, new_fields) = (fields(before), fields(task));
+    let mut times = BTreeMap::new();
+
+    for field in field_names(before, &old_fields, task, &new_fields) {
+        let (old_time, new_time) = (changed_at(before, &field), changed_at(task, &field));
+        let time = if old_fields.get(&field) == new_fields.get(&field) || new_time > old_time {
+            old_time.max(new_time)
+        } else {
+            after(Some(old_time), now)
+        };
+        if time != OffsetDateTime::UNIX_EPOCH {
+            times.insert(field, time);
+        }
+    }
+
+    let mut task = task.clone();
+    task.updated_at = task.updated_at.max(before.updated_at);
+    set_change_times(&mut task, times);
+    task
+}
+
+fn merge_task(local: &Task, remote: &Task, conflicts: &mut Vec<String>) -> Task {
+    let (ours, theirs) = (fields(local), fields(remote));
+    let mut merged = ours.clone();
+    let mut times = BTreeMap::new();
+    let mut tied = Vec::new();
+
+    for field in field_names(local, &ours, remote, &theirs) {
+        let (our_time, their_time) = (changed_at(local, &field), changed_at(remote, &field));
+        let (our_value, their_value) = (ours.get(&field), theirs.get(&field));
+
+        let take_theirs = if our_value == their_value {
+            false
+        } else if our_time != their_time {
+            their_time > our_time
+        } else {
+            // Either copy merging the other must settle on the same value. A
+            // tombstone meeting a copy it was restored in is not a conflict.
+            if field != DELETED_FIELD {
+                tied.push(field.clone());
+            }
+            sort_key(their_value) > sort_key(our_value)
+        };
+        if take_theirs {
+            match their_value {
+                Some(value) => merged.insert(field.clone(), value.clone()),
+                None => merged.remove(&field),
+            };
+        }
+
+        let time = our_time.max(their_time);
+        if time != OffsetDateTime::UNIX_EPOCH {
+            times.insert(field, time);
+        }
+    }
+
+    // A change the deleting side never saw brings the task back. This follows
+    // from the merged times alone, so the order of merges does not matter.
+    let mut restored = false;
+    if merged.contains_key(DELETED_FIELD) {
+        let deleted_at = times.get(DELETED_FIELD);
+        let edited_at = times
+            .iter()
+            .filter(|(field, _)| *field != DELETED_FIELD)
+            .map(|(_, at)| at)
+            .max();
+        if edited_at >= deleted_at {
+            merged.remove(DELETED_FIELD);
+            restored = local.deleted_at.is_some() || remote.deleted_at.is_some();
+        }
+    }
+
+    merged.insert("id".to_string(), Value::String(local.id.to_string()));
+    // Fields are read independently, so any mix of two valid tasks is one too
+    let mut task: Task =
+        serde_json::from_value(Value::Object(merged)).unwrap_or_else(|_| local.clone());
+    task.updated_at = local.updated_at.max(remote.updated_at);
+    set_change_times(&mut task, times);
+
+    for field in tied {
+        conflicts.push(format!(
+            "{}: {} changed on both sides at the same time",
+            task.title, field
+        ));
+    }
+    if restored {
+        conflicts.push(format!(
+            "{}: deleted on one side and edited on the other, kept",
+            task.title
+        ));
+    }
+    task
+}
+
+/// Every field either copy has or recorded a change to, in a fixed order.
+fn field_names(
+    ours: &Task,
+    our_fields: &Map<String, Value>,
+    theirs: &Task,
+    their_fields: &Map<String, Value>,
+) -> Vec<String> {
+    let mut names: Vec<String> = our_fields
+        .keys()
+        .chain(their_fields.keys())
+        .cloned()
+        .chain(
+            ours.field_changes
+                .iter()
+                .chain(&theirs.field_changes)
+                .map(|change| change.field.clone()),
+        )
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// `now`, or just after `time` if the clock is behind it, so a change always
+/// comes after the one it replaces.
+fn after(time: Option<OffsetDateTime>, now: OffsetDateTime) -> OffsetDateTime {
+    match time {
+        Some(time) if time >= now => time + Duration::nanoseconds(1),
+        _ => now,
+    }
+}
+
+/// Orders values for settling ties; a missing value sorts first.
+fn sort_key(value: Option<&Value>) -> String {
+    value.map(Value::to_string).unwrap_or_default()
+}
+
+fn change_times(task: &Task) -> BTreeMap<String, OffsetDateTime> {
+    task.field_changes
+        .iter()
+        .map(|change| (change.field.clone(), change.at))
+        .collect()
+}
+
+fn set_change_times(task: &mut Task, times: BTreeMap<String, OffsetDateTime>) {
+    if let Some(latest) = times.values().max() {
+        task.updated_at = task.updated_at.max(Some(*latest));
+    }
+    task.field_changes = times
+        .into_iter()
+        .map(|(field, at)| FieldChange { field, at })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use time::macros::date;
+
+    use super::*;
+
+    /// Ids shared by every copy, so copies overlap.
+    const TASK_COUNT: u128 = 3;
+
+    fn at(seconds: i64) -> OffsetDateTime {
+        OffsetDateTime::UNIX_EPOCH + Duration::seconds(seconds)
+    }
+
+    /// How one copy holds a task: its title and due date with the times they
+    /// changed, and whether it was deleted, or deleted and then restored.
+    #[derive(Debug, Clone)]
+    struct CopyOfTask {
+        title: (u8, i64),
+        due: (u8, i64),
+        deletion: Deletion,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum Deletion {
+        None,
+        Deleted(i64),
+        Restored(i64),
+    }
+
+    fn copy_of_task() -> impl Strategy<Value = CopyOfTask> {
+        let deletion = prop_oneof![
+            Just(Deletion::None),
+            (1..3i64).prop_map(Deletion::Deleted),
+            (0..3i64).prop_map(Deletion::Restored),
+        ];
+        ((0..3u8, 1..5i64), (0..3u8, 1..5i64), deletion).prop_map(|(title, due, deletion)| {
+            CopyOfTask {
+                title,
+                due,
+                deletion,
+            }
+        })
+    }
+
+    fn copy_of_list() -> impl Strategy<Value = Vec<Task>> {
+        prop::collection::vec(prop::option::of(copy_of_task()), TASK_COUNT as usize).prop_map(
+            |copies| {
+                copies
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(index, copy)| Some(build(index as u128, copy?)))
+                    .collect()
+            },
+        )
+    }
+
+    /// A task as saves leave it: a tombstone's deletion comes after its other
+    /// changes, and a restore comes at or after the deletion it undoes.
+    fn build(id: u128, copy: CopyOfTask) -> Task {
+        let mut task = Task::new("base".to_string(), Vec::new(), None);
+        task.id = Uuid::from_u128(id);
+        task.created_at = date!(2026 - 01 - 01);
+        task.title = format!("title {}", copy.title.0);
+        task.due_date = Some(date!(2026 - 10 - 01) + Duration::days(copy.due.0.into()));
+
+        let mut times = BTreeMap::new();
+        times.insert("title".to_string(), at(copy.title.1));
+        times.insert("dueDate".to_string(), at(copy.due.1));
+        let latest = copy.title.1.max(copy.due.1);
+        match copy.deletion {
+            Deletion::None => {}
+            Deletion::Deleted(after) => {
+                task.deleted_at = Some(at(latest + after));
+                times.insert(DELETED_FIELD.to_string(), at(latest + after));
+            }
+            Deletion::Restored(before) => {
+                times.insert(DELETED_FIELD.to_string(), at((latest - before).max(1)));
+            }
+        }
+        set_change_times(&mut task, times);
+        task
+    }
+
+    /// The tasks as JSON, in id order, so copies compare regardless of order.
+    fn by_id(tasks: &[Task]) -> Vec<Value> {
+        let mut tasks = tasks.to_vec();
+        tasks.sort_by_key(|task| task.id);
+        tasks
+            .iter()
+            .map(|task| serde_json::to_value(task).unwrap())
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn merge_is_commutative(a in copy_of_list(), b in copy_of_list()) {
+            prop_assert_eq!(by_id(&merge(&a, &b).tasks), by_id(&merge(&b, &a).tasks));
+        }
+
+        #[test]
+        fn merge_is_idempotent(a in copy_of_list()) {
+            prop_assert_eq!(by_id(&merge(&a, &a).tasks), by_id(&a));
+        }
+
+        #[test]
+        fn merge_is_associative(a in copy_of_list(), b in copy_of_list(), c in copy_of_list()) {
+            let left = merge(&merge(&a, &b).tasks, &c).tasks;
+            let right = merge(&a, &merge(&b, &c).tasks).tasks;
+            prop_assert_eq!(by_id(&left), by_id(&right));
+        }
+
+        #[test]
+        fn tombstone_loses_only_to_later_changes(
+            edited in copy_of_task(),
+            deleted_at in 1..8i64,
+        ) {
+            let edited = build(0, CopyOfTask { deletion: Deletion::None, ..edited });
+            let mut deleted = build(0, CopyOfTask {
+                title: (0, 1),
+                due: (0, 1),
+                deletion: Deletion::None,
+            });
+            deleted = tombstone(&deleted, at(deleted_at));
+            // Deleting at the time of the last change still comes after it
+            let deleted_at = changed_at(&deleted, DELETED_FIELD);
+
+            let edited_at = changed_at(&edited, "title").max(changed_at(&edited, "dueDate"));
+            let merged = merge(&[edited], &[deleted]).tasks;
+            prop_assert_eq!(merged[0].deleted_at.is_some(), edited_at < deleted_at);
+        }
+    }
+
+    #[test]
+    fn record_changes_stamps_only_changed_fields() {
+        let before = build(
+            0,
+            CopyOfTask {
+                title: (0, 1),
+                due: (0, 1),
+                deletion: Deletion::None,
+            },
+        );
+        let mut after = before.clone();
+        after.title = "renamed".to_string();
+
+        let recorded = record_changes(&[before], &[after], at(10));
+        assert_eq!(changed_at(&recorded[0], "title"), at(10));
+        assert_eq!(changed_at(&recorded[0], "dueDate"), at(1));
+    }
+
+    #[test]
+    fn record_deletions_leaves_tombstones_for_missing_tasks() {
+        let task = Task::new("gone".to_string(), Vec::new(), None);
+        let mut records = Vec::new();
+
+        record_deletions(&[task], &mut records, at(10));
+        assert_eq!(records[0].deleted_at, Some(at(10)));
+        assert_eq!(changed_at(&records[0], DELETED_FIELD), at(10));
+    }
+}
@@ -1,5 +1,13 @@
+pub mod agenda;
+pub mod board;
+pub mod dependencies;
+pub mod effort;
+pub mod smart_view;
+pub mod stats;
+#[allow(clippy::module_inception)]
 pub mod task;
 pub mod task_list;
 
+pub use smart_view::SmartView;
 pub use task::Task;
-pub use task_list::TaskList;
+pub use task_list::{TaskLayout, TaskList};
@@ -0,0 +1,82 @@
+use std::cmp::Ordering;
+
+use time::{Date, Duration, macros::format_description};
+
+use super::task::Task;
+
+/// A heading in the agenda: open overdue tasks first, then one per day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgendaHeading {
+    Overdue,
+    Day(Date),
+}
+
+impl AgendaHeading {
+    pub fn label(&self, today: Date) -> String {
+        match self {
+            AgendaHeading::Overdue => "Overdue".to_string(),
+            AgendaHeading::Day(date) if *date == today => "Today".to_string(),
+            AgendaHeading::Day(date) if today.next_day() == Some(*date) => "Tomorrow".to_string(),
+            AgendaHeading::Day(date) => date
+                .format(format_description!(
+                    "[weekday repr:short] [day padding:none] [month repr:short]"
+                ))
+                .unwrap_or_else(|_| date.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AgendaSection<'a> {
+    pub heading: AgendaHeading,
+    pub tasks: Vec<&'a Task>,
+}
+
+/// Groups tasks due within `days` days from `today` under a heading per day.
+///
+/// Every day in the range gets a section, even when nothing is due. The overdue
+/// section is only present when there are open overdue tasks.
+pub fn build_agenda(tasks: &[Task], today: Date, days: u32) -> Vec<AgendaSection<'_>> {
+    let mut sections = Vec::new();
+
+    let mut overdue: Vec<&Task> = tasks.iter().filter(|task| task.is_overdue(today)).collect();
+    overdue.sort_by(|a, b| a.due_date.cmp(&b.due_date).then(compare_for_display(a, b)));
+
+    if !overdue.is_empty() {
+        sections.push(AgendaSection {
+            heading: AgendaHeading::Overdue,
+            tasks: overdue,
+        });
+    }
+
+    for offset in 0..i64::from(days) {
+        let Some(date) = today.checked_add(Duration::days(offset)) else {
+            break;
+        };
+
+        let mut due: Vec<&Task> = tasks
+            .iter()
+            .filter(|task| task.due_date == Some(date))
+            .collect();
+        due.sort_by(|a, b| compare_for_display(a, b));
+
+        sections.push(AgendaSection {
+            heading: AgendaHeading::Day(date),
+            tasks: due,
+        });
+    }
+
+    sections
+}
+
+/// Open tasks before completed or cancelled ones, favorites first.
+pub fn compare_for_display(a: &Task, b: &Task) -> Ordering {
+    let a_closed = a.status.is_closed();
+    let b_closed = b.status.is_closed();
+
+    if a_closed != b_closed {
+        return a_closed.cmp(&b_closed);
+    }
+
+    a.is_favorite.cmp(&b.is_favorite).reverse()
+}
@@ -0,0 +1,35 @@
+use super::{
+    agenda::compare_for_display,
+    task::{Status, Task},
+};
+
+#[derive(Debug, Clone)]
+pub struct BoardColumn<'a> {
+    pub status: Status,
+    pub tasks: Vec<&'a Task>,
+}
+
+/// The board's columns, in lifecycle order. Cancelled tasks are left off the board.
+pub const BOARD_STATUSES: [Status; 4] = [
+    Status::Todo,
+    Status::InProgress,
+    Status::Blocked,
+    Status::Completed,
+];
+
+/// Splits tasks into one column per status in [`BOARD_STATUSES`].
+pub fn build_board(tasks: &[Task]) -> Vec<BoardColumn<'_>> {
+    BOARD_STATUSES
+        .into_iter()
+        .map(|status| {
+            let mut column: Vec<&Task> =
+                tasks.iter().filter(|task| task.status == status).collect();
+            column.sort_by(|a, b| compare_for_display(a, b));
+
+            BoardColumn {
+                status,
+                tasks: column,
+            }
+        })
+        .collect()
+}
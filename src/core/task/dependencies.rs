@@ -0,0 +1,63 @@
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use super::task::Task;
+
+/// Prerequisites of `task` that are still open.
+pub fn open_prerequisites<'a>(task: &Task, tasks: &'a [Task]) -> Vec<&'a Task> {
+    tasks
+        .iter()
+        .filter(|other| task.depends_on.contains(&other.id) && other.is_open())
+        .collect()
+}
+
+/// Tasks that list `id` among their prerequisites.
+pub fn dependents_of(id: Uuid, tasks: &[Task]) -> Vec<&Task> {
+    tasks
+        .iter()
+        .filter(|task| task.depends_on.contains(&id))
+        .collect()
+}
+
+/// Looks for a chain of prerequisites leading from `depends_on` back to `task_id`.
+///
+/// Returns the ids along the cycle, starting and ending with `task_id`.
+pub fn find_cycle(task_id: Uuid, depends_on: &[Uuid], tasks: &[Task]) -> Option<Vec<Uuid>> {
+    let mut visited = HashSet::new();
+    let mut path = vec![task_id];
+
+    for id in depends_on {
+        if let Some(cycle) = visit(*id, task_id, tasks, &mut visited, &mut path) {
+            return Some(cycle);
+        }
+    }
+    None
+}
+
+fn visit(
+    id: Uuid,
+    target: Uuid,
+    tasks: &[Task],
+    visited: &mut HashSet<Uuid>,
+    path: &mut Vec<Uuid>,
+) -> Option<Vec<Uuid>> {
+    path.push(id);
+
+    if id == target {
+        return Some(path.clone());
+    }
+
+    if visited.insert(id)
+        && let Some(task) = tasks.iter().find(|task| task.id == id)
+    {
+        for next in &task.depends_on {
+            if let Some(cycle) = visit(*next, target, tasks, visited, path) {
+                return Some(cycle);
+            }
+        }
+    }
+
+    path.pop();
+    None
+}
@@ -0,0 +1,63 @@
+use std::fmt;
+
+use super::task::{Estimate, Task};
+use crate::utils::duration::format_duration;
+
+/// Estimated work left on a set of tasks. Minutes and story points are kept apart
+/// since they cannot be added up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EffortSummary {
+    pub minutes: u32,
+    pub points: u32,
+    /// Open tasks without an estimate.
+    pub unestimated: usize,
+}
+
+impl EffortSummary {
+    /// Adds up the estimates of the open tasks among `tasks`.
+    pub fn of<'a>(tasks: impl IntoIterator<Item = &'a Task>) -> Self {
+        let mut summary = Self::default();
+
+        for task in tasks.into_iter().filter(|task| task.is_open()) {
+            match task.estimate {
+                Some(Estimate::Minutes(minutes)) => {
+                    summary.minutes = summary.minutes.saturating_add(minutes)
+                }
+                Some(Estimate::Points(points)) => {
+                    summary.points = summary.points.saturating_add(points)
+                }
+                None => summary.unestimated += 1,
+            }
+        }
+        summary
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.minutes == 0 && self.points == 0
+    }
+
+    /// Whether the estimated minutes exceed `capacity_minutes`.
+    pub fn exceeds(&self, capacity_minutes: Option<u32>) -> bool {
+        capacity_minutes.is_some_and(|capacity| self.minutes > capacity)
+    }
+}
+
+impl fmt::Display for EffortSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if self.minutes > 0 {
+            parts.push(format_duration(time::Duration::minutes(
+                self.minutes.into(),
+            )));
+        }
+        if self.points > 0 {
+            parts.push(Estimate::Points(self.points).to_string());
+        }
+
+        write!(f, "{} left", parts.join(" + "))?;
+        if self.unestimated > 0 {
+            write!(f, " ({} unestimated)", self.unestimated)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use strum::{Display, EnumIter, IntoEnumIterator};
+use time::{Date, Duration};
+
+use super::task::{Status, Task};
+
+/// Built-in filters over the task list, switched with the number keys.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter, Display)]
+pub enum SmartView {
+    #[default]
+    All,
+    Overdue,
+    Today,
+    #[strum(to_string = "Next 7 days")]
+    Upcoming,
+    #[strum(to_string = "No date")]
+    NoDate,
+    Completed,
+}
+
+impl SmartView {
+    /// The number key that selects this view, starting at `1`.
+    pub fn key(&self) -> char {
+        let position = SmartView::iter()
+            .position(|view| view == *self)
+            .unwrap_or(0);
+        char::from_digit(position as u32 + 1, 10).unwrap_or('?')
+    }
+
+    pub fn from_key(key: char) -> Option<Self> {
+        SmartView::iter().find(|view| view.key() == key)
+    }
+
+    /// Matches a view by its name, ignoring case, spaces and dashes, so both
+    /// `next-7-days` and `Next 7 days` work. `upcoming` is accepted too.
+    pub fn parse(name: &str) -> Option<Self> {
+        let key: String = name
+            .chars()
+            .filter(|ch| !matches!(ch, ' ' | '-' | '_'))
+            .collect::<String>()
+            .to_lowercase();
+
+        if key == "upcoming" {
+            return Some(SmartView::Upcoming);
+        }
+        SmartView::iter().find(|view| view.to_string().replace(' ', "").to_lowercase() == key)
+    }
+
+    pub fn matches(&self, task: &Task, today: Date) -> bool {
+        let is_open = task.is_open();
+
+        match self {
+            SmartView::All => true,
+            SmartView::Overdue => task.is_overdue(today),
+            SmartView::Today => is_open && task.due_date == Some(today),
+            SmartView::Upcoming => {
+                let end = today.saturating_add(Duration::days(6));
+                is_open && task.due_date.is_some_and(|due| due >= today && due <= end)
+            }
+            SmartView::NoDate => is_open && task.due_date.is_none(),
+            SmartView::Completed => task.status == Status::Completed,
+        }
+    }
+}
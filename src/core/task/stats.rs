@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+
+use time::{Date, Duration};
+
+use super::task::{Status, Task};
+
+/// Days of completions shown in the daily chart.
+pub const DAYS_SHOWN: usize = 30;
+/// Weeks of completions shown in the weekly chart.
+pub const WEEKS_SHOWN: usize = 8;
+/// Days the overdue trend reaches back.
+pub const TREND_DAYS: usize = 14;
+
+/// Completed and open tasks in one group or under one tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakdown {
+    pub name: String,
+    pub completed: usize,
+    pub open: usize,
+}
+
+/// Figures for the statistics screen, worked out from the tasks' dates and history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    /// Completions on each of the last [`DAYS_SHOWN`] days, oldest first.
+    pub completed_per_day: Vec<(Date, u64)>,
+    /// Completions in each of the last [`WEEKS_SHOWN`] weeks, keyed by their Monday.
+    pub completed_per_week: Vec<(Date, u64)>,
+    /// Open overdue tasks at the end of each of the last [`TREND_DAYS`] days.
+    pub overdue_trend: Vec<(Date, u64)>,
+    /// Mean days from creation to completion.
+    pub average_lead_time: Option<f64>,
+    /// Consecutive days with at least one completion, ending today, or yesterday
+    /// if nothing has been completed yet today.
+    pub streak: u32,
+    pub total_completed: usize,
+    pub total_open: usize,
+    pub by_group: Vec<Breakdown>,
+    pub by_tag: Vec<Breakdown>,
+}
+
+impl Statistics {
+    pub fn compute(tasks: &[Task], today: Date) -> Self {
+        let completions: Vec<Date> = tasks.iter().filter_map(|task| task.completed_at).collect();
+        let count_on = |day: Date| completions.iter().filter(|&&date| date == day).count() as u64;
+
+        let completed_per_day = days_before(today, DAYS_SHOWN)
+            .map(|day| (day, count_on(day)))
+            .collect();
+
+        let this_week = today - Duration::days(today.weekday().number_days_from_monday().into());
+        let completed_per_week = (0..WEEKS_SHOWN)
+            .rev()
+            .map(|weeks_ago| {
+                let monday = this_week - Duration::weeks(weeks_ago as i64);
+                let count = completions
+                    .iter()
+                    .filter(|&&date| date >= monday && date < monday + Duration::weeks(1))
+                    .count() as u64;
+                (monday, count)
+            })
+            .collect();
+
+        let overdue_trend = days_before(today, TREND_DAYS)
+            .map(|day| {
+                let count = tasks
+                    .iter()
+                    .filter(|task| was_overdue_on(task, day))
+                    .count() as u64;
+                (day, count)
+            })
+            .collect();
+
+        let lead_times: Vec<i64> = tasks
+            .iter()
+            .filter_map(|task| {
+                task.completed_at
+                    .map(|completed| (completed - task.created_at).whole_days().max(0))
+            })
+            .collect();
+        let average_lead_time = (!lead_times.is_empty())
+            .then(|| lead_times.iter().sum::<i64>() as f64 / lead_times.len() as f64);
+
+        let mut streak = 0;
+        let mut day = if count_on(today) > 0 {
+            Some(today)
+        } else {
+            today.previous_day()
+        };
+        while let Some(current) = day
+            && count_on(current) > 0
+        {
+            streak += 1;
+            day = current.previous_day();
+        }
+
+        Self {
+            completed_per_day,
+            completed_per_week,
+            overdue_trend,
+            average_lead_time,
+            streak,
+            total_completed: tasks
+                .iter()
+                .filter(|task| task.status == Status::Completed)
+                .count(),
+            total_open: tasks.iter().filter(|task| task.is_open()).count(),
+            by_group: breakdown(tasks, |task| task.group.iter().cloned().collect()),
+            by_tag: breakdown(tasks, |task| task.tags.clone()),
+        }
+    }
+}
+
+/// The `count` days up to and including `today`, oldest first.
+fn days_before(today: Date, count: usize) -> impl Iterator<Item = Date> {
+    (0..count)
+        .rev()
+        .map(move |days_ago| today - Duration::days(days_ago as i64))
+}
+
+/// Whether the task was open and past its due date at the end of `day`.
+fn was_overdue_on(task: &Task, day: Date) -> bool {
+    let Some(due) = task.due_date else {
+        return false;
+    };
+    if due >= day || task.created_at > day {
+        return false;
+    }
+
+    match task.closed_on() {
+        Some(closed) => closed > day,
+        None => task.is_open(),
+    }
+}
+
+/// Completed and open counts per name, sorted by name. Cancelled tasks are left out.
+fn breakdown(tasks: &[Task], names: impl Fn(&Task) -> Vec<String>) -> Vec<Breakdown> {
+    let mut counts: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+
+    for task in tasks {
+        for name in names(task) {
+            let (completed, open) = counts.entry(name).or_default();
+            match task.status {
+                Status::Completed => *completed += 1,
+                Status::Cancelled => {}
+                _ => *open += 1,
+            }
+        }
+    }
+
+    counts
+        .into_iter()
+        .map(|(name, (completed, open))| Breakdown {
+            name,
+            completed,
+            open,
+        })
+        .collect()
+}
@@ -3,28 +3,252 @@ use ratatui::{
         Color, Modifier, Style,
         palette::{
             material::GRAY,
-            tailwind::{SLATE, YELLOW},
+            tailwind::{ORANGE, RED, SKY, SLATE, YELLOW},
         },
     },
-    text::Line,
+    text::{Line, Span},
     widgets::ListItem,
 };
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
-use time::Date;
+use strum::{Display, EnumIter};
+use time::{Date, Duration, OffsetDateTime, Time, macros::format_description};
 use uuid::Uuid;
 
-use crate::utils::date::get_today_with_fallbacks;
+use crate::utils::{
+    date::{get_today_with_fallbacks, local_offset, now_timestamp},
+    duration::{format_duration, parse_duration},
+};
 
 const TEXT_FG_COLOR: Color = SLATE.c200;
 const COMPLETED_TEXT_FG_COLOR: Style = Style::new()
     .fg(GRAY.c500)
     .add_modifier(Modifier::CROSSED_OUT);
+const CANCELLED_TEXT_FG_COLOR: Style = Style::new()
+    .fg(GRAY.c700)
+    .add_modifier(Modifier::CROSSED_OUT.union(Modifier::ITALIC));
+const DEFERRED_TEXT_FG_COLOR: Color = GRAY.c600;
+const DEFERRED_LABEL_STYLE: Style = Style::new().fg(GRAY.c600).add_modifier(Modifier::ITALIC);
 const STAR_TEXT_FG_COLOR: Color = YELLOW.c200;
+const IN_PROGRESS_TEXT_FG_COLOR: Color = SKY.c300;
+const BLOCKED_TEXT_FG_COLOR: Color = ORANGE.c300;
+const OVERDUE_TEXT_FG_COLOR: Color = RED.c300;
+const OVERDUE_LABEL_STYLE: Style = Style::new().fg(RED.c400).add_modifier(Modifier::ITALIC);
+const WAITING_LABEL_STYLE: Style = Style::new().fg(ORANGE.c400).add_modifier(Modifier::ITALIC);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, EnumIter,
+)]
 pub enum Status {
     Todo,
+    InProgress,
+    Blocked,
     Completed,
+    Cancelled,
+}
+
+impl Status {
+    /// Accepts the labels shown in the app as well as common spellings such as
+    /// `inprogress`, `doing` or `completed`, ignoring case.
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "todo" | "to do" | "open" => Some(Status::Todo),
+            "in progress" | "inprogress" | "in-progress" | "doing" => Some(Status::InProgress),
+            "blocked" => Some(Status::Blocked),
+            "done" | "completed" => Some(Status::Completed),
+            "cancelled" | "canceled" => Some(Status::Cancelled),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Todo => "Todo",
+            Status::InProgress => "In progress",
+            Status::Blocked => "Blocked",
+            Status::Completed => "Done",
+            Status::Cancelled => "Cancelled",
+        }
+    }
+
+    /// Whether work on a task with this status is finished, one way or another.
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Status::Completed | Status::Cancelled)
+    }
+
+    /// Statuses a task may move to from this one.
+    ///
+    /// Finished tasks have to be reopened before they can be cancelled, and
+    /// cancelled ones before they can be worked on or completed.
+    pub fn can_transition_to(&self, next: Status) -> bool {
+        match (self, next) {
+            // Re-blocking updates the reason
+            (Status::Blocked, Status::Blocked) => true,
+            (current, next) if *current == next => false,
+            (Status::Completed, Status::Cancelled) => false,
+            (Status::Cancelled, next) => next == Status::Todo,
+            _ => true,
+        }
+    }
+}
+
+/// A status a task entered, and when.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub status: Status,
+    #[serde(with = "time::serde::rfc3339")]
+    pub at: OffsetDateTime,
+}
+
+/// When a field of a task last changed, so copies edited on different devices
+/// can be merged field by field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// The field's name in the task file, such as `dueDate`.
+    pub field: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub at: OffsetDateTime,
+}
+
+/// A stretch of time spent on a task. `ended_at` stays empty while the timer runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntry {
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub ended_at: Option<OffsetDateTime>,
+}
+
+impl TimeEntry {
+    pub fn is_running(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    /// Time covered by the entry; a running entry counts up to `now`.
+    pub fn duration(&self, now: OffsetDateTime) -> Duration {
+        self.ended_at.unwrap_or(now) - self.started_at
+    }
+}
+
+/// When a reminder goes off: at a fixed moment, or some time before the task is due.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReminderTime {
+    At {
+        #[serde(with = "time::serde::rfc3339")]
+        at: OffsetDateTime,
+    },
+    BeforeDue {
+        minutes: u32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub time: ReminderTime,
+    #[serde(default)]
+    pub fired: bool,
+}
+
+impl fmt::Display for ReminderTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReminderTime::At { at } => write!(
+                f,
+                "{}",
+                at.format(format_description!(
+                    "[weekday repr:short] [day padding:none] [month repr:short] [hour]:[minute]"
+                ))
+                .unwrap_or_default()
+            ),
+            ReminderTime::BeforeDue { minutes: 0 } => write!(f, "when due"),
+            ReminderTime::BeforeDue { minutes } => write!(
+                f,
+                "{} before due",
+                format_duration(Duration::minutes((*minutes).into()))
+            ),
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Display,
+)]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+}
+
+impl Priority {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "high" | "h" | "1" => Some(Priority::High),
+            "medium" | "med" | "m" | "2" => Some(Priority::Medium),
+            "low" | "l" | "3" => Some(Priority::Low),
+            _ => None,
+        }
+    }
+}
+
+/// How much work a task is expected to take, in time or in story points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Estimate {
+    Minutes(u32),
+    Points(u32),
+}
+
+impl Estimate {
+    /// Parses `3pt`, `3pts` or `3sp` as story points and anything else as a
+    /// duration such as `45m`, `1h30m` or a plain number of minutes.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim().to_lowercase();
+
+        let points = ["pts", "pt", "sp"]
+            .iter()
+            .find_map(|suffix| input.strip_suffix(suffix));
+        if let Some(points) = points {
+            return points
+                .trim()
+                .parse()
+                .ok()
+                .filter(|points| *points > 0)
+                .map(Estimate::Points);
+        }
+
+        parse_duration(&input)
+            .map(|duration| duration.whole_minutes())
+            .filter(|minutes| *minutes > 0)
+            .and_then(|minutes| u32::try_from(minutes).ok())
+            .map(Estimate::Minutes)
+    }
+
+    /// The estimate without spaces, such as `90m` or `3pt`, as [`Estimate::parse`] reads it.
+    pub fn compact(&self) -> String {
+        match self {
+            Estimate::Minutes(minutes) => format!("{}m", minutes),
+            Estimate::Points(points) => format!("{}pt", points),
+        }
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Estimate::Minutes(minutes) => {
+                write!(
+                    f,
+                    "{}",
+                    format_duration(Duration::minutes((*minutes).into()))
+                )
+            }
+            Estimate::Points(1) => write!(f, "1 pt"),
+            Estimate::Points(points) => write!(f, "{} pts", points),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,42 +258,252 @@ pub struct Task {
     pub title: String,
     pub description: Vec<String>,
     pub status: Status,
-    // pub group: Option<String>,
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub priority: Option<Priority>,
     pub is_favorite: bool,
     pub due_date: Option<Date>,
     pub created_at: Date,
     pub completed_at: Option<Date>,
+    /// Why the task is blocked; only kept while the status is `Blocked`.
+    #[serde(default)]
+    pub blocked_reason: Option<String>,
+    /// Every status change, oldest first. Tasks saved before this existed have none.
+    #[serde(default)]
+    pub status_changes: Vec<StatusChange>,
+    /// Tasks that have to be finished before this one can start.
+    #[serde(default)]
+    pub depends_on: Vec<Uuid>,
+    /// Time spent on the task, in the order it was recorded. At most one entry is running.
+    #[serde(default)]
+    pub time_entries: Vec<TimeEntry>,
+    /// Pomodoro work phases finished on the task.
+    #[serde(default)]
+    pub pomodoros: u32,
+    #[serde(default)]
+    pub estimate: Option<Estimate>,
+    /// The task stays out of the list until this date.
+    #[serde(default)]
+    pub defer_until: Option<Date>,
+    #[serde(default)]
+    pub reminders: Vec<Reminder>,
+    /// When the task last changed. Recorded on save; see [`crate::core::sync::merge`].
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub updated_at: Option<OffsetDateTime>,
+    /// When each field last changed, sorted by field. Fields unchanged since the
+    /// task was created have none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub field_changes: Vec<FieldChange>,
+    /// Set on the tombstone a deleted task leaves in the task file, so a sync
+    /// does not bring it back.
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub deleted_at: Option<OffsetDateTime>,
 }
 
 impl Task {
-    pub fn new(title: String, description: Vec<String>, due_date: Date) -> Self {
+    pub fn new(title: String, description: Vec<String>, due_date: Option<Date>) -> Self {
         Self {
             id: Uuid::new_v4(),
             title,
             description,
             status: Status::Todo,
+            group: None,
+            tags: Vec::new(),
+            priority: None,
             is_favorite: false,
-            // group: None,
             created_at: get_today_with_fallbacks().0,
             completed_at: None,
-            due_date: Some(due_date),
+            due_date,
+            blocked_reason: None,
+            status_changes: Vec::new(),
+            depends_on: Vec::new(),
+            time_entries: Vec::new(),
+            pomodoros: 0,
+            estimate: None,
+            defer_until: None,
+            reminders: Vec::new(),
+            updated_at: None,
+            field_changes: Vec::new(),
+            deleted_at: None,
+        }
+    }
+
+    /// Moves the task to `status`, recording when it happened.
+    ///
+    /// Callers are expected to check [`Status::can_transition_to`] first.
+    pub fn set_status(&mut self, status: Status, blocked_reason: Option<String>) {
+        self.completed_at = if status == Status::Completed {
+            Some(get_today_with_fallbacks().0)
+        } else {
+            None
+        };
+        self.blocked_reason = if status == Status::Blocked {
+            blocked_reason
+        } else {
+            None
+        };
+        let now = now_timestamp();
+        if status.is_closed() {
+            self.stop_timer(now);
+        }
+
+        self.status = status;
+        self.status_changes.push(StatusChange { status, at: now });
+    }
+
+    pub fn running_entry(&self) -> Option<&TimeEntry> {
+        self.time_entries.iter().find(|entry| entry.is_running())
+    }
+
+    pub fn start_timer(&mut self, now: OffsetDateTime) {
+        if self.running_entry().is_none() {
+            self.time_entries.push(TimeEntry {
+                started_at: now,
+                ended_at: None,
+            });
+        }
+    }
+
+    pub fn stop_timer(&mut self, now: OffsetDateTime) {
+        for entry in self
+            .time_entries
+            .iter_mut()
+            .filter(|entry| entry.is_running())
+        {
+            entry.ended_at = Some(now);
+        }
+    }
+
+    /// Total time spent on the task, including a running timer.
+    pub fn tracked_time(&self, now: OffsetDateTime) -> Duration {
+        self.time_entries
+            .iter()
+            .map(|entry| entry.duration(now))
+            .sum()
+    }
+
+    /// The marker shown before the title in lists.
+    pub fn glyph(&self) -> &'static str {
+        match self.status {
+            Status::Completed => "✓",
+            Status::Cancelled => "✗",
+            _ if self.is_favorite => "✮",
+            Status::Todo => "☐",
+            Status::InProgress => "◐",
+            Status::Blocked => "⊘",
         }
     }
+
+    /// Whether the task still needs work.
+    pub fn is_open(&self) -> bool {
+        !self.status.is_closed()
+    }
+
+    /// When `reminder` goes off. Reminders relative to the due date count back from
+    /// `due_time` on that day and never go off for tasks without one.
+    pub fn reminder_at(&self, reminder: &Reminder, due_time: Time) -> Option<OffsetDateTime> {
+        match reminder.time {
+            ReminderTime::At { at } => Some(at),
+            ReminderTime::BeforeDue { minutes } => self.due_date.map(|date| {
+                date.with_time(due_time).assume_offset(local_offset())
+                    - Duration::minutes(minutes.into())
+            }),
+        }
+    }
+
+    /// Whether the task is open but set aside until a later day.
+    pub fn is_deferred(&self, today: Date) -> bool {
+        self.is_open() && self.defer_until.is_some_and(|date| date > today)
+    }
+
+    /// The day the task was completed or cancelled. Open tasks, and tasks cancelled
+    /// before status history was kept, have none.
+    pub fn closed_on(&self) -> Option<Date> {
+        if self.is_open() {
+            return None;
+        }
+
+        self.completed_at.or_else(|| {
+            self.status_changes
+                .iter()
+                .rev()
+                .find(|change| change.status.is_closed())
+                .map(|change| change.at.date())
+        })
+    }
+
+    /// Whether the task is still open and its due date has passed.
+    pub fn is_overdue(&self, today: Date) -> bool {
+        self.is_open() && self.due_date.is_some_and(|due| due < today)
+    }
 }
 
 impl From<&Task> for ListItem<'_> {
     fn from(value: &Task) -> Self {
-        let line = match value.status {
-            Status::Todo => {
-                if value.is_favorite {
-                    Line::styled(format!(" ✮ {}", value.title), STAR_TEXT_FG_COLOR)
-                } else {
-                    Line::styled(format!(" ☐ {}", value.title), TEXT_FG_COLOR)
-                }
-            }
+        value.list_item(0)
+    }
+}
+
+impl Task {
+    /// The task as a list row. `waiting_on` is the number of open prerequisites;
+    /// while there are any, an open task is shown as blocked.
+    pub fn list_item(&self, waiting_on: usize) -> ListItem<'static> {
+        let today = get_today_with_fallbacks().0;
+
+        let line = match self.status {
             Status::Completed => {
-                Line::styled(format!(" ✓ {}", value.title), COMPLETED_TEXT_FG_COLOR)
+                Line::styled(format!(" ✓ {}", self.title), COMPLETED_TEXT_FG_COLOR)
+            }
+            Status::Cancelled => {
+                Line::styled(format!(" ✗ {}", self.title), CANCELLED_TEXT_FG_COLOR)
+            }
+            _ if self.is_overdue(today) => Line::from(vec![
+                Span::styled(
+                    format!(" {} {}", self.glyph(), self.title),
+                    OVERDUE_TEXT_FG_COLOR,
+                ),
+                Span::styled(" (overdue)", OVERDUE_LABEL_STYLE),
+            ]),
+            _ if self.is_deferred(today) => Line::from(vec![
+                Span::styled(
+                    format!(" {} {}", self.glyph(), self.title),
+                    DEFERRED_TEXT_FG_COLOR,
+                ),
+                Span::styled(
+                    format!(
+                        " (deferred until {})",
+                        self.defer_until
+                            .and_then(|date| date
+                                .format(format_description!(
+                                    "[day padding:none] [month repr:short]"
+                                ))
+                                .ok())
+                            .unwrap_or_default()
+                    ),
+                    DEFERRED_LABEL_STYLE,
+                ),
+            ]),
+            _ if waiting_on > 0 => Line::from(vec![
+                Span::styled(format!(" ⊘ {}", self.title), BLOCKED_TEXT_FG_COLOR),
+                Span::styled(format!(" (waiting on {})", waiting_on), WAITING_LABEL_STYLE),
+            ]),
+            _ if self.is_favorite => Line::styled(format!(" ✮ {}", self.title), STAR_TEXT_FG_COLOR),
+            Status::Todo => Line::styled(format!(" ☐ {}", self.title), TEXT_FG_COLOR),
+            Status::InProgress => {
+                Line::styled(format!(" ◐ {}", self.title), IN_PROGRESS_TEXT_FG_COLOR)
             }
+            Status::Blocked => Line::styled(format!(" ⊘ {}", self.title), BLOCKED_TEXT_FG_COLOR),
         };
         ListItem::new(line)
     }
@@ -1,16 +1,64 @@
 use ratatui::widgets::ListState;
-use time::Date;
+use strum::IntoEnumIterator;
+use time::{Date, Duration, OffsetDateTime};
 use uuid::Uuid;
 
-use crate::utils::date::get_today_with_fallbacks;
+use crate::{
+    core::{error::AppResult, sync::merge::tombstone, validation::TaskValidator},
+    utils::{
+        config::Config,
+        date::{get_today_with_fallbacks, now_timestamp},
+    },
+};
+
+use super::{
+    agenda::{AgendaSection, build_agenda, compare_for_display},
+    board::{BOARD_STATUSES, BoardColumn, build_board},
+    dependencies::{dependents_of, open_prerequisites},
+    effort::EffortSummary,
+    smart_view::SmartView,
+    stats::Statistics,
+    task::{Estimate, Reminder, ReminderTime, Status, Task, TimeEntry},
+};
+
+/// The main view the tasks are laid out in. Navigation follows its order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TaskLayout {
+    #[default]
+    List,
+    Agenda,
+    Calendar,
+    Board,
+    Stats,
+}
 
-use super::task::{Status, Task};
+impl TaskLayout {
+    pub fn next(&self) -> Self {
+        match self {
+            TaskLayout::List => TaskLayout::Agenda,
+            TaskLayout::Agenda => TaskLayout::Calendar,
+            TaskLayout::Calendar => TaskLayout::Board,
+            TaskLayout::Board => TaskLayout::Stats,
+            TaskLayout::Stats => TaskLayout::List,
+        }
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct TaskList {
     pub task_list: Vec<Task>,
+    /// Tombstones of the tasks deleted since loading, saved so that other
+    /// copies of the list learn about the deletion.
+    pub deleted: Vec<Task>,
     pub state: ListState,
+    pub agenda_state: ListState,
     pub selected_task_id: Option<Uuid>,
+    pub view: SmartView,
+    pub layout: TaskLayout,
+    /// The day focused in the calendar layout; `None` follows today.
+    pub focused_date: Option<Date>,
+    /// Whether deferred tasks are listed before their date arrives.
+    pub show_deferred: bool,
 }
 
 impl TaskList {
@@ -19,12 +67,57 @@ impl TaskList {
     }
 
     pub fn add_task(&mut self, title: String, description: Vec<String>, due_date: Date) {
-        let new_task = Task::new(title, description, due_date);
-        self.selected_task_id = Some(new_task.id);
-        self.task_list.push(new_task);
+        self.insert_task(Task::new(title, description, Some(due_date)));
+    }
+
+    pub fn insert_task(&mut self, task: Task) {
+        // Make sure the new task shows up in the list
+        if !self.view.matches(&task, get_today_with_fallbacks().0) {
+            self.view = SmartView::All;
+        }
+        self.selected_task_id = Some(task.id);
+        self.task_list.push(task);
+        self.sync_selection_state();
+    }
+
+    pub fn update_task(
+        &mut self,
+        id: Uuid,
+        title: String,
+        description: Vec<String>,
+        due_date: Option<Date>,
+        estimate: Option<Estimate>,
+    ) {
+        let display_index = self.get_selected_display_index();
+
+        if let Some(task) = self.task_list.iter_mut().find(|task| task.id == id) {
+            task.title = title;
+            task.description = description;
+            if task.due_date != due_date {
+                // Reminders counted from the due date go off again for the new one
+                for reminder in &mut task.reminders {
+                    if matches!(reminder.time, ReminderTime::BeforeDue { .. }) {
+                        reminder.fired = false;
+                    }
+                }
+            }
+            task.due_date = due_date;
+            task.estimate = estimate;
+        }
+
+        self.keep_selection_in_view(display_index);
         self.sync_selection_state();
     }
 
+    /// The tasks and the tombstones of the deleted ones, as they are saved.
+    pub fn records(&self) -> Vec<Task> {
+        self.task_list
+            .iter()
+            .chain(&self.deleted)
+            .cloned()
+            .collect()
+    }
+
     pub fn get_selected_task(&self) -> Option<&Task> {
         self.selected_task_id
             .and_then(|id| self.task_list.iter().find(|task| task.id == id))
@@ -38,27 +131,371 @@ impl TaskList {
         }
     }
 
-    pub fn get_sorted_tasks(&mut self) -> Vec<&Task> {
+    pub fn get_sorted_tasks(&self) -> Vec<&Task> {
         use itertools::Itertools;
 
+        let today = get_today_with_fallbacks().0;
+
         self.task_list
             .iter()
-            .sorted_by(|a, b| {
-                let a_completed = a.status == Status::Completed;
-                let b_completed = b.status == Status::Completed;
+            .filter(|task| self.is_listed(task, self.view, today))
+            .sorted_by(|a, b| compare_for_display(a, b))
+            .collect()
+    }
 
-                if a_completed != b_completed {
-                    return a_completed.cmp(&b_completed);
-                }
+    pub fn get_agenda(&self) -> Vec<AgendaSection<'_>> {
+        let today = get_today_with_fallbacks().0;
+        build_agenda(&self.task_list, today, Config::global().agenda.days)
+    }
+
+    pub fn get_focused_date(&self) -> Date {
+        self.focused_date
+            .unwrap_or_else(|| get_today_with_fallbacks().0)
+    }
+
+    /// Focuses a day in the calendar and selects its first task, if any.
+    pub fn set_focused_date(&mut self, date: Date) {
+        self.focused_date = Some(date);
+
+        if self.get_selected_display_index().is_none() {
+            self.selected_task_id = None;
+            self.select_first();
+        }
+        self.sync_selection_state();
+    }
+
+    pub fn get_tasks_due_on(&self, date: Date) -> Vec<&Task> {
+        use itertools::Itertools;
+
+        self.task_list
+            .iter()
+            .filter(|task| task.due_date == Some(date))
+            .sorted_by(|a, b| compare_for_display(a, b))
+            .collect()
+    }
+
+    /// Tasks in the order the current layout shows them.
+    pub fn get_display_tasks(&self) -> Vec<&Task> {
+        match self.layout {
+            TaskLayout::List => self.get_sorted_tasks(),
+            TaskLayout::Agenda => self
+                .get_agenda()
+                .into_iter()
+                .flat_map(|section| section.tasks)
+                .collect(),
+            TaskLayout::Calendar => self.get_tasks_due_on(self.get_focused_date()),
+            TaskLayout::Board => build_board(&self.task_list)
+                .into_iter()
+                .flat_map(|column| column.tasks)
+                .collect(),
+            TaskLayout::Stats => Vec::new(),
+        }
+    }
+
+    pub fn get_statistics(&self) -> Statistics {
+        Statistics::compute(&self.task_list, get_today_with_fallbacks().0)
+    }
+
+    pub fn get_board(&self) -> Vec<BoardColumn<'_>> {
+        build_board(&self.task_list)
+    }
+
+    /// Column and row of the selected task on the board.
+    pub fn get_board_position(&self) -> Option<(usize, usize)> {
+        let id = self.selected_task_id?;
+
+        self.get_board()
+            .iter()
+            .enumerate()
+            .find_map(|(column_index, column)| {
+                column
+                    .tasks
+                    .iter()
+                    .position(|task| task.id == id)
+                    .map(|row| (column_index, row))
+            })
+    }
+
+    /// Moves the selection up or down within its board column, wrapping around.
+    pub fn select_in_board_column(&mut self, forward: bool) {
+        let Some((column_index, row)) = self.get_board_position() else {
+            self.select_first();
+            return;
+        };
+
+        let board = self.get_board();
+        let column = &board[column_index].tasks;
+        let next_row = if forward {
+            (row + 1) % column.len()
+        } else {
+            (row + column.len() - 1) % column.len()
+        };
+
+        self.selected_task_id = Some(column[next_row].id);
+        self.sync_selection_state();
+    }
+
+    /// Selects a task in the nearest non-empty column to the left or right,
+    /// keeping the row where possible.
+    pub fn select_board_column(&mut self, forward: bool) {
+        let (column_index, row) = self.get_board_position().unwrap_or((0, 0));
+        let board = self.get_board();
+
+        let candidates: Vec<usize> = if forward {
+            (column_index + 1..board.len()).collect()
+        } else {
+            (0..column_index).rev().collect()
+        };
+
+        let target = candidates
+            .into_iter()
+            .find(|index| !board[*index].tasks.is_empty())
+            .map(|index| {
+                let column = &board[index].tasks;
+                column[row.min(column.len() - 1)].id
+            });
+
+        if let Some(id) = target {
+            self.selected_task_id = Some(id);
+            self.sync_selection_state();
+        }
+    }
+
+    /// The status column to the left or right of the selected card, if any.
+    pub fn board_neighbour_status(&self, forward: bool) -> Option<Status> {
+        let task = self.get_selected_task()?;
+
+        let statuses = BOARD_STATUSES;
+        let index = statuses
+            .iter()
+            .position(|status| *status == task.status)
+            .unwrap_or(0);
+        let target = if forward {
+            statuses.get(index + 1)
+        } else {
+            index.checked_sub(1).and_then(|index| statuses.get(index))
+        };
+
+        target.copied()
+    }
+
+    /// Moves the selected task to `status` if the lifecycle allows it.
+    pub fn set_status(&mut self, status: Status, blocked_reason: Option<String>) -> AppResult<()> {
+        let display_index = self.get_selected_display_index();
+
+        if let Some(task) = self.get_selected_task_mut() {
+            if task.status == status && status != Status::Blocked {
+                return Ok(());
+            }
+
+            TaskValidator::validate_status_transition(task.status, status)?;
+            task.set_status(status, blocked_reason);
+        }
+
+        self.keep_selection_in_view(display_index);
+        self.sync_selection_state();
+        Ok(())
+    }
+
+    /// Prerequisites of `task` that are still open.
+    pub fn open_prerequisites(&self, task: &Task) -> Vec<&Task> {
+        open_prerequisites(task, &self.task_list)
+    }
+
+    /// Tasks that depend on the task with `id`.
+    pub fn dependents_of(&self, id: Uuid) -> Vec<&Task> {
+        dependents_of(id, &self.task_list)
+    }
+
+    /// Replaces the prerequisites of the selected task.
+    pub fn set_dependencies(&mut self, depends_on: Vec<Uuid>) -> AppResult<()> {
+        let Some(id) = self.selected_task_id else {
+            return Ok(());
+        };
+
+        let depends_on = TaskValidator::validate_dependencies(id, &depends_on, &self.task_list)?;
+
+        if let Some(task) = self.get_selected_task_mut() {
+            task.depends_on = depends_on;
+        }
+        Ok(())
+    }
+
+    /// The task whose timer is running, if any.
+    pub fn running_timer(&self) -> Option<&Task> {
+        self.task_list
+            .iter()
+            .find(|task| task.running_entry().is_some())
+    }
+
+    /// Starts a timer on the selected task, stopping the one running elsewhere, or
+    /// stops it if it is already running there.
+    pub fn toggle_timer(&mut self, now: OffsetDateTime) {
+        let Some(id) = self.selected_task_id else {
+            return;
+        };
+
+        let was_running = self
+            .get_selected_task()
+            .is_some_and(|task| task.running_entry().is_some());
+
+        for task in &mut self.task_list {
+            task.stop_timer(now);
+        }
+
+        if !was_running && let Some(task) = self.task_list.iter_mut().find(|task| task.id == id) {
+            task.start_timer(now);
+        }
+    }
+
+    /// Records `duration` on the selected task as time that ended `now`.
+    pub fn add_time_entry(&mut self, duration: Duration, now: OffsetDateTime) {
+        if let Some(task) = self.get_selected_task_mut() {
+            task.time_entries.push(TimeEntry {
+                started_at: now - duration,
+                ended_at: Some(now),
+            });
+        }
+    }
+
+    /// Changes how long an entry of the selected task lasted, keeping its start.
+    /// A running entry is stopped at the new length.
+    pub fn set_time_entry_duration(&mut self, index: usize, duration: Duration) {
+        if let Some(entry) = self
+            .get_selected_task_mut()
+            .and_then(|task| task.time_entries.get_mut(index))
+        {
+            entry.ended_at = Some(entry.started_at + duration);
+        }
+    }
+
+    pub fn delete_time_entry(&mut self, index: usize) {
+        if let Some(task) = self.get_selected_task_mut()
+            && index < task.time_entries.len()
+        {
+            task.time_entries.remove(index);
+        }
+    }
+
+    pub fn record_pomodoro(&mut self, id: Uuid) {
+        if let Some(task) = self.task_list.iter_mut().find(|task| task.id == id) {
+            task.pomodoros += 1;
+        }
+    }
+
+    /// Estimated work left in the current smart view.
+    pub fn view_effort(&self) -> EffortSummary {
+        EffortSummary::of(self.get_sorted_tasks())
+    }
+
+    /// Estimated work left on all tasks in `group`.
+    pub fn group_effort(&self, group: &str) -> EffortSummary {
+        EffortSummary::of(
+            self.task_list
+                .iter()
+                .filter(|task| task.group.as_deref() == Some(group)),
+        )
+    }
+
+    pub fn add_reminder(&mut self, time: ReminderTime) {
+        if let Some(task) = self.get_selected_task_mut() {
+            task.reminders.push(Reminder { time, fired: false });
+        }
+    }
+
+    pub fn delete_reminder(&mut self, index: usize) {
+        if let Some(task) = self.get_selected_task_mut()
+            && index < task.reminders.len()
+        {
+            task.reminders.remove(index);
+        }
+    }
+
+    /// Time tracked on all tasks in `group`.
+    pub fn group_tracked_time(&self, group: &str, now: OffsetDateTime) -> Duration {
+        self.task_list
+            .iter()
+            .filter(|task| task.group.as_deref() == Some(group))
+            .map(|task| task.tracked_time(now))
+            .sum()
+    }
+
+    pub fn set_layout(&mut self, layout: TaskLayout) {
+        self.layout = layout;
+
+        if self.get_selected_display_index().is_none() {
+            self.selected_task_id = None;
+            self.select_first();
+        }
+        self.sync_selection_state();
+    }
+
+    /// Switches the smart view, keeping the selection if the task is still visible.
+    pub fn set_view(&mut self, view: SmartView) {
+        self.view = view;
+
+        if self.get_selected_display_index().is_none() {
+            self.selected_task_id = None;
+            self.select_first();
+        }
+        self.sync_selection_state();
+    }
+
+    /// Whether `task` shows up in the list under `view`. Deferred tasks are left
+    /// out until their date unless `show_deferred` is set.
+    fn is_listed(&self, task: &Task, view: SmartView, today: Date) -> bool {
+        view.matches(task, today) && (self.show_deferred || !task.is_deferred(today))
+    }
+
+    /// Open tasks currently hidden because they are deferred.
+    pub fn deferred_count(&self) -> usize {
+        let today = get_today_with_fallbacks().0;
+        self.task_list
+            .iter()
+            .filter(|task| task.is_deferred(today))
+            .count()
+    }
+
+    pub fn toggle_show_deferred(&mut self) {
+        let display_index = self.get_selected_display_index();
+        self.show_deferred = !self.show_deferred;
+        self.keep_selection_in_view(display_index);
+        self.sync_selection_state();
+    }
 
-                a.is_favorite.cmp(&b.is_favorite).reverse()
+    /// Defers the selected task until `date`, or brings it back with `None`.
+    pub fn set_defer_until(&mut self, date: Option<Date>) -> AppResult<()> {
+        let date = date.map(TaskValidator::validate_defer_until).transpose()?;
+        let display_index = self.get_selected_display_index();
+
+        if let Some(task) = self.get_selected_task_mut() {
+            task.defer_until = date;
+        }
+
+        self.keep_selection_in_view(display_index);
+        self.sync_selection_state();
+        Ok(())
+    }
+
+    /// Number of tasks in each smart view, in tab order.
+    pub fn view_counts(&self) -> Vec<(SmartView, usize)> {
+        let today = get_today_with_fallbacks().0;
+
+        SmartView::iter()
+            .map(|view| {
+                let count = self
+                    .task_list
+                    .iter()
+                    .filter(|task| self.is_listed(task, view, today))
+                    .count();
+                (view, count)
             })
             .collect()
     }
 
     pub fn get_selected_display_index(&mut self) -> Option<usize> {
         self.selected_task_id.and_then(|id| {
-            self.get_sorted_tasks()
+            self.get_display_tasks()
                 .iter()
                 .position(|task| task.id == id)
         })
@@ -78,7 +515,13 @@ impl TaskList {
         if let Some(selected_id) = self.selected_task_id {
             let deleted_index = self.get_selected_display_index().unwrap_or(0);
 
+            if let Some(task) = self.get_selected_task() {
+                self.deleted.push(tombstone(task, now_timestamp()));
+            }
             self.task_list.retain(|task| task.id != selected_id);
+            for task in &mut self.task_list {
+                task.depends_on.retain(|id| *id != selected_id);
+            }
             self.selected_task_id = None;
 
             if !self.task_list.is_empty() {
@@ -90,8 +533,18 @@ impl TaskList {
         }
     }
 
+    /// Moves the selection to a neighbour when the selected task left the current view.
+    fn keep_selection_in_view(&mut self, previous_index: Option<usize>) {
+        if let Some(index) = previous_index
+            && self.get_selected_display_index().is_none()
+        {
+            self.selected_task_id = None;
+            self.select_task_near_deleted_task(index);
+        }
+    }
+
     pub fn select_task_near_deleted_task(&mut self, deleted_index: usize) {
-        let sorted_tasks = self.get_sorted_tasks();
+        let sorted_tasks = self.get_display_tasks();
 
         if sorted_tasks.is_empty() {
             return;
@@ -104,21 +557,19 @@ impl TaskList {
         }
     }
 
-    pub fn toggle_status(&mut self) {
-        if let Some(task) = self.get_selected_task_mut() {
-            match task.status {
-                Status::Completed => {
-                    task.status = Status::Todo;
-                    task.completed_at = None;
-                }
-                Status::Todo => {
-                    task.status = Status::Completed;
-                    task.completed_at = Some(get_today_with_fallbacks().0);
-                }
-            }
-        }
+    /// Completes an open task, or reopens a finished or cancelled one.
+    pub fn toggle_status(&mut self) -> AppResult<()> {
+        let Some(task) = self.get_selected_task() else {
+            return Ok(());
+        };
 
-        self.sync_selection_state();
+        let status = if task.is_open() {
+            Status::Completed
+        } else {
+            Status::Todo
+        };
+
+        self.set_status(status, None)
     }
 
     pub fn toggle_favorite(&mut self) {
@@ -135,7 +586,7 @@ impl TaskList {
 
     pub fn select_next(&mut self) {
         let selected_id = self.selected_task_id;
-        let tasks = self.get_sorted_tasks();
+        let tasks = self.get_display_tasks();
 
         if tasks.is_empty() {
             return;
@@ -150,7 +601,7 @@ impl TaskList {
 
     pub fn select_previous(&mut self) {
         let selected_id = self.selected_task_id;
-        let tasks = self.get_sorted_tasks();
+        let tasks = self.get_display_tasks();
 
         if tasks.is_empty() {
             return;
@@ -166,7 +617,7 @@ impl TaskList {
     }
 
     pub fn select_first(&mut self) {
-        let tasks = self.get_sorted_tasks();
+        let tasks = self.get_display_tasks();
 
         if tasks.is_empty() {
             return;
@@ -177,7 +628,7 @@ impl TaskList {
     }
 
     pub fn select_last(&mut self) {
-        let tasks = self.get_sorted_tasks();
+        let tasks = self.get_display_tasks();
 
         if tasks.is_empty() {
             return;
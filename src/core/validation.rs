@@ -1,14 +1,58 @@
 use std::collections::HashMap;
 
-use time::{Date, Duration, format_description, macros::format_description};
+use serde::{Deserialize, Serialize};
+use time::{Date, Duration, OffsetDateTime, Time, format_description, macros};
 use uuid::Uuid;
 
-use crate::{ui::forms::task_form::FormField, utils::date::get_today_with_fallbacks};
-
-use super::error::AppError;
+use crate::{
+    ui::forms::task_form::FormField,
+    utils::{
+        config::Config,
+        date::{get_today_with_fallbacks, local_offset, parse_natural_date},
+        duration::parse_duration,
+    },
+};
+
+use super::{
+    error::AppError,
+    task::{
+        Task,
+        dependencies::find_cycle,
+        task::{Estimate, ReminderTime, Status},
+    },
+};
 
 pub type ValidationResult<T> = Result<T, AppError>;
 
+/// How due dates before today are treated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PastDueDates {
+    #[default]
+    Reject,
+    Warn,
+    Allow,
+}
+
+/// Limits applied to due dates, configured under `dueDate` in `config.json`, e.g.
+/// `{ "dueDate": { "pastDates": "warn", "maxDaysAhead": null } }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DueDatePolicy {
+    pub past_dates: PastDueDates,
+    /// How far ahead a due date may be; `null` removes the limit.
+    pub max_days_ahead: Option<u32>,
+}
+
+impl Default for DueDatePolicy {
+    fn default() -> Self {
+        Self {
+            past_dates: PastDueDates::Reject,
+            max_days_ahead: Some(365),
+        }
+    }
+}
+
 pub struct TaskValidator;
 
 impl TaskValidator {
@@ -56,25 +100,232 @@ impl TaskValidator {
     }
 
     pub fn validate_due_date(date: Date) -> ValidationResult<Date> {
+        Self::validate_due_date_against(date, None)
+    }
+
+    /// Validates a due date under the configured [`DueDatePolicy`].
+    ///
+    /// `original` is the date the task already had, if any: keeping it unchanged is
+    /// always accepted, so editing an overdue task does not trip the past-date rule.
+    pub fn validate_due_date_against(date: Date, original: Option<Date>) -> ValidationResult<Date> {
+        if original == Some(date) {
+            return Ok(date);
+        }
+
+        let policy = &Config::global().due_date;
         let today = get_today_with_fallbacks().0;
 
-        if date < today {
+        if date < today && policy.past_dates == PastDueDates::Reject {
             return Err(AppError::InvalidDate {
                 input: date.to_string(),
                 expected_format: "Date cannot be in the past".to_string(),
             });
         }
 
-        if date > today.saturating_add(Duration::days(365)) {
+        if let Some(max_days_ahead) = policy.max_days_ahead
+            && date > today.saturating_add(Duration::days(max_days_ahead.into()))
+        {
+            return Err(AppError::InvalidDate {
+                input: date.to_string(),
+                expected_format: format!(
+                    "Date cannot be more than {} days in the future",
+                    max_days_ahead
+                ),
+            });
+        }
+
+        Ok(date)
+    }
+
+    /// A warning to show when an accepted due date is already in the past.
+    pub fn due_date_warning(date: Date) -> Option<AppError> {
+        let today = get_today_with_fallbacks().0;
+
+        if date < today && Config::global().due_date.past_dates == PastDueDates::Warn {
+            Some(AppError::PastDueDate { date })
+        } else {
+            None
+        }
+    }
+
+    pub fn validate_group(group: &str) -> ValidationResult<String> {
+        let trimmed = group.trim();
+
+        if trimmed.is_empty() || trimmed.len() > 50 {
+            return Err(AppError::InvalidGroup {
+                reason: "Group must be between 1 and 50 characters".to_string(),
+            });
+        }
+
+        if trimmed.contains(char::is_whitespace) {
+            return Err(AppError::InvalidGroup {
+                reason: "Group cannot contain whitespace".to_string(),
+            });
+        }
+
+        Ok(trimmed.to_string())
+    }
+
+    pub fn validate_tag(tag: &str) -> ValidationResult<String> {
+        let trimmed = tag.trim();
+
+        if trimmed.is_empty() || trimmed.len() > 30 {
+            return Err(AppError::InvalidTag {
+                reason: "Tag must be between 1 and 30 characters".to_string(),
+            });
+        }
+
+        if trimmed.contains(char::is_whitespace) {
+            return Err(AppError::InvalidTag {
+                reason: "Tag cannot contain whitespace".to_string(),
+            });
+        }
+
+        Ok(trimmed.to_string())
+    }
+
+    pub fn validate_status_transition(from: Status, to: Status) -> ValidationResult<Status> {
+        if from.can_transition_to(to) {
+            Ok(to)
+        } else {
+            Err(AppError::InvalidStatusTransition {
+                from: from.label().to_string(),
+                to: to.label().to_string(),
+            })
+        }
+    }
+
+    /// Checks the prerequisites chosen for `task_id`: they must exist and must not
+    /// lead back to the task itself. Duplicates are dropped.
+    pub fn validate_dependencies(
+        task_id: Uuid,
+        depends_on: &[Uuid],
+        tasks: &[Task],
+    ) -> ValidationResult<Vec<Uuid>> {
+        let existing_ids: Vec<Uuid> = tasks.iter().map(|task| task.id).collect();
+        let mut validated = Vec::new();
+
+        for id in depends_on {
+            let id = Self::validate_task_id(*id, &existing_ids)?;
+            if !validated.contains(&id) {
+                validated.push(id);
+            }
+        }
+
+        if let Some(cycle) = find_cycle(task_id, &validated, tasks) {
+            let titles = cycle
+                .iter()
+                .filter_map(|id| tasks.iter().find(|task| task.id == *id))
+                .map(|task| task.title.clone())
+                .collect();
+            return Err(AppError::DependencyCycle { titles });
+        }
+
+        Ok(validated)
+    }
+
+    /// Parses a manually entered time entry length, e.g. `1h30m` or `45`.
+    pub fn validate_duration(input: &str) -> ValidationResult<Duration> {
+        let duration = parse_duration(input).ok_or_else(|| AppError::InvalidDuration {
+            input: input.trim().to_string(),
+            reason: "expected e.g. '45', '1h30m' or '1:30'".to_string(),
+        })?;
+
+        if duration <= Duration::ZERO {
+            return Err(AppError::InvalidDuration {
+                input: input.trim().to_string(),
+                reason: "must be longer than zero".to_string(),
+            });
+        }
+
+        if duration > Duration::DAY {
+            return Err(AppError::InvalidDuration {
+                input: input.trim().to_string(),
+                reason: "cannot exceed 24 hours".to_string(),
+            });
+        }
+
+        Ok(duration)
+    }
+
+    /// A task can only be deferred to a day after today.
+    pub fn validate_defer_until(date: Date) -> ValidationResult<Date> {
+        if date <= get_today_with_fallbacks().0 {
             return Err(AppError::InvalidDate {
                 input: date.to_string(),
-                expected_format: "Date cannot be more than 1 year in the future".to_string(),
+                expected_format: "Defer date must be after today".to_string(),
             });
         }
 
         Ok(date)
     }
 
+    /// Parses a reminder: an offset before the due date such as `30m before` or
+    /// `1d before`, or a moment such as `14:00`, `tomorrow 9:00` or
+    /// `2026-10-20 14:00`. A bare time means the next time the clock shows it.
+    pub fn validate_reminder(
+        input: &str,
+        now: OffsetDateTime,
+        has_due_date: bool,
+    ) -> ValidationResult<ReminderTime> {
+        let trimmed = input.trim().to_lowercase();
+        let invalid = |reason: &str| AppError::InvalidReminder {
+            input: input.trim().to_string(),
+            reason: reason.to_string(),
+        };
+
+        if let Some(offset) = trimmed
+            .strip_suffix("before due")
+            .or_else(|| trimmed.strip_suffix("before"))
+        {
+            if !has_due_date {
+                return Err(invalid("the task has no due date"));
+            }
+            let duration = parse_duration(offset)
+                .filter(|duration| !duration.is_negative())
+                .ok_or_else(|| invalid("expected e.g. '30m before' or '1d before'"))?;
+            return Ok(ReminderTime::BeforeDue {
+                minutes: u32::try_from(duration.whole_minutes()).unwrap_or(u32::MAX),
+            });
+        }
+
+        let (date_part, time_part) = trimmed.rsplit_once(' ').unwrap_or(("", &trimmed));
+        let time = Time::parse(
+            time_part,
+            macros::format_description!("[hour padding:none]:[minute]"),
+        )
+        .map_err(|_| invalid("expected e.g. '30m before', '14:00' or 'tomorrow 9:00'"))?;
+
+        let today = now.date();
+        let at = if date_part.trim().is_empty() {
+            let at = today.with_time(time).assume_offset(local_offset());
+            if at > now { at } else { at + Duration::DAY }
+        } else {
+            let date = DateValidator::resolve_date_input(date_part)
+                .map_err(|_| invalid("expected a date such as 'tomorrow' or 'YYYY-MM-DD'"))?;
+            date.with_time(time).assume_offset(local_offset())
+        };
+
+        if at <= now {
+            return Err(invalid("that time has already passed"));
+        }
+
+        Ok(ReminderTime::At { at })
+    }
+
+    /// An empty input clears the estimate.
+    pub fn validate_estimate(input: &str) -> ValidationResult<Option<Estimate>> {
+        if input.trim().is_empty() {
+            return Ok(None);
+        }
+
+        Estimate::parse(input)
+            .map(Some)
+            .ok_or_else(|| AppError::InvalidEstimate {
+                input: input.trim().to_string(),
+            })
+    }
+
     pub fn validate_task_data(
         title: &str,
         description: &[String],
@@ -98,7 +349,9 @@ impl TaskValidator {
     pub fn validate_all_task_field(
         title: &str,
         description: &[String],
-        due_date: Date,
+        due_date: Option<Date>,
+        original_due_date: Option<Date>,
+        estimate: &str,
     ) -> Result<(), HashMap<FormField, String>> {
         let mut field_errors = HashMap::<FormField, String>::new();
 
@@ -110,10 +363,18 @@ impl TaskValidator {
             field_errors.insert(FormField::Description, app_error.user_message());
         }
 
-        if let Err(app_error) = TaskValidator::validate_due_date(due_date) {
+        // A task may go without a due date
+        if let Some(due_date) = due_date
+            && let Err(app_error) =
+                TaskValidator::validate_due_date_against(due_date, original_due_date)
+        {
             field_errors.insert(FormField::DueDate, app_error.user_message());
         }
 
+        if let Err(app_error) = TaskValidator::validate_estimate(estimate) {
+            field_errors.insert(FormField::Estimate, app_error.user_message());
+        }
+
         if field_errors.is_empty() {
             Ok(())
         } else {
@@ -125,7 +386,7 @@ impl TaskValidator {
 pub struct DateValidator;
 
 impl DateValidator {
-    pub fn parse_date_string(input: &str, format: &str) -> ValidationResult<Date> {
+    pub fn parse_date(input: &str, format: &str) -> ValidationResult<Date> {
         let trimmed = input.trim();
 
         if trimmed.is_empty() {
@@ -140,17 +401,44 @@ impl DateValidator {
             expected_format: format.to_string(),
         })?;
 
-        let parsed_date =
-            Date::parse(trimmed, &format_desc).map_err(|_| AppError::InvalidDate {
-                input: input.to_string(),
-                expected_format: format.to_string(),
-            })?;
+        Date::parse(trimmed, &format_desc).map_err(|_| AppError::InvalidDate {
+            input: input.to_string(),
+            expected_format: format.to_string(),
+        })
+    }
+
+    pub fn parse_date_string(input: &str, format: &str) -> ValidationResult<Date> {
+        let parsed_date = Self::parse_date(input, format)?;
 
         TaskValidator::validate_due_date(parsed_date)
     }
 
+    /// Resolves either an ISO date or a natural-language expression such as
+    /// `tomorrow` or `in 3 days`, relative to today.
+    pub fn resolve_date_input(input: &str) -> ValidationResult<Date> {
+        if let Ok(date) = Self::parse_date(input, "[year]-[month]-[day]") {
+            return Ok(date);
+        }
+
+        parse_natural_date(input, get_today_with_fallbacks().0).ok_or_else(|| {
+            AppError::InvalidDate {
+                input: input.trim().to_string(),
+                expected_format: "YYYY-MM-DD or e.g. 'fri', 'in 3 days'".to_string(),
+            }
+        })
+    }
+
     pub fn validate_date_input(input: &str) -> ValidationResult<Date> {
-        Self::parse_date_string(input, "[year]-[month]-[day]")
+        Self::validate_date_input_against(input, None)
+    }
+
+    pub fn validate_date_input_against(
+        input: &str,
+        original: Option<Date>,
+    ) -> ValidationResult<Date> {
+        let date = Self::resolve_date_input(input)?;
+
+        TaskValidator::validate_due_date_against(date, original)
     }
 }
 
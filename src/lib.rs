@@ -1,4 +1,5 @@
 pub mod app;
+pub mod cli;
 pub mod core;
 pub mod ui;
 pub mod utils;
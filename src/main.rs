@@ -1,7 +1,17 @@
-use delibird::app::App;
+use delibird::{app::App, cli};
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(message) = cli::run(&args) {
+            eprintln!("delibird: {}", message);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let terminal = ratatui::init();
     let app = App::new().map_err(|err| {
         ratatui::restore();
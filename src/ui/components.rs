@@ -5,12 +5,17 @@ use ratatui::{
     widgets::{Block, Padding, Widget},
 };
 
-use crate::app::App;
+use crate::{app::App, core::task::TaskLayout};
 
+pub mod agenda;
+pub mod board;
+pub mod calendar;
 pub mod error_display;
 pub mod footer;
 pub mod messages;
+pub mod notification;
 pub mod popups;
+pub mod stats;
 pub mod task_detail;
 pub mod task_list;
 
@@ -27,19 +32,68 @@ impl Widget for &mut App {
         let [main_area, footer_area] =
             Layout::vertical([Constraint::Fill(1), Constraint::Length(2)]).areas(inner_area);
 
+        let [tabs_area, main_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(main_area);
+
         let [list_area, detail_area] =
             Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
                 .areas(main_area);
 
-        self.render_task_list(list_area, buf);
-        self.render_detail(detail_area, buf);
+        match self.task_list.layout {
+            TaskLayout::List => {
+                self.render_view_tabs(tabs_area, buf);
+                self.render_task_list(list_area, buf);
+                self.render_detail(detail_area, buf);
+            }
+            TaskLayout::Agenda => {
+                self.render_agenda(list_area, buf);
+                self.render_detail(detail_area, buf);
+            }
+            TaskLayout::Calendar => {
+                self.render_calendar(main_area, buf);
+            }
+            TaskLayout::Board => {
+                self.render_board(main_area, buf);
+            }
+            TaskLayout::Stats => {
+                self.render_stats(main_area, buf);
+            }
+        }
         self.render_footer(footer_area, buf);
 
         if self.task_form.is_open {
             self.render_add_task_popup(area, buf);
         }
 
+        if self.quick_add.is_open {
+            self.render_quick_add_popup(area, buf);
+        }
+
+        if self.status_picker.is_open {
+            self.render_status_picker(area, buf);
+        }
+
+        if self.dependency_picker.is_open {
+            self.render_dependency_picker(area, buf);
+        }
+
+        if self.snooze_picker.is_open {
+            self.render_snooze_picker(area, buf);
+        }
+
+        if self.time_log.is_open {
+            self.render_time_log(area, buf);
+        }
+
+        if self.reminder_list.is_open {
+            self.render_reminder_list(area, buf);
+        }
+
+        self.render_pomodoro(area, buf);
+
         self.render_delete_popup(area, buf);
+        self.render_close_popup(area, buf);
         self.render_error_state(area, buf);
+        self.render_notification_banner(area, buf);
     }
 }
@@ -0,0 +1,87 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style, palette::tailwind::SLATE},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, HighlightSpacing, List, ListItem, StatefulWidget},
+};
+
+use crate::{
+    app::App,
+    core::task::{agenda::AgendaHeading, effort::EffortSummary},
+    utils::{config::Config, date::get_today_with_fallbacks},
+};
+
+const SELECTED_STYLE: Style = Style::new().bg(SLATE.c800).add_modifier(Modifier::BOLD);
+const HEADING_STYLE: Style = Style::new().fg(SLATE.c400).add_modifier(Modifier::BOLD);
+const EFFORT_STYLE: Style = Style::new().fg(SLATE.c500);
+const OVERBOOKED_STYLE: Style = Style::new()
+    .fg(Color::LightRed)
+    .add_modifier(Modifier::ITALIC);
+const EMPTY_DAY_STYLE: Style = Style::new().fg(SLATE.c600).add_modifier(Modifier::ITALIC);
+
+impl App {
+    pub fn render_agenda(&mut self, area: Rect, buf: &mut Buffer) {
+        let today = get_today_with_fallbacks().0;
+        let selected_id = self.task_list.selected_task_id;
+
+        let block = Block::new()
+            .title(format!(
+                "Agenda · next {} days",
+                Config::global().agenda.days
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded);
+
+        let mut items: Vec<ListItem> = Vec::new();
+        let mut selected_row = None;
+
+        let capacity = Config::global().agenda.daily_capacity_minutes;
+
+        for section in self.task_list.get_agenda() {
+            let effort = EffortSummary::of(section.tasks.iter().copied());
+            let overbooked =
+                matches!(section.heading, AgendaHeading::Day(_)) && effort.exceeds(capacity);
+
+            let heading_style = match section.heading {
+                AgendaHeading::Overdue => HEADING_STYLE.fg(Color::LightRed),
+                _ if overbooked => HEADING_STYLE.fg(Color::LightRed),
+                AgendaHeading::Day(date) if date == today => HEADING_STYLE.fg(Color::Yellow),
+                AgendaHeading::Day(_) => HEADING_STYLE,
+            };
+
+            let mut heading = vec![Span::styled(section.heading.label(today), heading_style)];
+            if !effort.is_empty() {
+                heading.push(Span::styled(format!(" · {}", effort), EFFORT_STYLE));
+            }
+            if overbooked {
+                heading.push(Span::styled(" (overbooked)", OVERBOOKED_STYLE));
+            }
+            items.push(ListItem::new(Line::from(heading)));
+
+            if section.tasks.is_empty() {
+                items.push(ListItem::new(Line::styled(
+                    "   Nothing due",
+                    EMPTY_DAY_STYLE,
+                )));
+            }
+
+            for task in section.tasks {
+                if Some(task.id) == selected_id {
+                    selected_row = Some(items.len());
+                }
+                items.push(task.list_item(self.task_list.open_prerequisites(task).len()));
+            }
+        }
+
+        self.task_list.agenda_state.select(selected_row);
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(SELECTED_STYLE)
+            .highlight_symbol(">>")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, area, buf, &mut self.task_list.agenda_state);
+    }
+}
@@ -0,0 +1,65 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, palette::tailwind::SLATE},
+    widgets::{
+        Block, BorderType, Borders, HighlightSpacing, List, ListItem, ListState, StatefulWidget,
+    },
+};
+
+use crate::app::App;
+
+const SELECTED_STYLE: Style = Style::new().bg(SLATE.c800).add_modifier(Modifier::BOLD);
+
+impl App {
+    pub fn render_board(&mut self, area: Rect, buf: &mut Buffer) {
+        let [columns_area, detail_area] =
+            Layout::vertical([Constraint::Percentage(65), Constraint::Percentage(35)]).areas(area);
+
+        let board = self.task_list.get_board();
+        let selected_id = self.task_list.selected_task_id;
+
+        let column_areas =
+            Layout::horizontal(vec![Constraint::Fill(1); board.len()]).split(columns_area);
+
+        for (column, column_area) in board.iter().zip(column_areas.iter()) {
+            let selected_row = column
+                .tasks
+                .iter()
+                .position(|task| Some(task.id) == selected_id);
+
+            let border_style = if selected_row.is_some() {
+                Style::default().fg(Color::LightYellow)
+            } else {
+                Style::default()
+            };
+
+            let block = Block::new()
+                .title(format!(
+                    "{} ({})",
+                    column.status.label(),
+                    column.tasks.len()
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(border_style);
+
+            let items: Vec<ListItem> = column
+                .tasks
+                .iter()
+                .map(|task| task.list_item(self.task_list.open_prerequisites(task).len()))
+                .collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(SELECTED_STYLE)
+                .highlight_symbol(">>")
+                .highlight_spacing(HighlightSpacing::Always);
+
+            let mut state = ListState::default().with_selected(selected_row);
+            StatefulWidget::render(list, *column_area, buf, &mut state);
+        }
+
+        self.render_detail(detail_area, buf);
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, palette::tailwind::SLATE},
+    text::{Line, Span},
+    widgets::{
+        Block, BorderType, Borders, HighlightSpacing, List, ListItem, Paragraph, StatefulWidget,
+        Widget,
+        calendar::{CalendarEventStore, Monthly},
+    },
+};
+use time::{Date, macros::format_description};
+
+use crate::{app::App, utils::date::get_today_with_fallbacks};
+
+const SELECTED_STYLE: Style = Style::new().bg(SLATE.c800).add_modifier(Modifier::BOLD);
+const ONE_TASK_STYLE: Style = Style::new().fg(Color::LightGreen);
+const TWO_TASKS_STYLE: Style = Style::new().fg(Color::Yellow);
+const MANY_TASKS_STYLE: Style = Style::new()
+    .fg(Color::LightMagenta)
+    .add_modifier(Modifier::BOLD);
+const OVERDUE_STYLE: Style = Style::new().fg(Color::Red).add_modifier(Modifier::BOLD);
+
+impl App {
+    pub fn render_calendar(&mut self, area: Rect, buf: &mut Buffer) {
+        let [calendar_area, tasks_area] =
+            Layout::horizontal([Constraint::Length(30), Constraint::Fill(1)]).areas(area);
+
+        let [month_area, legend_area] =
+            Layout::vertical([Constraint::Length(10), Constraint::Fill(1)]).areas(calendar_area);
+
+        let [day_area, detail_area] =
+            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(tasks_area);
+
+        self.render_month(month_area, buf);
+        render_calendar_legend(legend_area, buf);
+        self.render_focused_day(day_area, buf);
+        self.render_detail(detail_area, buf);
+    }
+
+    fn render_month(&self, area: Rect, buf: &mut Buffer) {
+        let today = get_today_with_fallbacks().0;
+        let focused = self.task_list.get_focused_date();
+
+        // (tasks due, any of them overdue) per day
+        let mut due_days: HashMap<Date, (usize, bool)> = HashMap::new();
+        for task in &self.task_list.task_list {
+            if let Some(due) = task.due_date {
+                let entry = due_days.entry(due).or_default();
+                entry.0 += 1;
+                entry.1 |= task.is_overdue(today);
+            }
+        }
+
+        let mut events =
+            CalendarEventStore::today(Style::default().add_modifier(Modifier::UNDERLINED));
+        for (date, (count, has_overdue)) in &due_days {
+            let style = match (count, has_overdue) {
+                (_, true) => OVERDUE_STYLE,
+                (1, _) => ONE_TASK_STYLE,
+                (2, _) => TWO_TASKS_STYLE,
+                _ => MANY_TASKS_STYLE,
+            };
+            events.add(*date, style);
+        }
+
+        let focused_style = events
+            .0
+            .get(&focused)
+            .copied()
+            .unwrap_or_default()
+            .add_modifier(Modifier::REVERSED);
+        events.add(focused, focused_style);
+
+        Monthly::new(focused, events)
+            .block(
+                Block::new()
+                    .title("Calendar")
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            )
+            .show_month_header(Style::default().add_modifier(Modifier::BOLD))
+            .show_weekdays_header(Style::default().fg(SLATE.c400))
+            .show_surrounding(Style::default().fg(SLATE.c600))
+            .render(area, buf);
+    }
+
+    fn render_focused_day(&mut self, area: Rect, buf: &mut Buffer) {
+        let focused = self.task_list.get_focused_date();
+        let day_label = focused
+            .format(format_description!(
+                "[weekday repr:short] [day padding:none] [month repr:short] [year]"
+            ))
+            .unwrap_or_else(|_| focused.to_string());
+
+        let items: Vec<ListItem> = self
+            .task_list
+            .get_tasks_due_on(focused)
+            .into_iter()
+            .map(|task| task.list_item(self.task_list.open_prerequisites(task).len()))
+            .collect();
+
+        let block = Block::new()
+            .title(format!("Due {} ({})", day_label, items.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded);
+
+        if items.is_empty() {
+            Paragraph::new("Nothing due on this day")
+                .block(block)
+                .centered()
+                .render(area, buf);
+            return;
+        }
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(SELECTED_STYLE)
+            .highlight_symbol(">>")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, area, buf, &mut self.task_list.state);
+    }
+}
+
+fn render_calendar_legend(area: Rect, buf: &mut Buffer) {
+    let legend = vec![
+        Line::from(vec![
+            Span::styled(" ■ ", ONE_TASK_STYLE),
+            Span::raw("1 task  "),
+            Span::styled("■ ", TWO_TASKS_STYLE),
+            Span::raw("2 tasks"),
+        ]),
+        Line::from(vec![
+            Span::styled(" ■ ", MANY_TASKS_STYLE),
+            Span::raw("3+ tasks "),
+            Span::styled("■ ", OVERDUE_STYLE),
+            Span::raw("overdue"),
+        ]),
+        Line::from(""),
+        Line::styled(" ←/→ day   j/k week", Style::default().fg(SLATE.c400)),
+        Line::styled(" [/] month {/} year", Style::default().fg(SLATE.c400)),
+        Line::styled(" t today   ↑/↓ task", Style::default().fg(SLATE.c400)),
+    ];
+
+    Paragraph::new(legend).render(area, buf);
+}
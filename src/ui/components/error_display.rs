@@ -18,10 +18,8 @@ impl App {
                 ErrorSeverity::Warning => {
                     self.render_warning_banner(area, buf);
                 }
-                ErrorSeverity::Error => {
-                    if self.error_state.should_show_popup() {
-                        self.render_error_popup(area, buf);
-                    }
+                ErrorSeverity::Error if self.error_state.should_show_popup() => {
+                    self.render_error_popup(area, buf);
                 }
                 _ => {}
             }
@@ -1,15 +1,17 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
+    style::{Color, Style},
+    text::Line,
     widgets::{Paragraph, Widget},
 };
 
-use crate::app::App;
+use crate::{app::App, utils::duration::format_clock};
 
 impl App {
     pub fn render_footer(&mut self, area: Rect, buf: &mut Buffer) {
-        let full_text = "Add Task: n | Previous: k | Next: j | Toggle Complete: <Enter> | Delete Task: d | Cancel: <esc> | Quit: q".to_string();
-        let short_text = "Add: n | Previous: k | Next: j | Toggle: <Enter> | Delete: d | Cancel: <esc> | Quit: q".to_string();
+        let full_text = "Add Task: n | Quick Add: a | Edit Task: e | Set Status: S | Dependencies: D | Timer: T | Time Log: M | Reminders: R | Pomodoro: P | Defer: z | Switch View: v | Previous: k | Next: j | Toggle Complete: <Enter> | Delete Task: d | Cancel: <esc> | Quit: q".to_string();
+        let short_text = "Add: n | Quick: a | Edit: e | Status: S | Timer: T | View: v | Previous: k | Next: j | Toggle: <Enter> | Delete: d | Cancel: <esc> | Quit: q".to_string();
         let tiny_text = "Add:n | Toggle:<Enter> | Quit:q".to_string();
 
         // Get the width of the footer area
@@ -24,6 +26,21 @@ impl App {
             tiny_text
         };
 
-        Paragraph::new(text).centered().render(area, buf);
+        let mut lines = vec![Line::from(text)];
+
+        if let Some(task) = self.task_list.running_timer()
+            && let Some(entry) = task.running_entry()
+        {
+            lines.push(Line::styled(
+                format!(
+                    "⏱ {} {} (stop: T)",
+                    task.title,
+                    format_clock(entry.duration(self.now))
+                ),
+                Style::default().fg(Color::LightGreen),
+            ));
+        }
+
+        Paragraph::new(lines).centered().render(area, buf);
     }
 }
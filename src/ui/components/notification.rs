@@ -0,0 +1,47 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+use crate::{app::App, core::error::ErrorSeverity, ui::get_warning_rect};
+
+impl App {
+    pub fn render_notification_banner(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(notification) = &self.notification else {
+            return;
+        };
+
+        let mut banner_area = get_warning_rect(40, 4, area);
+
+        // Sit below a warning banner rather than hiding it
+        let warning_shown = self
+            .error_state
+            .current_error
+            .as_ref()
+            .is_some_and(|error| error.severity() == ErrorSeverity::Warning);
+        if warning_shown {
+            banner_area.y = banner_area.y.saturating_add(3);
+        }
+        let banner_area = banner_area.intersection(area);
+
+        Clear.render(banner_area, buf);
+
+        Paragraph::new(notification.message.as_str())
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .title(Line::styled(
+                        "Reminder",
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .style(Style::default().bg(Color::Black).fg(Color::LightCyan)),
+            )
+            .style(Style::default().fg(Color::White))
+            .render(banner_area, buf);
+    }
+}
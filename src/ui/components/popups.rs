@@ -1,3 +1,11 @@
+pub mod close_confirm;
 pub mod delete_confirm;
+pub mod dependency_picker;
 pub mod help;
+pub mod pomodoro;
+pub mod quick_add;
+pub mod reminder_list;
+pub mod snooze_picker;
+pub mod status_picker;
 pub mod task_form;
+pub mod time_log;
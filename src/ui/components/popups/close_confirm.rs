@@ -0,0 +1,54 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+use crate::{app::App, core::task::task::Status, ui::get_center_rect};
+
+impl App {
+    /// Asks before completing or cancelling a task that other tasks depend on.
+    pub fn render_close_popup(&self, area: Rect, buf: &mut Buffer) {
+        let Some(status) = self.pending_close else {
+            return;
+        };
+        let Some(task) = self.task_list.get_selected_task() else {
+            return;
+        };
+
+        let verb = match status {
+            Status::Cancelled => "Cancel",
+            _ => "Complete",
+        };
+        let titles: Vec<&str> = self
+            .task_list
+            .dependents_of(task.id)
+            .into_iter()
+            .filter(|dependent| dependent.is_open())
+            .map(|dependent| dependent.title.as_str())
+            .collect();
+
+        let text = vec![
+            Line::from(format!("{} Task: {}?", verb, task.title)),
+            Line::from(format!("Needed by: {}", titles.join(", "))),
+            Line::from("[Y]es   [N]o"),
+        ];
+
+        let confirm_area = get_center_rect(48, 5, area);
+
+        Clear.render(confirm_area, buf);
+
+        let confirm_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .style(Style::default().fg(Color::Yellow).bg(Color::Black));
+
+        Paragraph::new(text)
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(confirm_block)
+            .wrap(Wrap { trim: true })
+            .render(confirm_area, buf);
+    }
+}
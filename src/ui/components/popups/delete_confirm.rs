@@ -3,7 +3,7 @@ use ratatui::{
     layout::Rect,
     style::{Color, Style},
     text::Line,
-    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget, Wrap},
 };
 
 use crate::{app::App, ui::get_center_rect};
@@ -14,7 +14,17 @@ impl App {
             return;
         }
         if let Some(task) = self.task_list.get_selected_task() {
-            let confirm_area = get_center_rect(32, 4, area);
+            let dependents = self.task_list.dependents_of(task.id);
+
+            let mut text = vec![Line::from(format!("Delete Task: {}?", task.title))];
+            if !dependents.is_empty() {
+                let titles: Vec<&str> = dependents.iter().map(|task| task.title.as_str()).collect();
+                text.push(Line::from(format!("Needed by: {}", titles.join(", "))));
+            }
+            text.push(Line::from("[Y]es   [N]o"));
+
+            let width = if dependents.is_empty() { 32 } else { 48 };
+            let confirm_area = get_center_rect(width, text.len() as u16 + 2, area);
 
             Clear.render(confirm_area, buf);
 
@@ -23,14 +33,10 @@ impl App {
                 .border_type(BorderType::Rounded)
                 .style(Style::default().fg(Color::Yellow).bg(Color::Black));
 
-            let text = vec![
-                Line::from(format!("Delete Task: {}?", task.title)),
-                Line::from("[Y]es   [N]o"),
-            ];
-
             Paragraph::new(text)
                 .alignment(ratatui::layout::Alignment::Center)
                 .block(confirm_block)
+                .wrap(Wrap { trim: true })
                 .render(confirm_area, buf);
         }
     }
@@ -0,0 +1,70 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style, palette::tailwind::SLATE},
+    text::Line,
+    widgets::{
+        Block, BorderType, Borders, Clear, HighlightSpacing, List, ListItem, Padding, Paragraph,
+        StatefulWidget, Widget, block::Position,
+    },
+};
+
+use crate::{app::App, ui::get_center_rect};
+
+const SELECTED_STYLE: Style = Style::new().bg(SLATE.c800).add_modifier(Modifier::BOLD);
+
+impl App {
+    pub fn render_dependency_picker(&mut self, area: Rect, buf: &mut Buffer) {
+        let popup_area = get_center_rect(60, 16, area);
+
+        Clear.render(popup_area, buf);
+
+        let title = self
+            .task_list
+            .get_selected_task()
+            .map(|task| format!("Depends On · {}", task.title))
+            .unwrap_or_else(|| "Depends On".to_string());
+
+        let popup_block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::symmetric(1, 0))
+            .style(Style::default().bg(Color::Black).fg(Color::White))
+            .title_bottom(
+                Line::from("<Space> to toggle, <Enter> to save, <Esc> to cancel")
+                    .alignment(Alignment::Center),
+            )
+            .title_position(Position::Top);
+
+        if self.dependency_picker.candidates.is_empty() {
+            Paragraph::new("No other tasks to depend on")
+                .block(popup_block)
+                .centered()
+                .render(popup_area, buf);
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .dependency_picker
+            .candidates
+            .iter()
+            .map(|(id, label)| {
+                let checkbox = if self.dependency_picker.is_chosen(*id) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                ListItem::new(format!("{} {}", checkbox, label))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(popup_block)
+            .highlight_style(SELECTED_STYLE)
+            .highlight_symbol(">>")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, popup_area, buf, &mut self.dependency_picker.state);
+    }
+}
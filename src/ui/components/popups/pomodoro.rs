@@ -0,0 +1,123 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Widget, block::Position},
+};
+
+use crate::{
+    app::App,
+    core::pomodoro::{Pomodoro, PomodoroPhase},
+    ui::get_center_rect,
+};
+
+/// Rows of each large digit, `#` marking a filled cell.
+const DIGITS: [[&str; 5]; 10] = [
+    ["###", "# #", "# #", "# #", "###"],
+    ["  #", "  #", "  #", "  #", "  #"],
+    ["###", "  #", "###", "#  ", "###"],
+    ["###", "  #", "###", "  #", "###"],
+    ["# #", "# #", "###", "  #", "  #"],
+    ["###", "#  ", "###", "  #", "###"],
+    ["###", "#  ", "###", "# #", "###"],
+    ["###", "  #", "  #", "  #", "  #"],
+    ["###", "# #", "###", "# #", "###"],
+    ["###", "# #", "###", "  #", "###"],
+];
+const COLON: [&str; 5] = [" ", "#", " ", "#", " "];
+
+impl App {
+    pub fn render_pomodoro(&self, area: Rect, buf: &mut Buffer) {
+        let Some(pomodoro) = &self.pomodoro else {
+            return;
+        };
+
+        let popup_area = get_center_rect(60, 14, area);
+
+        Clear.render(popup_area, buf);
+
+        let color = match pomodoro.phase {
+            PomodoroPhase::Work => Color::LightRed,
+            PomodoroPhase::ShortBreak | PomodoroPhase::LongBreak => Color::LightGreen,
+        };
+
+        let task_title = self
+            .task_list
+            .task_list
+            .iter()
+            .find(|task| task.id == pomodoro.task_id)
+            .map(|task| task.title.as_str())
+            .unwrap_or_default();
+
+        let hint = if pomodoro.paused {
+            "<Space> to resume, s to skip, <Esc> to stop"
+        } else {
+            "<Space> to pause, s to skip, <Esc> to stop"
+        };
+
+        let popup_block = Block::default()
+            .title(format!("Pomodoro · {}", task_title))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color))
+            .padding(Padding::symmetric(1, 1))
+            .style(Style::default().bg(Color::Black).fg(Color::White))
+            .title_bottom(Line::from(hint).alignment(Alignment::Center))
+            .title_position(Position::Top);
+
+        let inner_area = popup_block.inner(popup_area);
+        popup_block.render(popup_area, buf);
+
+        let [phase_area, countdown_area, count_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(6),
+            Constraint::Length(2),
+        ])
+        .areas(inner_area);
+
+        let phase = if pomodoro.paused {
+            format!("{} (paused)", pomodoro.phase.label())
+        } else {
+            pomodoro.phase.label().to_string()
+        };
+        Paragraph::new(Line::styled(
+            phase,
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ))
+        .centered()
+        .render(phase_area, buf);
+
+        Paragraph::new(countdown_lines(pomodoro))
+            .style(Style::default().fg(color))
+            .centered()
+            .render(countdown_area, buf);
+
+        Paragraph::new(format!("Finished this session: {}", pomodoro.completed))
+            .style(Style::default().fg(Color::DarkGray))
+            .centered()
+            .render(count_area, buf);
+    }
+}
+
+/// The remaining time as `MM:SS` in large block digits.
+fn countdown_lines(pomodoro: &Pomodoro) -> Vec<Line<'static>> {
+    let seconds = pomodoro.remaining().as_secs();
+    let text = format!("{:02}:{:02}", seconds / 60, seconds % 60);
+
+    (0..5)
+        .map(|row| {
+            let line: Vec<String> = text
+                .chars()
+                .map(|ch| {
+                    let cells = match ch.to_digit(10) {
+                        Some(digit) => DIGITS[digit as usize][row],
+                        None => COLON[row],
+                    };
+                    cells.replace('#', "██").replace(' ', "  ")
+                })
+                .collect();
+            Line::from(line.join("  "))
+        })
+        .collect()
+}
@@ -0,0 +1,64 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Widget, block::Position},
+};
+
+use crate::{app::App, ui::get_center_rect};
+
+impl App {
+    pub fn render_quick_add_popup(&mut self, area: Rect, buf: &mut Buffer) {
+        let popup_area = get_center_rect(90, 8, area);
+
+        Clear.render(popup_area, buf);
+
+        let popup_block = Block::default()
+            .style(Style::default().bg(Color::Black).fg(Color::White))
+            .padding(Padding::symmetric(1, 1))
+            .title("Press <Enter> to add, <Esc> to cancel")
+            .title_position(Position::Bottom)
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().fg(Color::White));
+
+        let inner_area = popup_block.inner(popup_area);
+
+        popup_block.render(popup_area, buf);
+
+        let [input_area, preview_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Length(1)]).areas(inner_area);
+
+        let border_style = match &self.quick_add.parsed {
+            Some(Err(_)) => Style::default().fg(Color::Red),
+            _ => Style::default().fg(Color::LightYellow),
+        };
+
+        self.quick_add.input.set_block(
+            Block::bordered()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(Line::from("Quick Add").left_aligned())
+                .border_style(border_style),
+        );
+        self.quick_add
+            .input
+            .set_cursor_style(Style::default().bg(Color::White));
+        self.quick_add.input.render(input_area, buf);
+
+        let preview = match &self.quick_add.parsed {
+            Some(Ok(quick_add)) => {
+                Line::styled(quick_add.summary(), Style::default().fg(Color::LightGreen))
+            }
+            Some(Err(app_error)) => {
+                Line::styled(app_error.user_message(), Style::default().fg(Color::Red))
+            }
+            None => Line::styled(
+                "e.g. Write report due:fri +work #urgent !high",
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+
+        Paragraph::new(preview).render(preview_area, buf);
+    }
+}
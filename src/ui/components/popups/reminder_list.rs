@@ -0,0 +1,108 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, palette::tailwind::SLATE},
+    text::Line,
+    widgets::{
+        Block, BorderType, Borders, Clear, HighlightSpacing, List, ListItem, Padding, Paragraph,
+        StatefulWidget, Widget, block::Position,
+    },
+};
+use time::macros::format_description;
+
+use crate::{app::App, ui::get_center_rect, utils::config::Config};
+
+const SELECTED_STYLE: Style = Style::new().bg(SLATE.c800).add_modifier(Modifier::BOLD);
+
+impl App {
+    pub fn render_reminder_list(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(task) = self.task_list.get_selected_task() else {
+            return;
+        };
+
+        let popup_area = get_center_rect(56, 14, area);
+
+        Clear.render(popup_area, buf);
+
+        let hint = if self.reminder_list.adding.is_some() {
+            "<Enter> to add, <Esc> to go back"
+        } else {
+            "a: add, d: delete, <Esc>: close"
+        };
+
+        let popup_block = Block::default()
+            .title(format!("Reminders · {}", task.title))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::symmetric(1, 0))
+            .style(Style::default().bg(Color::Black).fg(Color::White))
+            .title_bottom(Line::from(hint).alignment(Alignment::Center))
+            .title_position(Position::Top);
+
+        let inner_area = popup_block.inner(popup_area);
+        popup_block.render(popup_area, buf);
+
+        let [reminders_area, input_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(3)]).areas(inner_area);
+
+        let due_time = Config::global().reminders.due_time();
+
+        let items: Vec<ListItem> = task
+            .reminders
+            .iter()
+            .map(|reminder| {
+                let at = task
+                    .reminder_at(reminder, due_time)
+                    .and_then(|at| {
+                        at.format(format_description!("[year]-[month]-[day] [hour]:[minute]"))
+                            .ok()
+                    })
+                    .unwrap_or_else(|| "no due date".to_string());
+                let text = format!("{:<18} {}", reminder.time.to_string(), at);
+
+                if reminder.fired {
+                    ListItem::new(Line::styled(
+                        format!("{} (done)", text),
+                        Style::default().fg(Color::DarkGray),
+                    ))
+                } else {
+                    ListItem::new(text)
+                }
+            })
+            .collect();
+
+        if items.is_empty() {
+            Paragraph::new("No reminders yet. Press a to add one.")
+                .style(Style::default().fg(Color::DarkGray))
+                .render(reminders_area, buf);
+        } else {
+            let list = List::new(items)
+                .highlight_style(SELECTED_STYLE)
+                .highlight_symbol(">>")
+                .highlight_spacing(HighlightSpacing::Always);
+
+            StatefulWidget::render(list, reminders_area, buf, &mut self.reminder_list.state);
+        }
+
+        if let Some(adding) = &mut self.reminder_list.adding {
+            let (title, border_style) = match &adding.error {
+                Some(error) => (error.user_message(), Style::default().fg(Color::Red)),
+                None => (
+                    "e.g. 30m before, 1d before, 14:00 or tomorrow 9:00".to_string(),
+                    Style::default().fg(Color::LightYellow),
+                ),
+            };
+
+            adding.input.set_block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .title(title)
+                    .border_style(border_style),
+            );
+            adding
+                .input
+                .set_cursor_style(Style::default().bg(Color::White));
+            adding.input.render(input_area, buf);
+        }
+    }
+}
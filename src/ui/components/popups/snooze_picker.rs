@@ -0,0 +1,121 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{
+        Block, BorderType, Borders, Clear, Padding, Paragraph, Widget,
+        block::Position,
+        calendar::{CalendarEventStore, Monthly},
+    },
+};
+use strum::IntoEnumIterator;
+use time::macros::format_description;
+
+use crate::{
+    app::App,
+    ui::{forms::snooze_picker::SnoozePreset, get_center_rect},
+    utils::date::get_today_with_fallbacks,
+};
+
+impl App {
+    pub fn render_snooze_picker(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(base) = self.snooze_picker.base else {
+            return;
+        };
+
+        let height = if self.snooze_picker.picking.is_some() {
+            19
+        } else {
+            9
+        };
+        let popup_area = get_center_rect(44, height, area);
+
+        Clear.render(popup_area, buf);
+
+        let hint = if self.snooze_picker.picking.is_some() {
+            "<Enter> to defer, <Esc> to go back"
+        } else {
+            "<Enter> to defer, <Esc> to cancel"
+        };
+
+        let popup_block = Block::default()
+            .title("Defer Until")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::symmetric(1, 0))
+            .style(Style::default().bg(Color::Black).fg(Color::White))
+            .title_bottom(Line::from(hint).alignment(Alignment::Center))
+            .title_position(Position::Top);
+
+        let inner_area = popup_block.inner(popup_area);
+        popup_block.render(popup_area, buf);
+
+        let [options_area, calendar_area] =
+            Layout::vertical([Constraint::Length(6), Constraint::Fill(1)]).areas(inner_area);
+        let [_, calendar_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(24),
+            Constraint::Fill(1),
+        ])
+        .areas(calendar_area);
+
+        let options: Vec<Line> = SnoozePreset::iter()
+            .enumerate()
+            .map(|(index, preset)| {
+                let is_selected = index == self.snooze_picker.selected;
+                let marker = if is_selected { ">>" } else { "  " };
+                let date = preset
+                    .date(base)
+                    .and_then(|date| {
+                        date.format(format_description!(
+                            "[weekday repr:short] [day padding:none] [month repr:short]"
+                        ))
+                        .ok()
+                    })
+                    .map(|date| format!(" ({})", date))
+                    .unwrap_or_default();
+
+                let style = if is_selected {
+                    Style::default()
+                        .fg(Color::LightYellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+
+                Line::styled(
+                    format!("{} {} {}{}", marker, index + 1, preset.label(), date),
+                    style,
+                )
+            })
+            .collect();
+
+        Paragraph::new(options).render(options_area, buf);
+
+        if let Some(date) = self.snooze_picker.picking {
+            let mut events = CalendarEventStore::default();
+            events.add(
+                get_today_with_fallbacks().0,
+                Style::default().add_modifier(Modifier::UNDERLINED),
+            );
+            events.add(
+                date,
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::REVERSED),
+            );
+
+            Monthly::new(date, events)
+                .block(
+                    Block::new()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(Color::LightYellow)),
+                )
+                .show_month_header(Style::default())
+                .show_weekdays_header(Style::default())
+                .render(calendar_area, buf);
+        }
+    }
+}
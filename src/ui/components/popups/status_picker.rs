@@ -0,0 +1,79 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Widget, block::Position},
+};
+use strum::IntoEnumIterator;
+
+use crate::{app::App, core::task::task::Status, ui::get_center_rect};
+
+impl App {
+    pub fn render_status_picker(&mut self, area: Rect, buf: &mut Buffer) {
+        let popup_area = get_center_rect(44, 13, area);
+
+        Clear.render(popup_area, buf);
+
+        let hint = if self.status_picker.entering_reason {
+            "<Enter> to block, <Esc> to go back"
+        } else {
+            "<Enter> to set, <Esc> to cancel"
+        };
+
+        let popup_block = Block::default()
+            .title("Set Status")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::symmetric(1, 0))
+            .style(Style::default().bg(Color::Black).fg(Color::White))
+            .title_bottom(Line::from(hint).alignment(Alignment::Center))
+            .title_position(Position::Top);
+
+        let inner_area = popup_block.inner(popup_area);
+        popup_block.render(popup_area, buf);
+
+        let [options_area, reason_area] =
+            Layout::vertical([Constraint::Length(6), Constraint::Length(3)]).areas(inner_area);
+
+        let options: Vec<Line> = Status::iter()
+            .enumerate()
+            .map(|(index, status)| {
+                let is_selected = index == self.status_picker.selected;
+                let is_current = self.status_picker.current == Some(status);
+                let marker = if is_selected { ">>" } else { "  " };
+                let current = if is_current { " (current)" } else { "" };
+
+                let style = if !self.status_picker.is_allowed(status) {
+                    Style::default().fg(Color::DarkGray)
+                } else if is_selected {
+                    Style::default()
+                        .fg(Color::LightYellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+
+                Line::styled(
+                    format!("{} {} {}{}", marker, index + 1, status.label(), current),
+                    style,
+                )
+            })
+            .collect();
+
+        Paragraph::new(options).render(options_area, buf);
+
+        if self.status_picker.entering_reason {
+            self.status_picker.reason.set_block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .title("Blocked by (optional)")
+                    .border_style(Style::default().fg(Color::LightYellow)),
+            );
+            self.status_picker
+                .reason
+                .set_cursor_style(Style::default().bg(Color::White));
+            self.status_picker.reason.render(reason_area, buf);
+        }
+    }
+}
@@ -14,7 +14,7 @@ use tui_textarea::TextArea;
 
 impl App {
     pub fn render_add_task_popup(&mut self, area: Rect, buf: &mut Buffer) {
-        let popup_area = get_center_rect(90, 20, area);
+        let popup_area = get_center_rect(90, 23, area);
 
         Clear.render(popup_area, buf);
 
@@ -30,7 +30,8 @@ impl App {
 
         popup_block.render(popup_area, buf);
 
-        let [title_area, due_date_area, description_area] = Layout::vertical([
+        let [title_area, due_date_area, estimate_area, description_area] = Layout::vertical([
+            Constraint::Length(3),
             Constraint::Length(3),
             Constraint::Length(3),
             Constraint::Fill(1),
@@ -39,6 +40,13 @@ impl App {
 
         self.render_popup_form_textarea(title_area, buf, "Title".to_string(), FormField::Title);
 
+        self.render_popup_form_textarea(
+            estimate_area,
+            buf,
+            "Estimate (e.g. 45m, 1h30m, 3pt)".to_string(),
+            FormField::Estimate,
+        );
+
         self.render_popup_form_textarea(
             description_area,
             buf,
@@ -0,0 +1,120 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, palette::tailwind::SLATE},
+    text::Line,
+    widgets::{
+        Block, BorderType, Borders, Clear, HighlightSpacing, List, ListItem, Padding, Paragraph,
+        StatefulWidget, Widget, block::Position,
+    },
+};
+use time::macros::format_description;
+
+use crate::{
+    app::App,
+    ui::get_center_rect,
+    utils::duration::{format_clock, format_duration},
+};
+
+const SELECTED_STYLE: Style = Style::new().bg(SLATE.c800).add_modifier(Modifier::BOLD);
+
+impl App {
+    pub fn render_time_log(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(task) = self.task_list.get_selected_task() else {
+            return;
+        };
+
+        let popup_area = get_center_rect(56, 16, area);
+
+        Clear.render(popup_area, buf);
+
+        let hint = if self.time_log.editing.is_some() {
+            "<Enter> to save, <Esc> to go back"
+        } else {
+            "a: add, e: edit, d: delete, <Esc>: close"
+        };
+
+        let popup_block = Block::default()
+            .title(format!("Time Log · {}", task.title))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::symmetric(1, 0))
+            .style(Style::default().bg(Color::Black).fg(Color::White))
+            .title_bottom(Line::from(hint).alignment(Alignment::Center))
+            .title_position(Position::Top);
+
+        let inner_area = popup_block.inner(popup_area);
+        popup_block.render(popup_area, buf);
+
+        let [entries_area, total_area, input_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(3),
+        ])
+        .areas(inner_area);
+
+        let now = self.now;
+
+        let items: Vec<ListItem> = task
+            .time_entries
+            .iter()
+            .map(|entry| {
+                let started = entry
+                    .started_at
+                    .format(format_description!("[year]-[month]-[day] [hour]:[minute]"))
+                    .unwrap_or_default();
+
+                if entry.is_running() {
+                    ListItem::new(Line::styled(
+                        format!("{}  running {}", started, format_clock(entry.duration(now))),
+                        Style::default().fg(Color::LightGreen),
+                    ))
+                } else {
+                    ListItem::new(format!(
+                        "{}  {}",
+                        started,
+                        format_duration(entry.duration(now))
+                    ))
+                }
+            })
+            .collect();
+
+        if items.is_empty() {
+            Paragraph::new("No time tracked yet. Press a to add an entry.")
+                .style(Style::default().fg(Color::DarkGray))
+                .render(entries_area, buf);
+        } else {
+            let list = List::new(items)
+                .highlight_style(SELECTED_STYLE)
+                .highlight_symbol(">>")
+                .highlight_spacing(HighlightSpacing::Always);
+
+            StatefulWidget::render(list, entries_area, buf, &mut self.time_log.state);
+        }
+
+        Paragraph::new(format!("Total {}", format_duration(task.tracked_time(now))))
+            .style(Style::default().fg(Color::LightYellow))
+            .render(total_area, buf);
+
+        if let Some(editing) = &mut self.time_log.editing {
+            let (title, border_style) = match &editing.error {
+                Some(error) => (error.user_message(), Style::default().fg(Color::Red)),
+                None => (
+                    "Duration, e.g. 45, 1h30m or 1:30".to_string(),
+                    Style::default().fg(Color::LightYellow),
+                ),
+            };
+
+            editing.input.set_block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .title(title)
+                    .border_style(border_style),
+            );
+            editing
+                .input
+                .set_cursor_style(Style::default().bg(Color::White));
+            editing.input.render(input_area, buf);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, BorderType, Borders, Paragraph, Sparkline, Widget},
+};
+use time::macros::format_description;
+
+use crate::{
+    app::App,
+    core::task::stats::{Breakdown, DAYS_SHOWN, TREND_DAYS, WEEKS_SHOWN},
+};
+
+const LABEL_STYLE: Style = Style::new().fg(Color::DarkGray);
+const VALUE_STYLE: Style = Style::new()
+    .fg(Color::LightYellow)
+    .add_modifier(Modifier::BOLD);
+
+impl App {
+    pub fn render_stats(&mut self, area: Rect, buf: &mut Buffer) {
+        let stats = self.task_list.get_statistics();
+
+        let [summary_area, charts_area, trend_area, breakdown_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(5),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        let lead_time = match stats.average_lead_time {
+            Some(days) => format!("{:.1} days", days),
+            None => "-".to_string(),
+        };
+        let streak = match stats.streak {
+            1 => "1 day".to_string(),
+            days => format!("{} days", days),
+        };
+
+        let summary = Line::from(vec![
+            Span::styled("Completed ", LABEL_STYLE),
+            Span::styled(stats.total_completed.to_string(), VALUE_STYLE),
+            Span::styled("   Open ", LABEL_STYLE),
+            Span::styled(stats.total_open.to_string(), VALUE_STYLE),
+            Span::styled("   Average lead time ", LABEL_STYLE),
+            Span::styled(lead_time, VALUE_STYLE),
+            Span::styled("   Streak ", LABEL_STYLE),
+            Span::styled(streak, VALUE_STYLE),
+        ]);
+
+        Paragraph::new(summary)
+            .block(bordered_block("Statistics"))
+            .render(summary_area, buf);
+
+        let [daily_area, weekly_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(charts_area);
+
+        let daily: Vec<u64> = stats.completed_per_day.iter().map(|(_, n)| *n).collect();
+        let daily_max = daily.iter().copied().max().unwrap_or(0);
+        let daily = stretch(&daily, daily_area.width.saturating_sub(2));
+        Sparkline::default()
+            .block(bordered_block(&format!(
+                "Completed per day · last {} days · best {}",
+                DAYS_SHOWN, daily_max
+            )))
+            .data(&daily)
+            .max(daily_max.max(1))
+            .style(Style::default().fg(Color::LightGreen))
+            .render(daily_area, buf);
+
+        let bars: Vec<Bar> = stats
+            .completed_per_week
+            .iter()
+            .map(|(monday, count)| {
+                let label = monday
+                    .format(format_description!("[day padding:none] [month repr:short]"))
+                    .unwrap_or_default();
+                Bar::default().value(*count).label(Line::from(label))
+            })
+            .collect();
+        BarChart::default()
+            .block(bordered_block(&format!(
+                "Completed per week · last {} weeks",
+                WEEKS_SHOWN
+            )))
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(6)
+            .bar_gap(1)
+            .bar_style(Style::default().fg(Color::LightBlue))
+            .value_style(Style::default().fg(Color::Black).bg(Color::LightBlue))
+            .render(weekly_area, buf);
+
+        let overdue: Vec<u64> = stats.overdue_trend.iter().map(|(_, n)| *n).collect();
+        let overdue_now = overdue.last().copied().unwrap_or(0);
+        let overdue_before = overdue.first().copied().unwrap_or(0);
+        let overdue = stretch(&overdue, trend_area.width.saturating_sub(2));
+        let direction = match overdue_now.cmp(&overdue_before) {
+            std::cmp::Ordering::Less => "falling",
+            std::cmp::Ordering::Equal => "steady",
+            std::cmp::Ordering::Greater => "rising",
+        };
+        Sparkline::default()
+            .block(bordered_block(&format!(
+                "Overdue · last {} days · {} now, {}",
+                TREND_DAYS, overdue_now, direction
+            )))
+            .data(&overdue)
+            .max(overdue.iter().copied().max().unwrap_or(0).max(1))
+            .style(Style::default().fg(Color::LightRed))
+            .render(trend_area, buf);
+
+        let [group_area, tag_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(breakdown_area);
+
+        render_breakdown("By group", &stats.by_group, group_area, buf);
+        render_breakdown("By tag", &stats.by_tag, tag_area, buf);
+    }
+}
+
+/// Repeats each point so the series spans `width` columns of a sparkline.
+fn stretch(data: &[u64], width: u16) -> Vec<u64> {
+    let repeat = (usize::from(width) / data.len().max(1)).max(1);
+    data.iter()
+        .flat_map(|&value| std::iter::repeat_n(value, repeat))
+        .collect()
+}
+
+fn bordered_block(title: &str) -> Block<'static> {
+    Block::new()
+        .title(title.to_string())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+}
+
+/// One line per name with a bar of its share of completed tasks.
+fn render_breakdown(title: &str, rows: &[Breakdown], area: Rect, buf: &mut Buffer) {
+    let block = bordered_block(title);
+
+    if rows.is_empty() {
+        Paragraph::new(Line::styled("Nothing to show yet", LABEL_STYLE))
+            .block(block)
+            .render(area, buf);
+        return;
+    }
+
+    let name_width = rows
+        .iter()
+        .map(|row| row.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(16);
+
+    let lines: Vec<Line> = rows
+        .iter()
+        .map(|row| {
+            let total = row.completed + row.open;
+            let filled = (row.completed * 10).checked_div(total).unwrap_or(0);
+            let name: String = row.name.chars().take(name_width).collect();
+
+            Line::from(vec![
+                Span::raw(format!("{:<width$} ", name, width = name_width)),
+                Span::styled("█".repeat(filled), Style::default().fg(Color::LightGreen)),
+                Span::styled("░".repeat(10 - filled), LABEL_STYLE),
+                Span::styled(
+                    format!(" {} done, {} open", row.completed, row.open),
+                    LABEL_STYLE,
+                ),
+            ])
+        })
+        .collect();
+
+    Paragraph::new(lines).block(block).render(area, buf);
+}
@@ -7,7 +7,14 @@ use ratatui::{
 };
 use time::macros::format_description;
 
-use crate::{app::App, core::task::task::Status};
+use crate::{
+    app::App,
+    core::task::{Task, task::Status},
+    utils::duration::format_duration,
+};
+
+const MUTED_STYLE: Style = Style::new().fg(Color::DarkGray);
+const TRACKED_STYLE: Style = Style::new().fg(Color::LightGreen);
 
 impl App {
     pub fn render_detail(&mut self, area: Rect, buf: &mut Buffer) {
@@ -20,7 +27,7 @@ impl App {
 
         let [title_area, due_date_area, description_area] = Layout::vertical([
             Constraint::Length(3),
-            Constraint::Length(2),
+            Constraint::Length(4),
             Constraint::Fill(1),
         ])
         .areas(detail_area);
@@ -28,32 +35,138 @@ impl App {
         block.render(area, buf);
 
         if let Some(task) = self.task_list.get_selected_task() {
-            let status = if task.status == Status::Todo {
-                Line::from("Todo").style(
-                    Style::default()
-                        .fg(Color::LightGreen)
-                        .add_modifier(Modifier::ITALIC),
-                )
-            } else {
-                Line::from("Complete").style(
-                    Style::default()
-                        .fg(Color::Red)
-                        .add_modifier(Modifier::ITALIC),
-                )
+            let status_color = match task.status {
+                Status::Todo => Color::LightGreen,
+                Status::InProgress => Color::LightCyan,
+                Status::Blocked => Color::LightYellow,
+                Status::Completed => Color::Red,
+                Status::Cancelled => Color::DarkGray,
             };
+            let status_label = match (&task.status, &task.blocked_reason) {
+                (Status::Blocked, Some(reason)) => format!("Blocked: {}", reason),
+                (status, _) => status.label().to_string(),
+            };
+            let status = Line::from(status_label).style(
+                Style::default()
+                    .fg(status_color)
+                    .add_modifier(Modifier::ITALIC),
+            );
             let title = vec![status, Line::from(task.title.clone())];
             let due_date = task
                 .due_date
-                .unwrap()
-                .format(format_description!("[year]-[month]-[day]"))
-                .unwrap_or_else(|_| "No Due Date".to_string());
+                .and_then(|date| {
+                    date.format(format_description!("[year]-[month]-[day]"))
+                        .ok()
+                })
+                .unwrap_or_else(|| "No Due Date".to_string());
+
+            let mut metadata = Vec::new();
+            if let Some(group) = &task.group {
+                metadata.push(format!("+{}", group));
+            }
+            metadata.extend(task.tags.iter().map(|tag| format!("#{}", tag)));
+            if let Some(priority) = task.priority {
+                metadata.push(format!("!{}", priority));
+            }
+            let due_date = match task.defer_until {
+                Some(defer_until) => format!(
+                    "{} · deferred until {}",
+                    due_date,
+                    defer_until
+                        .format(format_description!("[year]-[month]-[day]"))
+                        .unwrap_or_default()
+                ),
+                None => due_date,
+            };
+            let mut due_date = vec![Line::from(due_date), Line::from(metadata.join(" "))];
+
+            if let Some(estimate) = task.estimate {
+                let mut effort = format!("Estimate {}", estimate);
+                if let Some(group) = &task.group {
+                    let group_effort = self.task_list.group_effort(group);
+                    if !group_effort.is_empty() {
+                        effort.push_str(&format!(" · +{} {}", group, group_effort));
+                    }
+                }
+                due_date.push(Line::styled(effort, MUTED_STYLE));
+            }
 
-            let description: Vec<Line> = task
+            let mut progress = Vec::new();
+            if !task.time_entries.is_empty() {
+                let tracked = task.tracked_time(self.now);
+                progress.push(format!("Tracked {}", format_duration(tracked)));
+                if let Some(group) = &task.group {
+                    let group_total = self.task_list.group_tracked_time(group, self.now);
+                    progress.push(format!("+{} {}", group, format_duration(group_total)));
+                }
+            }
+            match task.pomodoros {
+                0 => {}
+                1 => progress.push("1 pomodoro".to_string()),
+                count => progress.push(format!("{} pomodoros", count)),
+            }
+            if !progress.is_empty() {
+                due_date.push(Line::styled(progress.join(" · "), TRACKED_STYLE));
+            }
+
+            let mut description: Vec<Line> = task
                 .description
                 .iter()
                 .map(|line| Line::from(line.as_str()))
                 .collect();
 
+            let prerequisites: Vec<&Task> = task
+                .depends_on
+                .iter()
+                .filter_map(|id| {
+                    self.task_list
+                        .task_list
+                        .iter()
+                        .find(|other| other.id == *id)
+                })
+                .collect();
+            let dependents = self.task_list.dependents_of(task.id);
+
+            for (heading, related) in [("Depends on", prerequisites), ("Needed by", dependents)] {
+                if related.is_empty() {
+                    continue;
+                }
+                description.push(Line::from(""));
+                description.push(Line::styled(heading, MUTED_STYLE));
+                description.extend(
+                    related
+                        .iter()
+                        .map(|other| Line::from(format!("{} {}", other.glyph(), other.title))),
+                );
+            }
+
+            let pending_reminders: Vec<_> = task
+                .reminders
+                .iter()
+                .filter(|reminder| !reminder.fired)
+                .collect();
+            if !pending_reminders.is_empty() {
+                description.push(Line::from(""));
+                description.push(Line::styled("Reminders", MUTED_STYLE));
+                description.extend(
+                    pending_reminders
+                        .iter()
+                        .map(|reminder| Line::from(format!("⏰ {}", reminder.time))),
+                );
+            }
+
+            if !task.status_changes.is_empty() {
+                description.push(Line::from(""));
+                description.push(Line::styled("History", MUTED_STYLE));
+                description.extend(task.status_changes.iter().rev().map(|change| {
+                    let at = change
+                        .at
+                        .format(format_description!("[year]-[month]-[day] [hour]:[minute]"))
+                        .unwrap_or_default();
+                    Line::styled(format!("{}  {}", at, change.status.label()), MUTED_STYLE)
+                }));
+            }
+
             Paragraph::new(title)
                 .block(Block::default())
                 .style(Style::default().fg(Color::White))
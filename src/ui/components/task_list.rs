@@ -1,35 +1,57 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Modifier, Style, palette::tailwind::SLATE},
-    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph, StatefulWidget, Widget},
+    style::{Color, Modifier, Style, palette::tailwind::SLATE},
+    text::Line,
+    widgets::{
+        Block, BorderType, Borders, List, ListItem, Paragraph, StatefulWidget, Tabs, Widget,
+    },
 };
 
-use crate::app::App;
+use crate::{app::App, core::task::SmartView};
 
 const SELECTED_STYLE: Style = Style::new().bg(SLATE.c800).add_modifier(Modifier::BOLD);
 
 impl App {
     pub fn render_task_list(&mut self, area: Rect, buf: &mut Buffer) {
-        let block = Block::new()
-            .title("Task List")
+        let deferred = match (
+            self.task_list.deferred_count(),
+            self.task_list.show_deferred,
+        ) {
+            (0, _) => String::new(),
+            (count, true) => format!(" · {} deferred shown", count),
+            (count, false) => format!(" · {} deferred hidden", count),
+        };
+
+        let mut block = Block::new()
+            .title(format!("Task List · {}{}", self.task_list.view, deferred))
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded);
 
+        let effort = self.task_list.view_effort();
+        if !effort.is_empty() {
+            block = block.title_bottom(Line::from(format!(" {} ", effort)).right_aligned());
+        }
+
+        // Iterate through all elements in the `items` and stylize them.
+        let items: Vec<ListItem> = self
+            .task_list
+            .get_sorted_tasks()
+            .into_iter()
+            .map(|task| task.list_item(self.task_list.open_prerequisites(task).len()))
+            .collect();
+
         if self.task_list.task_list.is_empty() {
             Paragraph::new("Press n to add new task!")
                 .block(block)
                 .centered()
                 .render(area, buf);
+        } else if items.is_empty() {
+            Paragraph::new("No tasks in this view")
+                .block(block)
+                .centered()
+                .render(area, buf);
         } else {
-            // Iterate through all elements in the `items` and stylize them.
-            let items: Vec<ListItem> = self
-                .task_list
-                .get_sorted_tasks()
-                .into_iter()
-                .map(ListItem::from)
-                .collect();
-
             let list = List::new(items)
                 .block(block)
                 .highlight_style(SELECTED_STYLE)
@@ -39,4 +61,31 @@ impl App {
             StatefulWidget::render(list, area, buf, &mut self.task_list.state);
         }
     }
+
+    pub fn render_view_tabs(&self, area: Rect, buf: &mut Buffer) {
+        let counts = self.task_list.view_counts();
+
+        let titles: Vec<Line> = counts
+            .iter()
+            .map(|(view, count)| {
+                let style = if *view == SmartView::Overdue && *count > 0 {
+                    Style::default().fg(Color::LightRed)
+                } else {
+                    Style::default()
+                };
+                Line::styled(format!("{} {} ({})", view.key(), view, count), style)
+            })
+            .collect();
+
+        let selected = counts
+            .iter()
+            .position(|(view, _)| *view == self.task_list.view)
+            .unwrap_or(0);
+
+        Tabs::new(titles)
+            .select(selected)
+            .highlight_style(SELECTED_STYLE)
+            .divider("|")
+            .render(area, buf);
+    }
 }
@@ -1,3 +1,9 @@
 pub mod date_input;
+pub mod dependency_picker;
 pub mod form_field;
+pub mod quick_add;
+pub mod reminder_list;
+pub mod snooze_picker;
+pub mod status_picker;
 pub mod task_form;
+pub mod time_log;
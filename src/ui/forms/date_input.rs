@@ -9,13 +9,13 @@ use ratatui::{
         calendar::{CalendarEventStore, Monthly},
     },
 };
-use time::{Date, Duration, Month, OffsetDateTime, macros::format_description};
+use time::{Date, Duration, macros::format_description};
 use tui_textarea::TextArea;
 
 use crate::{
     core::{error::AppResult, validation::DateValidator},
     ui::get_center_rect,
-    utils::date::get_today_with_fallbacks,
+    utils::date::{add_months, get_today_with_fallbacks},
 };
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -31,6 +31,8 @@ pub struct DateInput {
     pub input_mode: DateInputMode,
     pub date_format: &'static str,
     pub error_message: Option<String>,
+    /// The date the edited task already had; keeping it is always valid.
+    pub original_date: Option<Date>,
 }
 
 impl Default for DateInput {
@@ -50,6 +52,7 @@ impl Default for DateInput {
             input_mode: DateInputMode::Text,
             date_format: "[year]-[month]-[day]",
             error_message: warning,
+            original_date: None,
         }
     }
 }
@@ -59,6 +62,27 @@ impl DateInput {
         DateInput::default()
     }
 
+    /// A date input pre-filled with an existing task's due date, or left blank
+    /// for a task without one.
+    pub fn from_date(date: Option<Date>) -> Self {
+        let mut date_input = DateInput {
+            input: TextArea::default(),
+            ..DateInput::default()
+        };
+
+        if let Some(date) = date {
+            date_input.input.insert_str(
+                date.format(format_description!("[year]-[month]-[day]"))
+                    .unwrap_or_default(),
+            );
+            date_input.selected_date = date;
+            date_input.original_date = Some(date);
+            date_input.error_message = None;
+        }
+
+        date_input
+    }
+
     pub fn handle_input(&mut self, key: KeyEvent) {
         match self.input_mode {
             DateInputMode::Calendar => self.handle_calendar_input(key),
@@ -73,24 +97,67 @@ impl DateInput {
         }
     }
 
-    fn validate_current_input(&self) -> AppResult<Date> {
-        DateValidator::validate_date_input(&self.input.lines()[0])
+    /// The typed date, or `None` when the field is left blank.
+    pub fn validate_current_input(&self) -> AppResult<Option<Date>> {
+        let text = &self.input.lines()[0];
+        if text.trim().is_empty() {
+            return Ok(None);
+        }
+        DateValidator::validate_date_input_against(text, self.original_date).map(Some)
+    }
+
+    /// The due date the form holds, `None` when the field is blank.
+    pub fn due_date(&self) -> Option<Date> {
+        if self.input.lines()[0].trim().is_empty() {
+            None
+        } else {
+            Some(self.selected_date)
+        }
     }
 
     fn handle_text_input(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Char('c' | 'C') if key.modifiers == KeyModifiers::CONTROL => {
                 self.input_mode = DateInputMode::Calendar;
-                if let Ok(date) = self.validate_current_input() {
+                if let Ok(Some(date)) = self.validate_current_input() {
                     self.selected_date = date;
                 }
             }
             _ => {
                 self.input.input(key);
+                self.update_preview();
             }
         }
     }
 
+    /// Re-resolves the typed text so the title can preview the date it maps to.
+    pub fn update_preview(&mut self) {
+        match self.validate_current_input() {
+            Ok(date) => {
+                if let Some(date) = date {
+                    self.selected_date = date;
+                }
+                self.error_message = None;
+            }
+            Err(app_error) => {
+                self.error_message = Some(app_error.user_message());
+            }
+        }
+    }
+
+    fn preview_line(&self) -> Line<'static> {
+        let preview = match self.due_date() {
+            Some(date) => date
+                .format(format_description!(
+                    "[weekday repr:short], [year]-[month]-[day]"
+                ))
+                .unwrap_or_default(),
+            None => "no due date".to_string(),
+        };
+
+        Line::from(format!("→ {}", preview)).right_aligned()
+    }
+
     fn handle_calendar_input(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc => {
@@ -104,102 +171,13 @@ impl DateInput {
                 self.input = TextArea::default();
                 self.input.insert_str(&date_str);
                 self.input_mode = DateInputMode::Text;
+                self.error_message = None;
             }
-            KeyCode::Char('h') => {
-                self.selected_date = self
-                    .selected_date
-                    .previous_day()
-                    .unwrap_or(self.selected_date);
-            }
-            KeyCode::Char('l') => {
-                self.selected_date = self.selected_date.next_day().unwrap_or(self.selected_date);
-            }
-            KeyCode::Char('j') => {
-                self.selected_date = self
-                    .selected_date
-                    .checked_add(Duration::days(7))
-                    .unwrap_or(self.selected_date);
-            }
-            KeyCode::Char('k') => {
-                self.selected_date = self
-                    .selected_date
-                    .checked_sub(Duration::days(7))
-                    .unwrap_or(self.selected_date);
-            }
-
-            KeyCode::Char('p') => {
-                let month_num = u8::from(self.selected_date.month());
-
-                if month_num > 1 {
-                    if let Ok(month) = Month::try_from(month_num - 1) {
-                        let year = self.selected_date.year();
-                        let day =
-                            u8::min(self.selected_date.day(), days_in_month(year, month_num - 1));
-                        if let Ok(date) = Date::from_calendar_date(year, month, day) {
-                            self.selected_date = date;
-                        }
-                    }
-                } else if let Ok(date) = Date::from_calendar_date(
-                    self.selected_date.year() - 1,
-                    Month::December,
-                    self.selected_date.day().min(31),
-                ) {
-                    self.selected_date = date;
-                }
-            }
-
-            KeyCode::Char('n') => {
-                let month_num = u8::from(self.selected_date.month());
-
-                if month_num < 12 {
-                    if let Ok(month) = Month::try_from(month_num + 1) {
-                        let year = self.selected_date.year();
-                        let day =
-                            u8::min(self.selected_date.day(), days_in_month(year, month_num + 1));
-                        if let Ok(date) = Date::from_calendar_date(year, month, day) {
-                            self.selected_date = date;
-                        }
-                    }
-                } else if let Ok(date) = Date::from_calendar_date(
-                    self.selected_date.year() + 1,
-                    Month::January,
-                    self.selected_date.day().min(31),
-                ) {
+            code => {
+                if let Some(date) = navigate_calendar(self.selected_date, code) {
                     self.selected_date = date;
                 }
             }
-
-            KeyCode::Char('N') => {
-                let year = self.selected_date.year() + 1;
-                let month = self.selected_date.month();
-                let day = u8::min(
-                    self.selected_date.day(),
-                    days_in_month(year, u8::from(month)),
-                );
-
-                if let Ok(date) = Date::from_calendar_date(year, month, day) {
-                    self.selected_date = date
-                }
-            }
-            KeyCode::Char('P') => {
-                let year = self.selected_date.year() - 1;
-                let month = self.selected_date.month();
-                let day = u8::min(
-                    self.selected_date.day(),
-                    days_in_month(year, u8::from(month)),
-                );
-
-                if let Ok(date) = Date::from_calendar_date(year, month, day) {
-                    self.selected_date = date
-                }
-            }
-
-            KeyCode::Char('t') => {
-                self.selected_date = OffsetDateTime::now_local()
-                    .unwrap_or(OffsetDateTime::now_utc())
-                    .date();
-            }
-            _ => {}
         }
     }
 
@@ -213,22 +191,19 @@ impl DateInput {
         cursor_style: Style,
         error: Option<&String>,
     ) {
-        let input_block = match error {
-            Some(message) => Block::default()
-                .title(Line::from("Due Date: (YYYY-MM-DD)").left_aligned())
-                .title(Line::from(message.to_string()).right_aligned())
-                .title_bottom(Line::from("Press <Ctrl-c> for calendar view").right_aligned())
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(border_style),
-            None => Block::default()
-                .title(Line::from("Due Date: (YYYY-MM-DD)").left_aligned())
-                .title_bottom(Line::from("Press <Ctrl-c> for calendar view").right_aligned())
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(border_style),
+        let status_line = match error.or(self.error_message.as_ref()) {
+            Some(message) => Line::from(message.to_string()).right_aligned(),
+            None => self.preview_line(),
         };
 
+        let input_block = Block::default()
+            .title(Line::from("Due Date: (YYYY-MM-DD, today, fri, in 3 days...)").left_aligned())
+            .title(status_line)
+            .title_bottom(Line::from("Press <Ctrl-c> for calendar view").right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(border_style);
+
         match self.input_mode {
             DateInputMode::Text => {
                 self.input.set_block(input_block);
@@ -266,18 +241,23 @@ impl DateInput {
     }
 }
 
-fn days_in_month(year: i32, month: u8) -> u8 {
-    match month {
-        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
-        4 | 6 | 9 | 11 => 30,
-        2 => {
-            // February - check for leap year
-            if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 {
-                29
-            } else {
-                28
-            }
-        }
-        _ => panic!("Invalid month"),
-    }
+/// Moves a calendar cursor with the picker keys: `h`/`l` by a day, `j`/`k` by a week,
+/// `n`/`p` by a month, `N`/`P` by a year and `t` back to today.
+///
+/// Returns `None` for keys that are not calendar navigation.
+pub fn navigate_calendar(date: Date, code: KeyCode) -> Option<Date> {
+    let moved = match code {
+        KeyCode::Char('h') => date.previous_day(),
+        KeyCode::Char('l') => date.next_day(),
+        KeyCode::Char('j') => date.checked_add(Duration::days(7)),
+        KeyCode::Char('k') => date.checked_sub(Duration::days(7)),
+        KeyCode::Char('n') => add_months(date, 1),
+        KeyCode::Char('p') => add_months(date, -1),
+        KeyCode::Char('N') => add_months(date, 12),
+        KeyCode::Char('P') => add_months(date, -12),
+        KeyCode::Char('t') => Some(get_today_with_fallbacks().0),
+        _ => return None,
+    };
+
+    Some(moved.unwrap_or(date))
 }
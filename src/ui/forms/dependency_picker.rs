@@ -0,0 +1,83 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::widgets::ListState;
+use uuid::Uuid;
+
+/// What the picker wants the app to do after a key press.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyPickerAction {
+    None,
+    Cancel,
+    Confirm(Vec<Uuid>),
+}
+
+#[derive(Debug, Default)]
+pub struct DependencyPicker {
+    pub is_open: bool,
+    /// Tasks that can be picked as prerequisites, with the label shown for each.
+    pub candidates: Vec<(Uuid, String)>,
+    pub state: ListState,
+    /// Prerequisites ticked so far.
+    pub chosen: Vec<Uuid>,
+}
+
+impl DependencyPicker {
+    pub fn open(&mut self, candidates: Vec<(Uuid, String)>, chosen: &[Uuid]) {
+        let selected = (!candidates.is_empty()).then_some(0);
+
+        *self = Self {
+            is_open: true,
+            candidates,
+            state: ListState::default().with_selected(selected),
+            chosen: chosen.to_vec(),
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_chosen(&self, id: Uuid) -> bool {
+        self.chosen.contains(&id)
+    }
+
+    pub fn handle_input(&mut self, key: KeyEvent) -> DependencyPickerAction {
+        let count = self.candidates.len();
+
+        match key.code {
+            KeyCode::Esc => DependencyPickerAction::Cancel,
+            KeyCode::Enter => DependencyPickerAction::Confirm(self.chosen.clone()),
+            _ if count == 0 => DependencyPickerAction::None,
+            KeyCode::Char('j') | KeyCode::Down => {
+                let selected = self.state.selected().unwrap_or(0);
+                self.state.select(Some((selected + 1) % count));
+                DependencyPickerAction::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                let selected = self.state.selected().unwrap_or(0);
+                self.state.select(Some((selected + count - 1) % count));
+                DependencyPickerAction::None
+            }
+            KeyCode::Char(' ') => {
+                self.toggle_selected();
+                DependencyPickerAction::None
+            }
+            _ => DependencyPickerAction::None,
+        }
+    }
+
+    fn toggle_selected(&mut self) {
+        let Some((id, _)) = self
+            .state
+            .selected()
+            .and_then(|index| self.candidates.get(index))
+        else {
+            return;
+        };
+
+        if let Some(position) = self.chosen.iter().position(|chosen| chosen == id) {
+            self.chosen.remove(position);
+        } else {
+            self.chosen.push(*id);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crossterm::event::KeyEvent;
+use tui_textarea::TextArea;
+
+use crate::core::{quick_add::QuickAdd, validation::ValidationResult};
+
+#[derive(Debug, Default)]
+pub struct QuickAddForm {
+    pub is_open: bool,
+    pub input: TextArea<'static>,
+    pub parsed: Option<ValidationResult<QuickAdd>>,
+}
+
+impl QuickAddForm {
+    pub fn open(&mut self) {
+        *self = Self {
+            is_open: true,
+            ..Self::default()
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn line(&self) -> &str {
+        &self.input.lines()[0]
+    }
+
+    pub fn input(&mut self, key: KeyEvent) {
+        self.input.input(key);
+        self.update_preview();
+    }
+
+    pub fn update_preview(&mut self) {
+        self.parsed = if self.line().trim().is_empty() {
+            None
+        } else {
+            Some(QuickAdd::parse(self.line()))
+        };
+    }
+}
@@ -0,0 +1,124 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::widgets::ListState;
+use time::OffsetDateTime;
+use tui_textarea::TextArea;
+
+use crate::core::{error::AppError, task::task::ReminderTime, validation::TaskValidator};
+
+/// What the reminder list wants the app to do after a key press.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReminderListAction {
+    None,
+    Close,
+    Add(ReminderTime),
+    Delete(usize),
+}
+
+/// A reminder being typed.
+#[derive(Debug, Default)]
+pub struct ReminderInput {
+    pub input: TextArea<'static>,
+    pub error: Option<AppError>,
+}
+
+#[derive(Debug, Default)]
+pub struct ReminderList {
+    pub is_open: bool,
+    pub state: ListState,
+    pub reminder_count: usize,
+    pub adding: Option<ReminderInput>,
+}
+
+impl ReminderList {
+    pub fn open(&mut self, reminder_count: usize) {
+        *self = Self {
+            is_open: true,
+            state: ListState::default().with_selected(reminder_count.checked_sub(1)),
+            reminder_count,
+            adding: None,
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Keeps the selection valid after reminders were added or removed, moving it
+    /// to a newly added reminder.
+    pub fn set_reminder_count(&mut self, reminder_count: usize) {
+        let selected = match self.state.selected() {
+            _ if reminder_count == 0 => None,
+            Some(index) if reminder_count <= self.reminder_count => {
+                Some(index.min(reminder_count - 1))
+            }
+            _ => Some(reminder_count - 1),
+        };
+
+        self.reminder_count = reminder_count;
+        self.state.select(selected);
+    }
+
+    /// `has_due_date` decides whether reminders relative to the due date are allowed.
+    pub fn handle_input(
+        &mut self,
+        key: KeyEvent,
+        now: OffsetDateTime,
+        has_due_date: bool,
+    ) -> ReminderListAction {
+        if let Some(adding) = &mut self.adding {
+            return match key.code {
+                KeyCode::Esc => {
+                    self.adding = None;
+                    ReminderListAction::None
+                }
+                KeyCode::Enter => {
+                    match TaskValidator::validate_reminder(
+                        &adding.input.lines()[0],
+                        now,
+                        has_due_date,
+                    ) {
+                        Ok(time) => {
+                            self.adding = None;
+                            ReminderListAction::Add(time)
+                        }
+                        Err(error) => {
+                            adding.error = Some(error);
+                            ReminderListAction::None
+                        }
+                    }
+                }
+                _ => {
+                    adding.input.input(key);
+                    adding.error = None;
+                    ReminderListAction::None
+                }
+            };
+        }
+
+        let selected = self.state.selected();
+
+        match key.code {
+            KeyCode::Esc => ReminderListAction::Close,
+            KeyCode::Char('j') | KeyCode::Down if self.reminder_count > 0 => {
+                let index = selected.map_or(0, |index| (index + 1) % self.reminder_count);
+                self.state.select(Some(index));
+                ReminderListAction::None
+            }
+            KeyCode::Char('k') | KeyCode::Up if self.reminder_count > 0 => {
+                let index = selected.map_or(0, |index| {
+                    (index + self.reminder_count - 1) % self.reminder_count
+                });
+                self.state.select(Some(index));
+                ReminderListAction::None
+            }
+            KeyCode::Char('a') => {
+                self.adding = Some(ReminderInput::default());
+                ReminderListAction::None
+            }
+            KeyCode::Char('d') => {
+                selected.map_or(ReminderListAction::None, ReminderListAction::Delete)
+            }
+            _ => ReminderListAction::None,
+        }
+    }
+}
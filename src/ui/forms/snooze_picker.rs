@@ -0,0 +1,136 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use strum::{EnumIter, IntoEnumIterator};
+use time::{Date, Duration};
+
+use super::date_input::navigate_calendar;
+use crate::utils::date::add_months;
+
+/// The choices offered when snoozing a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum SnoozePreset {
+    Tomorrow,
+    NextWeek,
+    NextMonth,
+    PickDate,
+    Clear,
+}
+
+impl SnoozePreset {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SnoozePreset::Tomorrow => "Tomorrow",
+            SnoozePreset::NextWeek => "Next week",
+            SnoozePreset::NextMonth => "Next month",
+            SnoozePreset::PickDate => "Pick a date…",
+            SnoozePreset::Clear => "Don't defer",
+        }
+    }
+
+    /// The date the preset defers to, counted from `base`.
+    pub fn date(&self, base: Date) -> Option<Date> {
+        match self {
+            SnoozePreset::Tomorrow => base.next_day(),
+            SnoozePreset::NextWeek => base.checked_add(Duration::weeks(1)),
+            SnoozePreset::NextMonth => add_months(base, 1),
+            SnoozePreset::PickDate | SnoozePreset::Clear => None,
+        }
+    }
+}
+
+/// What the picker wants the app to do after a key press.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnoozePickerAction {
+    None,
+    Cancel,
+    /// Defer until the date, or stop deferring with `None`.
+    Confirm(Option<Date>),
+}
+
+#[derive(Debug, Default)]
+pub struct SnoozePicker {
+    pub is_open: bool,
+    /// The day presets count from: the current defer date if it is still ahead,
+    /// otherwise today. Snoozing again pushes the date further out.
+    pub base: Option<Date>,
+    pub selected: usize,
+    /// The day under the cursor while picking a date on the calendar.
+    pub picking: Option<Date>,
+}
+
+impl SnoozePicker {
+    pub fn open(&mut self, base: Date) {
+        *self = Self {
+            is_open: true,
+            base: Some(base),
+            selected: 0,
+            picking: None,
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn selected_preset(&self) -> SnoozePreset {
+        SnoozePreset::iter()
+            .nth(self.selected)
+            .unwrap_or(SnoozePreset::Tomorrow)
+    }
+
+    pub fn handle_input(&mut self, key: KeyEvent) -> SnoozePickerAction {
+        if let Some(date) = self.picking {
+            return match key.code {
+                KeyCode::Esc => {
+                    self.picking = None;
+                    SnoozePickerAction::None
+                }
+                KeyCode::Enter => SnoozePickerAction::Confirm(Some(date)),
+                code => {
+                    if let Some(date) = navigate_calendar(date, code) {
+                        self.picking = Some(date);
+                    }
+                    SnoozePickerAction::None
+                }
+            };
+        }
+
+        let count = SnoozePreset::iter().count();
+
+        match key.code {
+            KeyCode::Esc => SnoozePickerAction::Cancel,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1) % count;
+                SnoozePickerAction::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = (self.selected + count - 1) % count;
+                SnoozePickerAction::None
+            }
+            KeyCode::Char(digit @ '1'..='9') => {
+                let index = digit as usize - '1' as usize;
+                if index < count {
+                    self.selected = index;
+                    return self.confirm();
+                }
+                SnoozePickerAction::None
+            }
+            KeyCode::Enter => self.confirm(),
+            _ => SnoozePickerAction::None,
+        }
+    }
+
+    fn confirm(&mut self) -> SnoozePickerAction {
+        let Some(base) = self.base else {
+            return SnoozePickerAction::Cancel;
+        };
+
+        match self.selected_preset() {
+            SnoozePreset::PickDate => {
+                self.picking = base.next_day();
+                SnoozePickerAction::None
+            }
+            SnoozePreset::Clear => SnoozePickerAction::Confirm(None),
+            preset => SnoozePickerAction::Confirm(preset.date(base)),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use strum::IntoEnumIterator;
+use tui_textarea::TextArea;
+
+use crate::core::task::task::Status;
+
+/// What the picker wants the app to do after a key press.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusPickerAction {
+    None,
+    Cancel,
+    Confirm(Status, Option<String>),
+}
+
+#[derive(Debug, Default)]
+pub struct StatusPicker {
+    pub is_open: bool,
+    /// Status of the task the picker was opened for.
+    pub current: Option<Status>,
+    pub selected: usize,
+    /// Set once `Blocked` is chosen and the reason is being typed.
+    pub entering_reason: bool,
+    pub reason: TextArea<'static>,
+}
+
+impl StatusPicker {
+    pub fn open(&mut self, current: Status, blocked_reason: Option<&str>) {
+        let mut reason = TextArea::default();
+        if let Some(blocked_reason) = blocked_reason {
+            reason.insert_str(blocked_reason);
+        }
+
+        *self = Self {
+            is_open: true,
+            current: Some(current),
+            selected: Status::iter()
+                .position(|status| status == current)
+                .unwrap_or(0),
+            entering_reason: false,
+            reason,
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn selected_status(&self) -> Status {
+        Status::iter().nth(self.selected).unwrap_or(Status::Todo)
+    }
+
+    /// Whether the task can be moved to `status` from where it is now.
+    pub fn is_allowed(&self, status: Status) -> bool {
+        self.current
+            .is_none_or(|current| current.can_transition_to(status))
+    }
+
+    pub fn handle_input(&mut self, key: KeyEvent) -> StatusPickerAction {
+        if self.entering_reason {
+            return match key.code {
+                KeyCode::Esc => {
+                    self.entering_reason = false;
+                    StatusPickerAction::None
+                }
+                KeyCode::Enter => {
+                    let reason = self.reason.lines()[0].trim().to_string();
+                    let reason = (!reason.is_empty()).then_some(reason);
+                    StatusPickerAction::Confirm(Status::Blocked, reason)
+                }
+                _ => {
+                    self.reason.input(key);
+                    StatusPickerAction::None
+                }
+            };
+        }
+
+        let count = Status::iter().count();
+
+        match key.code {
+            KeyCode::Esc => StatusPickerAction::Cancel,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1) % count;
+                StatusPickerAction::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = (self.selected + count - 1) % count;
+                StatusPickerAction::None
+            }
+            KeyCode::Char(digit @ '1'..='9') => {
+                let index = digit as usize - '1' as usize;
+                if index < count {
+                    self.selected = index;
+                    return self.confirm();
+                }
+                StatusPickerAction::None
+            }
+            KeyCode::Enter => self.confirm(),
+            _ => StatusPickerAction::None,
+        }
+    }
+
+    fn confirm(&mut self) -> StatusPickerAction {
+        let status = self.selected_status();
+
+        if !self.is_allowed(status) {
+            return StatusPickerAction::None;
+        }
+
+        if status == Status::Blocked {
+            self.entering_reason = true;
+            return StatusPickerAction::None;
+        }
+
+        StatusPickerAction::Confirm(status, None)
+    }
+}
@@ -4,15 +4,21 @@ use crossterm::event::KeyEvent;
 use ratatui::style::{Color, Style};
 use strum::{Display, EnumIter, IntoEnumIterator};
 use time::Date;
-use tui_textarea::TextArea;
+use tui_textarea::{CursorMove, TextArea};
+use uuid::Uuid;
 
-use crate::core::{error::AppError, task::Task, validation::TaskValidator};
+use crate::core::{
+    task::{Task, task::Estimate},
+    validation::TaskValidator,
+};
 
 use super::date_input::DateInput;
 
 #[derive(Debug)]
 pub struct TaskForm {
     pub is_open: bool,
+    /// The task being edited, or `None` when creating a new one.
+    pub editing_task_id: Option<Uuid>,
     pub selected: FormField,
     pub form_input: FormInput,
     pub field_errors: HashMap<FormField, String>,
@@ -22,6 +28,7 @@ pub struct TaskForm {
 pub enum FormField {
     Title,
     DueDate,
+    Estimate,
     Description,
     // Group,
 }
@@ -43,16 +50,14 @@ impl FormField {
 }
 
 pub trait FormFieldAccess<T> {
-    fn access_field<'a, 'b>(&'a self, form: &'b mut FormInput) -> Option<&'b mut T>;
+    fn access_field<'b>(&self, form: &'b mut FormInput) -> Option<&'b mut T>;
 }
 
 impl FormFieldAccess<TextArea<'static>> for FormField {
-    fn access_field<'a, 'b>(
-        &'a self,
-        form: &'b mut FormInput,
-    ) -> Option<&'b mut TextArea<'static>> {
+    fn access_field<'b>(&self, form: &'b mut FormInput) -> Option<&'b mut TextArea<'static>> {
         match self {
             FormField::Title => Some(&mut form.title),
+            FormField::Estimate => Some(&mut form.estimate),
             FormField::Description => Some(&mut form.description),
             _ => None,
         }
@@ -69,7 +74,7 @@ impl FormFieldAccess<TextArea<'static>> for FormField {
 // }
 
 impl FormFieldAccess<DateInput> for FormField {
-    fn access_field<'a, 'b>(&'a self, form: &'b mut FormInput) -> Option<&'b mut DateInput> {
+    fn access_field<'b>(&self, form: &'b mut FormInput) -> Option<&'b mut DateInput> {
         match self {
             FormField::DueDate => Some(&mut form.due_date),
             _ => None,
@@ -83,13 +88,15 @@ pub struct FormInput {
     pub description: TextArea<'static>,
     pub group: String,
     pub due_date: DateInput,
+    pub estimate: TextArea<'static>,
 }
 
 #[derive(Debug, Clone)]
 pub struct FormInputData {
     pub title: String,
     pub description: Vec<String>,
-    pub due_date: Date,
+    pub due_date: Option<Date>,
+    pub estimate: Option<Estimate>,
 }
 
 impl Default for FormInput {
@@ -99,6 +106,7 @@ impl Default for FormInput {
             description: TextArea::default(),
             group: String::new(),
             due_date: DateInput::new(),
+            estimate: TextArea::default(),
         }
     }
 }
@@ -107,6 +115,7 @@ impl Default for TaskForm {
     fn default() -> Self {
         Self {
             is_open: false,
+            editing_task_id: None,
             selected: FormField::Title,
             form_input: FormInput::default(),
             field_errors: HashMap::new(),
@@ -143,7 +152,7 @@ impl TaskForm {
 
     pub fn input(&mut self, key: KeyEvent) {
         match self.selected {
-            FormField::Title | FormField::Description => {
+            FormField::Title | FormField::Estimate | FormField::Description => {
                 if let Some(textarea) = self.access_current_field::<TextArea<'static>>() {
                     textarea.input(key);
                 }
@@ -160,6 +169,33 @@ impl TaskForm {
     pub fn reset_form_input(&mut self) {
         self.form_input = FormInput::default();
         self.selected = FormField::Title;
+        self.editing_task_id = None;
+    }
+
+    /// Fills the form with an existing task so it can be edited.
+    pub fn load_task(&mut self, task: &Task) {
+        let mut title = TextArea::default();
+        title.insert_str(&task.title);
+
+        let mut description = TextArea::from(task.description.clone());
+        description.move_cursor(CursorMove::Bottom);
+        description.move_cursor(CursorMove::End);
+
+        let mut estimate = TextArea::default();
+        if let Some(task_estimate) = task.estimate {
+            estimate.insert_str(task_estimate.to_string().replace(' ', ""));
+        }
+
+        self.form_input = FormInput {
+            title,
+            description,
+            group: task.group.clone().unwrap_or_default(),
+            due_date: DateInput::from_date(task.due_date),
+            estimate,
+        };
+        self.selected = FormField::Title;
+        self.editing_task_id = Some(task.id);
+        self.clear_field_errors();
     }
 
     pub fn validate_current_field(&mut self) -> Result<(), (FormField, String)> {
@@ -171,12 +207,14 @@ impl TaskForm {
                     Err(app_error) => Err((FormField::Title, app_error.user_message())),
                 }
             }
-            FormField::DueDate => {
-                let selected_date = self.form_input.due_date.selected_date;
-
-                match TaskValidator::validate_due_date(selected_date) {
+            FormField::DueDate => match self.form_input.due_date.validate_current_input() {
+                Ok(_) => Ok(()),
+                Err(app_error) => Err((FormField::DueDate, app_error.user_message())),
+            },
+            FormField::Estimate => {
+                match TaskValidator::validate_estimate(&self.form_input.estimate.lines()[0]) {
                     Ok(_) => Ok(()),
-                    Err(app_error) => Err((FormField::DueDate, app_error.user_message())),
+                    Err(app_error) => Err((FormField::Estimate, app_error.user_message())),
                 }
             }
             FormField::Description => {
@@ -199,9 +237,29 @@ impl TaskForm {
     pub fn validate_all_field(&mut self) -> Result<(), HashMap<FormField, String>> {
         let title = &self.form_input.title.lines()[0];
         let description = self.form_input.description.lines();
-        let due_date = self.form_input.due_date.selected_date;
+        let due_date = self.form_input.due_date.due_date();
+
+        let mut field_errors = match TaskValidator::validate_all_task_field(
+            title,
+            description,
+            due_date,
+            self.form_input.due_date.original_date,
+            &self.form_input.estimate.lines()[0],
+        ) {
+            Ok(_) => HashMap::new(),
+            Err(field_errors) => field_errors,
+        };
+
+        // The typed text wins over the last resolved date if it no longer parses
+        if let Err(app_error) = self.form_input.due_date.validate_current_input() {
+            field_errors.insert(FormField::DueDate, app_error.user_message());
+        }
 
-        TaskValidator::validate_all_task_field(title, description, due_date)
+        if field_errors.is_empty() {
+            Ok(())
+        } else {
+            Err(field_errors)
+        }
     }
 
     pub fn clear_field_errors(&mut self) {
@@ -235,7 +293,10 @@ impl TaskForm {
                 .iter()
                 .map(|s| s.to_string())
                 .collect(),
-            due_date: self.form_input.due_date.selected_date,
+            due_date: self.form_input.due_date.due_date(),
+            estimate: TaskValidator::validate_estimate(&self.form_input.estimate.lines()[0])
+                .ok()
+                .flatten(),
         }
     }
 }
@@ -0,0 +1,138 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::widgets::ListState;
+use time::Duration;
+use tui_textarea::TextArea;
+
+use crate::{
+    core::{error::AppError, validation::TaskValidator},
+    utils::duration::format_duration,
+};
+
+/// What the time log wants the app to do after a key press.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeLogAction {
+    None,
+    Close,
+    Add(Duration),
+    SetDuration(usize, Duration),
+    Delete(usize),
+}
+
+/// The duration being typed, for a new entry (`index` is `None`) or an existing one.
+#[derive(Debug, Default)]
+pub struct DurationInput {
+    pub index: Option<usize>,
+    pub input: TextArea<'static>,
+    pub error: Option<AppError>,
+}
+
+#[derive(Debug, Default)]
+pub struct TimeLog {
+    pub is_open: bool,
+    pub state: ListState,
+    pub entry_count: usize,
+    pub editing: Option<DurationInput>,
+}
+
+impl TimeLog {
+    pub fn open(&mut self, entry_count: usize) {
+        *self = Self {
+            is_open: true,
+            state: ListState::default().with_selected(entry_count.checked_sub(1)),
+            entry_count,
+            editing: None,
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Keeps the selection valid after entries were added or removed, moving it
+    /// to a newly added entry.
+    pub fn set_entry_count(&mut self, entry_count: usize) {
+        let selected = match self.state.selected() {
+            _ if entry_count == 0 => None,
+            Some(index) if entry_count <= self.entry_count => Some(index.min(entry_count - 1)),
+            _ => Some(entry_count - 1),
+        };
+
+        self.entry_count = entry_count;
+        self.state.select(selected);
+    }
+
+    /// Starts typing a duration, prefilled with `current` when editing an entry.
+    fn edit(&mut self, index: Option<usize>, current: Option<Duration>) {
+        let mut input = TextArea::default();
+        if let Some(current) = current {
+            input.insert_str(format_duration(current).replace(' ', ""));
+        }
+
+        self.editing = Some(DurationInput {
+            index,
+            input,
+            error: None,
+        });
+    }
+
+    /// `durations` are the lengths of the task's entries, used to prefill edits.
+    pub fn handle_input(&mut self, key: KeyEvent, durations: &[Duration]) -> TimeLogAction {
+        if let Some(editing) = &mut self.editing {
+            return match key.code {
+                KeyCode::Esc => {
+                    self.editing = None;
+                    TimeLogAction::None
+                }
+                KeyCode::Enter => match TaskValidator::validate_duration(&editing.input.lines()[0])
+                {
+                    Ok(duration) => {
+                        let action = match editing.index {
+                            Some(index) => TimeLogAction::SetDuration(index, duration),
+                            None => TimeLogAction::Add(duration),
+                        };
+                        self.editing = None;
+                        action
+                    }
+                    Err(error) => {
+                        editing.error = Some(error);
+                        TimeLogAction::None
+                    }
+                },
+                _ => {
+                    editing.input.input(key);
+                    editing.error = None;
+                    TimeLogAction::None
+                }
+            };
+        }
+
+        let selected = self.state.selected();
+
+        match key.code {
+            KeyCode::Esc => TimeLogAction::Close,
+            KeyCode::Char('j') | KeyCode::Down if self.entry_count > 0 => {
+                let index = selected.map_or(0, |index| (index + 1) % self.entry_count);
+                self.state.select(Some(index));
+                TimeLogAction::None
+            }
+            KeyCode::Char('k') | KeyCode::Up if self.entry_count > 0 => {
+                let index =
+                    selected.map_or(0, |index| (index + self.entry_count - 1) % self.entry_count);
+                self.state.select(Some(index));
+                TimeLogAction::None
+            }
+            KeyCode::Char('a') => {
+                self.edit(None, None);
+                TimeLogAction::None
+            }
+            KeyCode::Enter | KeyCode::Char('e') => {
+                if let Some(index) = selected {
+                    self.edit(Some(index), durations.get(index).copied());
+                }
+                TimeLogAction::None
+            }
+            KeyCode::Char('d') => selected.map_or(TimeLogAction::None, TimeLogAction::Delete),
+            _ => TimeLogAction::None,
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::{collections::BTreeMap, fs, path::PathBuf, sync::OnceLock};
+
+use serde::{Deserialize, Serialize};
+use time::{
+    Time,
+    macros::{format_description, time},
+};
+
+use crate::core::{
+    error::{AppError, AppResult},
+    validation::DueDatePolicy,
+};
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+const DEFAULT_DUE_TIME: Time = time!(09:00);
+
+/// User settings read from `config.json` next to the task file.
+///
+/// Every field is optional in the file; missing ones fall back to their defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Config {
+    pub due_date: DueDatePolicy,
+    pub agenda: AgendaConfig,
+    pub pomodoro: PomodoroConfig,
+    pub reminders: ReminderConfig,
+    pub csv: CsvConfig,
+    pub org: OrgConfig,
+    pub git: GitConfig,
+    pub caldav: CaldavConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AgendaConfig {
+    /// How many days, starting today, the agenda covers.
+    pub days: u32,
+    /// Estimated minutes of work that fit in a day; busier days are flagged.
+    /// `null` turns the check off.
+    pub daily_capacity_minutes: Option<u32>,
+}
+
+impl Default for AgendaConfig {
+    fn default() -> Self {
+        Self {
+            days: 7,
+            daily_capacity_minutes: Some(480),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PomodoroConfig {
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    /// Every how many work phases the break is a long one.
+    pub long_break_every: u32,
+}
+
+impl Default for PomodoroConfig {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            long_break_every: 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReminderConfig {
+    /// Time of day, as `HH:MM`, that reminders relative to a due date count back from.
+    pub due_time: String,
+    /// Command run when a reminder goes off, e.g. `["notify-send", "delibird", "{message}"]`.
+    /// `{title}` and `{message}` in the arguments are filled in. Empty runs nothing.
+    pub command: Vec<String>,
+}
+
+impl Default for ReminderConfig {
+    fn default() -> Self {
+        Self {
+            due_time: "09:00".to_string(),
+            command: Vec::new(),
+        }
+    }
+}
+
+impl ReminderConfig {
+    pub fn due_time(&self) -> Time {
+        Time::parse(
+            self.due_time.trim(),
+            format_description!("[hour padding:none]:[minute]"),
+        )
+        .unwrap_or(DEFAULT_DUE_TIME)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CsvConfig {
+    /// Columns written on export, in order. Empty writes every column.
+    pub columns: Vec<String>,
+    /// Headers used by other tools and the column each one stands for,
+    /// e.g. `{"Task Name": "title", "Deadline": "due"}`.
+    pub mapping: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OrgConfig {
+    /// Org file rewritten with every task after each save. `~/` is the home directory.
+    pub auto_export: Option<String>,
+}
+
+impl OrgConfig {
+    pub fn auto_export_path(&self) -> Option<PathBuf> {
+        let path = self.auto_export.as_deref()?.trim();
+        if path.is_empty() {
+            return None;
+        }
+
+        match path.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+            None => Some(PathBuf::from(path)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GitConfig {
+    /// Keeps the data directory in a git repository with a commit per change.
+    pub enabled: bool,
+    /// Remote name, path or URL that `delibird sync` pulls from and pushes to.
+    pub remote: String,
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            remote: "origin".to_string(),
+        }
+    }
+}
+
+/// Which side wins when a task changed both locally and on the CalDAV server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictRule {
+    Local,
+    #[default]
+    Remote,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CaldavConfig {
+    /// The task calendar's collection URL. Empty turns CalDAV sync off.
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    pub on_conflict: ConflictRule,
+}
+
+impl CaldavConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.url.trim().is_empty()
+    }
+}
+
+impl Config {
+    pub fn file_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("delibird").join("config.json"))
+    }
+
+    pub fn load() -> AppResult<Self> {
+        let Some(file_path) = Self::file_path() else {
+            return Ok(Self::default());
+        };
+
+        if !file_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&file_path).map_err(|err| AppError::LoadFailed {
+            path: file_path.display().to_string(),
+            reason: format!("Failed to read config file: {}", err),
+        })?;
+
+        serde_json::from_str(&contents).map_err(|err| AppError::LoadFailed {
+            path: file_path.display().to_string(),
+            reason: format!("Invalid config file: {}", err),
+        })
+    }
+
+    /// Makes `config` the process-wide configuration. Only the first call has an effect.
+    pub fn install(config: Config) {
+        let _ = CONFIG.set(config);
+    }
+
+    /// The process-wide configuration, loaded from disk on first use.
+    pub fn global() -> &'static Config {
+        CONFIG.get_or_init(|| Self::load().unwrap_or_default())
+    }
+}
@@ -1,8 +1,35 @@
-use std::process::{Command, Output};
+use std::{
+    process::{Command, Output},
+    sync::{Mutex, OnceLock},
+    time::Instant,
+};
 
-use time::{Date, OffsetDateTime, macros::format_description};
+use time::{Date, Duration, Month, OffsetDateTime, UtcOffset, Weekday, macros::format_description};
 
+/// How long a resolved date is reused before asking the system again.
+const TODAY_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(30);
+
+static TODAY_CACHE: Mutex<Option<(Instant, Date, Option<String>)>> = Mutex::new(None);
+
+/// Today's date, plus a warning when it could not be taken from the local timezone.
+///
+/// The result is cached briefly since the fallbacks may spawn a process and this is
+/// called while rendering.
 pub fn get_today_with_fallbacks() -> (Date, Option<String>) {
+    let mut cache = TODAY_CACHE.lock().unwrap_or_else(|err| err.into_inner());
+
+    if let Some((resolved_at, date, warning)) = cache.as_ref()
+        && resolved_at.elapsed() < TODAY_CACHE_TTL
+    {
+        return (*date, warning.clone());
+    }
+
+    let (date, warning) = resolve_today();
+    *cache = Some((Instant::now(), date, warning.clone()));
+    (date, warning)
+}
+
+fn resolve_today() -> (Date, Option<String>) {
     if let Ok(local_date) = OffsetDateTime::now_local() {
         return (local_date.date(), None);
     }
@@ -21,6 +48,48 @@ pub fn get_today_with_fallbacks() -> (Date, Option<String>) {
     )
 }
 
+/// The current moment, in the local timezone when it can be determined and UTC otherwise.
+pub fn now_timestamp() -> OffsetDateTime {
+    OffsetDateTime::now_utc().to_offset(local_offset())
+}
+
+/// The local UTC offset, resolved once. Like today's date, it falls back to asking
+/// the system `date` command and then to UTC.
+pub fn local_offset() -> UtcOffset {
+    static LOCAL_OFFSET: OnceLock<UtcOffset> = OnceLock::new();
+
+    *LOCAL_OFFSET.get_or_init(|| {
+        UtcOffset::current_local_offset()
+            .ok()
+            .or_else(get_system_offset)
+            .unwrap_or(UtcOffset::UTC)
+    })
+}
+
+/// The local UTC offset in effect at `at`, which differs from the current one
+/// across a daylight saving change. Falls back to [`local_offset`].
+pub fn local_offset_at(at: OffsetDateTime) -> UtcOffset {
+    UtcOffset::local_offset_at(at).unwrap_or_else(|_| local_offset())
+}
+
+fn get_system_offset() -> Option<UtcOffset> {
+    if !cfg!(unix) {
+        return None;
+    }
+
+    let output = Command::new("date").arg("+%z").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let offset = String::from_utf8(output.stdout).ok()?;
+    UtcOffset::parse(
+        offset.trim(),
+        format_description!("[offset_hour sign:mandatory][offset_minute]"),
+    )
+    .ok()
+}
+
 pub fn get_system_date() -> Result<Date, String> {
     #[cfg(not(any(unix, windows)))]
     {
@@ -56,3 +125,398 @@ pub fn get_system_date() -> Result<Date, String> {
         Err("Date command returned error".to_string())
     }
 }
+
+/// Resolves a natural-language date expression relative to `today`.
+///
+/// Accepts `today`, `tomorrow`, weekday names (`fri`, `next monday`), offsets
+/// (`in 3 days`, `+2w`, `-1d`), `next week`/`next month`, `end of week|month|year`
+/// and month-day pairs (`nov 15`, `15 november 2027`). Returns `None` when the
+/// input is not recognised; ISO dates are left to the regular date parser.
+pub fn parse_natural_date(input: &str, today: Date) -> Option<Date> {
+    let normalized = input.trim().to_lowercase();
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+
+    match words.as_slice() {
+        ["today" | "tod" | "now"] => Some(today),
+        ["tomorrow" | "tmr" | "tom"] => today.next_day(),
+        ["yesterday"] => today.previous_day(),
+        ["next", "week"] => next_weekday(today, Weekday::Monday),
+        ["next", "month"] => add_months(today, 1)
+            .and_then(|date| Date::from_calendar_date(date.year(), date.month(), 1).ok()),
+        ["next", "year"] => Date::from_calendar_date(today.year() + 1, Month::January, 1).ok(),
+        ["end", "of", "week"] | ["eow"] => end_of_week(today),
+        ["end", "of", "month"] | ["eom"] => Date::from_calendar_date(
+            today.year(),
+            today.month(),
+            today.month().length(today.year()),
+        )
+        .ok(),
+        ["end", "of", "year"] | ["eoy"] => {
+            Date::from_calendar_date(today.year(), Month::December, 31).ok()
+        }
+        ["next", weekday] => {
+            let weekday = parse_weekday(weekday)?;
+            // "next <weekday>" is that day in the following calendar week
+            let next_week_start = next_weekday(today, Weekday::Monday)?;
+            let offset = weekday.number_days_from_monday();
+            next_week_start.checked_add(Duration::days(offset.into()))
+        }
+        ["in", amount, unit] => {
+            let amount = match *amount {
+                "a" | "an" | "one" => 1,
+                amount => amount.parse().ok()?,
+            };
+            add_offset(today, amount, parse_unit(unit)?)
+        }
+        [single] if single.starts_with('+') || single.starts_with('-') => {
+            parse_signed_offset(single, today)
+        }
+        [single] => {
+            if let Some(weekday) = parse_weekday(single) {
+                return next_weekday(today, weekday);
+            }
+            None
+        }
+        [first, second] => parse_month_day(first, second, None, today)
+            .or_else(|| parse_month_day(second, first, None, today)),
+        [first, second, year] => {
+            let year = year.parse().ok()?;
+            parse_month_day(first, second, Some(year), today)
+                .or_else(|| parse_month_day(second, first, Some(year), today))
+        }
+        _ => None,
+    }
+}
+
+/// Adds whole months to `date`, clamping the day to the target month's length.
+pub fn add_months(date: Date, months: i64) -> Option<Date> {
+    let total = (i64::from(date.year()) * 12 + i64::from(u8::from(date.month()) - 1))
+        .checked_add(months)?;
+    let year = i32::try_from(total.div_euclid(12)).ok()?;
+    let month = Month::try_from(u8::try_from(total.rem_euclid(12) + 1).ok()?).ok()?;
+    let day = date.day().min(month.length(year));
+
+    Date::from_calendar_date(year, month, day).ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OffsetUnit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+fn parse_unit(unit: &str) -> Option<OffsetUnit> {
+    match unit {
+        "d" | "day" | "days" => Some(OffsetUnit::Day),
+        "w" | "wk" | "week" | "weeks" => Some(OffsetUnit::Week),
+        "m" | "mo" | "month" | "months" => Some(OffsetUnit::Month),
+        "y" | "yr" | "year" | "years" => Some(OffsetUnit::Year),
+        _ => None,
+    }
+}
+
+/// Seconds in a day, for building offsets that may overflow a `Duration`.
+const SECONDS_PER_DAY: i64 = 86_400;
+
+fn add_offset(today: Date, amount: i64, unit: OffsetUnit) -> Option<Date> {
+    match unit {
+        OffsetUnit::Day => {
+            today.checked_add(Duration::seconds(amount.checked_mul(SECONDS_PER_DAY)?))
+        }
+        OffsetUnit::Week => {
+            today.checked_add(Duration::seconds(amount.checked_mul(7 * SECONDS_PER_DAY)?))
+        }
+        OffsetUnit::Month => add_months(today, amount),
+        OffsetUnit::Year => add_months(today, amount.checked_mul(12)?),
+    }
+}
+
+/// Parses `+3`, `+2w`, `-1d`: a signed amount with an optional unit (days by default).
+fn parse_signed_offset(input: &str, today: Date) -> Option<Date> {
+    let (sign, rest) = input.split_at(1);
+    let digits_end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let (digits, unit) = rest.split_at(digits_end);
+
+    let amount: i64 = digits.parse().ok()?;
+    let amount = if sign == "-" { -amount } else { amount };
+    let unit = if unit.is_empty() {
+        OffsetUnit::Day
+    } else {
+        parse_unit(unit)?
+    };
+
+    add_offset(today, amount, unit)
+}
+
+fn parse_weekday(input: &str) -> Option<Weekday> {
+    match input {
+        "mon" | "monday" => Some(Weekday::Monday),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tuesday),
+        "wed" | "wednesday" => Some(Weekday::Wednesday),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thursday),
+        "fri" | "friday" => Some(Weekday::Friday),
+        "sat" | "saturday" => Some(Weekday::Saturday),
+        "sun" | "sunday" => Some(Weekday::Sunday),
+        _ => None,
+    }
+}
+
+fn parse_month(input: &str) -> Option<Month> {
+    let month = match input {
+        "jan" | "january" => Month::January,
+        "feb" | "february" => Month::February,
+        "mar" | "march" => Month::March,
+        "apr" | "april" => Month::April,
+        "may" => Month::May,
+        "jun" | "june" => Month::June,
+        "jul" | "july" => Month::July,
+        "aug" | "august" => Month::August,
+        "sep" | "sept" | "september" => Month::September,
+        "oct" | "october" => Month::October,
+        "nov" | "november" => Month::November,
+        "dec" | "december" => Month::December,
+        _ => return None,
+    };
+    Some(month)
+}
+
+/// Resolves `nov 15` to the next such day on or after `today` unless a year is given.
+fn parse_month_day(month: &str, day: &str, year: Option<i32>, today: Date) -> Option<Date> {
+    let month = parse_month(month)?;
+    let day: u8 = day
+        .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+        .parse()
+        .ok()?;
+
+    if let Some(year) = year {
+        return Date::from_calendar_date(year, month, day).ok();
+    }
+
+    match Date::from_calendar_date(today.year(), month, day) {
+        Ok(date) if date >= today => Some(date),
+        _ => Date::from_calendar_date(today.year() + 1, month, day).ok(),
+    }
+}
+
+/// The first `weekday` strictly after `today`.
+fn next_weekday(today: Date, weekday: Weekday) -> Option<Date> {
+    let current = i64::from(today.weekday().number_days_from_monday());
+    let target = i64::from(weekday.number_days_from_monday());
+    let days = (target - current + 6).rem_euclid(7) + 1;
+
+    today.checked_add(Duration::days(days))
+}
+
+fn end_of_week(today: Date) -> Option<Date> {
+    let days = 6 - i64::from(today.weekday().number_days_from_monday());
+    today.checked_add(Duration::days(days))
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    /// A Wednesday.
+    const TODAY: Date = date!(2026 - 10 - 14);
+
+    fn parse(input: &str) -> Option<Date> {
+        parse_natural_date(input, TODAY)
+    }
+
+    #[test]
+    fn named_days_resolve_around_today() {
+        for input in ["today", "tod", "now"] {
+            assert_eq!(parse(input), Some(TODAY), "{input}");
+        }
+        for input in ["tomorrow", "tmr", "tom"] {
+            assert_eq!(parse(input), Some(date!(2026 - 10 - 15)), "{input}");
+        }
+        assert_eq!(parse("yesterday"), Some(date!(2026 - 10 - 13)));
+    }
+
+    #[test]
+    fn next_and_end_of_periods() {
+        assert_eq!(parse("next week"), Some(date!(2026 - 10 - 19)));
+        assert_eq!(parse("next month"), Some(date!(2026 - 11 - 01)));
+        assert_eq!(parse("next year"), Some(date!(2027 - 01 - 01)));
+
+        for (long, short, expected) in [
+            ("end of week", "eow", date!(2026 - 10 - 18)),
+            ("end of month", "eom", date!(2026 - 10 - 31)),
+            ("end of year", "eoy", date!(2026 - 12 - 31)),
+        ] {
+            assert_eq!(parse(long), Some(expected), "{long}");
+            assert_eq!(parse(short), Some(expected), "{short}");
+        }
+    }
+
+    #[test]
+    fn weekdays_are_strictly_after_today() {
+        assert_eq!(parse("fri"), Some(date!(2026 - 10 - 16)));
+        assert_eq!(parse("monday"), Some(date!(2026 - 10 - 19)));
+        assert_eq!(parse("wed"), Some(date!(2026 - 10 - 21)));
+        assert_eq!(parse("thurs"), Some(date!(2026 - 10 - 15)));
+    }
+
+    #[test]
+    fn next_weekday_is_in_the_following_week() {
+        assert_eq!(parse("next monday"), Some(date!(2026 - 10 - 19)));
+        assert_eq!(parse("next fri"), Some(date!(2026 - 10 - 23)));
+        assert_eq!(parse("next sunday"), Some(date!(2026 - 10 - 25)));
+        assert_eq!(parse("next blursday"), None);
+    }
+
+    #[test]
+    fn in_amount_unit_offsets() {
+        assert_eq!(parse("in 3 days"), Some(date!(2026 - 10 - 17)));
+        assert_eq!(parse("in a week"), Some(date!(2026 - 10 - 21)));
+        assert_eq!(parse("in 2 wk"), Some(date!(2026 - 10 - 28)));
+        assert_eq!(parse("in one month"), Some(date!(2026 - 11 - 14)));
+        assert_eq!(parse("in 2 years"), Some(date!(2028 - 10 - 14)));
+        assert_eq!(parse("in 3 fortnights"), None);
+        assert_eq!(parse("in some days"), None);
+    }
+
+    #[test]
+    fn signed_offsets_default_to_days() {
+        assert_eq!(parse("+3"), Some(date!(2026 - 10 - 17)));
+        assert_eq!(parse("-1"), Some(date!(2026 - 10 - 13)));
+        assert_eq!(parse("+2w"), Some(date!(2026 - 10 - 28)));
+        assert_eq!(parse("-1d"), Some(date!(2026 - 10 - 13)));
+        assert_eq!(parse("+1m"), Some(date!(2026 - 11 - 14)));
+        assert_eq!(parse("-1y"), Some(date!(2025 - 10 - 14)));
+        assert_eq!(parse("+0"), Some(TODAY));
+    }
+
+    #[test]
+    fn malformed_signed_offsets_are_rejected() {
+        for input in ["+", "-", "+w", "+3x", "+3 d", "+-3", "+3.5"] {
+            assert_eq!(parse_signed_offset(input, TODAY), None, "{input}");
+        }
+    }
+
+    #[test]
+    fn month_day_pairs_roll_into_next_year_once_past() {
+        assert_eq!(parse("nov 15"), Some(date!(2026 - 11 - 15)));
+        assert_eq!(parse("15 november"), Some(date!(2026 - 11 - 15)));
+        assert_eq!(parse("15th nov"), Some(date!(2026 - 11 - 15)));
+        assert_eq!(parse("oct 14"), Some(TODAY));
+        assert_eq!(parse("oct 1"), Some(date!(2027 - 10 - 01)));
+        assert_eq!(parse("sept 3 2025"), Some(date!(2025 - 09 - 03)));
+        assert_eq!(parse("3 march 2028"), Some(date!(2028 - 03 - 03)));
+    }
+
+    #[test]
+    fn input_is_trimmed_and_case_insensitive() {
+        assert_eq!(parse("  Next   FRIDAY "), Some(date!(2026 - 10 - 23)));
+        assert_eq!(parse("EOM"), Some(date!(2026 - 10 - 31)));
+    }
+
+    #[test]
+    fn unrecognised_input_is_rejected() {
+        for input in [
+            "",
+            "someday",
+            "2026-10-20",
+            "feb 30",
+            "feb 30 2027",
+            "nov 15 next",
+            "in 1 2 days",
+            "end of decade",
+            "next fortnight",
+        ] {
+            assert_eq!(parse(input), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn offsets_beyond_the_calendar_are_rejected_instead_of_panicking() {
+        for input in [
+            "in 200000000000000 days",
+            "+200000000000000d",
+            "+30000000000000w",
+            "in 9223372036854775807 months",
+            "in 9223372036854775807 years",
+            "-9223372036854775807y",
+            "+99999999999999999999",
+            "in 100000 years",
+        ] {
+            assert_eq!(parse(input), None, "{input}");
+        }
+    }
+
+    #[test]
+    fn add_months_clamps_to_the_end_of_shorter_months() {
+        assert_eq!(
+            add_months(date!(2026 - 01 - 31), 1),
+            Some(date!(2026 - 02 - 28))
+        );
+        assert_eq!(
+            add_months(date!(2028 - 01 - 31), 1),
+            Some(date!(2028 - 02 - 29))
+        );
+        assert_eq!(
+            add_months(date!(2026 - 03 - 31), -1),
+            Some(date!(2026 - 02 - 28))
+        );
+        assert_eq!(
+            add_months(date!(2026 - 05 - 31), 1),
+            Some(date!(2026 - 06 - 30))
+        );
+        assert_eq!(
+            add_months(date!(2028 - 02 - 29), 12),
+            Some(date!(2029 - 02 - 28))
+        );
+    }
+
+    #[test]
+    fn add_months_crosses_year_boundaries() {
+        assert_eq!(
+            add_months(date!(2026 - 12 - 15), 1),
+            Some(date!(2027 - 01 - 15))
+        );
+        assert_eq!(
+            add_months(date!(2026 - 01 - 15), -1),
+            Some(date!(2025 - 12 - 15))
+        );
+        assert_eq!(
+            add_months(date!(2026 - 10 - 14), -22),
+            Some(date!(2024 - 12 - 14))
+        );
+        assert_eq!(add_months(TODAY, 0), Some(TODAY));
+        assert_eq!(add_months(TODAY, i64::MAX), None);
+        assert_eq!(add_months(TODAY, i64::MIN), None);
+    }
+
+    #[test]
+    fn next_weekday_wraps_around_the_week() {
+        let sunday = date!(2026 - 10 - 18);
+        assert_eq!(
+            next_weekday(sunday, Weekday::Monday),
+            Some(date!(2026 - 10 - 19))
+        );
+        assert_eq!(
+            next_weekday(sunday, Weekday::Saturday),
+            Some(date!(2026 - 10 - 24))
+        );
+        assert_eq!(
+            next_weekday(sunday, Weekday::Sunday),
+            Some(date!(2026 - 10 - 25))
+        );
+        assert_eq!(
+            next_weekday(TODAY, Weekday::Tuesday),
+            Some(date!(2026 - 10 - 20))
+        );
+        assert_eq!(
+            next_weekday(date!(2026 - 12 - 31), Weekday::Monday),
+            Some(date!(2027 - 01 - 04))
+        );
+        assert_eq!(next_weekday(Date::MAX, Weekday::Monday), None);
+    }
+}
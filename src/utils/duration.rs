@@ -0,0 +1,81 @@
+use time::Duration;
+
+const SECONDS_PER_MINUTE: i64 = 60;
+const SECONDS_PER_HOUR: i64 = 3_600;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Parses a duration typed by the user: `90` (minutes), `45m`, `2h`, `1h30m`,
+/// `1h 30m`, `1d` or `1:30`. Amounts too large to represent give `None`.
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim().to_lowercase();
+
+    if input.is_empty() {
+        return None;
+    }
+
+    if let Ok(minutes) = input.parse::<i64>() {
+        return in_seconds(minutes, SECONDS_PER_MINUTE);
+    }
+
+    if let Some((hours, minutes)) = input.split_once(':') {
+        let hours: i64 = hours.trim().parse().ok()?;
+        let minutes: i64 = minutes.trim().parse().ok()?;
+        if !(0..60).contains(&minutes) {
+            return None;
+        }
+        return in_seconds(hours, SECONDS_PER_HOUR)?
+            .checked_add(in_seconds(minutes, SECONDS_PER_MINUTE)?);
+    }
+
+    let mut total = Duration::ZERO;
+    let mut number = String::new();
+    let mut seen_unit = false;
+
+    for ch in input.chars().filter(|ch| !ch.is_whitespace()) {
+        if ch.is_ascii_digit() {
+            number.push(ch);
+            continue;
+        }
+
+        let value: i64 = number.parse().ok()?;
+        number.clear();
+        let amount = match ch {
+            'd' => in_seconds(value, SECONDS_PER_DAY)?,
+            'h' => in_seconds(value, SECONDS_PER_HOUR)?,
+            'm' => in_seconds(value, SECONDS_PER_MINUTE)?,
+            's' => Duration::seconds(value),
+            _ => return None,
+        };
+        total = total.checked_add(amount)?;
+        seen_unit = true;
+    }
+
+    (seen_unit && number.is_empty()).then_some(total)
+}
+
+fn in_seconds(value: i64, seconds_per_unit: i64) -> Option<Duration> {
+    value.checked_mul(seconds_per_unit).map(Duration::seconds)
+}
+
+/// Compact form for totals, e.g. `1h 05m` or `12m`.
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.whole_minutes().max(0);
+
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Clock form for a running timer, e.g. `01:05:09`.
+pub fn format_clock(duration: Duration) -> String {
+    let seconds = duration.whole_seconds().max(0);
+
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
@@ -0,0 +1,589 @@
+//! Two-way CalDAV sync against a small in-process stand-in for a server.
+//!
+//! The stand-in keeps VTODO resources in memory and answers REPORT, GET, PUT
+//! and DELETE with ETags, honouring `If-Match` and `If-None-Match: *` with 412
+//! like a real server. Tests can also change resources behind the client's back.
+
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::Path,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use delibird::{
+    core::{
+        sync::caldav::{self, CaldavClient, CaldavReport, SyncState},
+        task::Task,
+    },
+    utils::config::{CaldavConfig, ConflictRule},
+};
+
+const COLLECTION: &str = "/calendars/me/tasks/";
+
+/// A request the stand-in received, with the precondition it carried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Request {
+    method: String,
+    path: String,
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct Collection {
+    /// Resources by path: their body and ETag.
+    resources: BTreeMap<String, (String, String)>,
+    next_etag: u32,
+    requests: Vec<Request>,
+    /// A body another client stores at the path just before the next PUT or
+    /// DELETE of it reaches the server.
+    race: Option<(String, String)>,
+}
+
+impl Collection {
+    fn store(&mut self, path: &str, body: String) -> String {
+        self.next_etag += 1;
+        let etag = format!("\"{}\"", self.next_etag);
+        self.resources
+            .insert(path.to_string(), (body, etag.clone()));
+        etag
+    }
+}
+
+struct Server {
+    url: String,
+    collection: Arc<Mutex<Collection>>,
+}
+
+impl Server {
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}{}", listener.local_addr().unwrap(), COLLECTION);
+        let collection = Arc::new(Mutex::new(Collection::default()));
+
+        let shared = Arc::clone(&collection);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                serve(stream, &shared);
+            }
+        });
+
+        Self { url, collection }
+    }
+
+    fn client(&self) -> CaldavClient {
+        CaldavClient::new(&CaldavConfig {
+            url: self.url.clone(),
+            username: "me".to_string(),
+            password: "secret".to_string(),
+            on_conflict: ConflictRule::Remote,
+        })
+    }
+
+    fn paths(&self) -> Vec<String> {
+        let collection = self.collection.lock().unwrap();
+        collection.resources.keys().cloned().collect()
+    }
+
+    fn body(&self, path: &str) -> Option<String> {
+        let collection = self.collection.lock().unwrap();
+        collection.resources.get(path).map(|(body, _)| body.clone())
+    }
+
+    /// Changes a resource the way another client, e.g. a phone, would.
+    fn edit(&self, path: &str, from: &str, to: &str) {
+        let mut collection = self.collection.lock().unwrap();
+        let body = collection.resources[path].0.replace(from, to);
+        collection.store(path, body);
+    }
+
+    fn remove(&self, path: &str) {
+        self.collection.lock().unwrap().resources.remove(path);
+    }
+
+    /// Makes another client change `path` right before the next write to it.
+    fn race(&self, path: &str, body: String) {
+        self.collection.lock().unwrap().race = Some((path.to_string(), body));
+    }
+
+    /// Requests since the last call, leaving out listings and downloads.
+    fn writes(&self) -> Vec<Request> {
+        let mut collection = self.collection.lock().unwrap();
+        std::mem::take(&mut collection.requests)
+            .into_iter()
+            .filter(|request| request.method == "PUT" || request.method == "DELETE")
+            .collect()
+    }
+}
+
+fn serve(stream: TcpStream, collection: &Mutex<Collection>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = BTreeMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+    let body = String::from_utf8(body).unwrap();
+
+    let request = Request {
+        method,
+        path,
+        if_match: headers.get("if-match").cloned(),
+        if_none_match: headers.get("if-none-match").cloned(),
+    };
+    let (status, etag, response_body) = respond(&request, body, &mut collection.lock().unwrap());
+
+    let mut response = format!(
+        "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status,
+        response_body.len()
+    );
+    if let Some(etag) = etag {
+        response.push_str(&format!("ETag: {}\r\n", etag));
+    }
+    response.push_str("\r\n");
+    response.push_str(&response_body);
+    let mut stream = stream;
+    let _ = stream.write_all(response.as_bytes());
+}
+
+fn respond(
+    request: &Request,
+    body: String,
+    collection: &mut Collection,
+) -> (u16, Option<String>, String) {
+    collection.requests.push(request.clone());
+    let path = request.path.as_str();
+
+    if matches!(request.method.as_str(), "PUT" | "DELETE")
+        && collection
+            .race
+            .as_ref()
+            .is_some_and(|(raced, _)| raced == path)
+        && let Some((_, body)) = collection.race.take()
+    {
+        collection.store(path, body);
+    }
+    let current = collection.resources.get(path).map(|(_, etag)| etag.clone());
+
+    match request.method.as_str() {
+        "REPORT" if path == COLLECTION => {
+            let mut xml = String::from(r#"<?xml version="1.0"?><d:multistatus xmlns:d="DAV:">"#);
+            for (href, (_, etag)) in &collection.resources {
+                xml.push_str(&format!(
+                    "<d:response><d:href>{}</d:href><d:propstat><d:prop>\
+                     <d:getetag>{}</d:getetag></d:prop>\
+                     <d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>",
+                    href,
+                    etag.replace('"', "&quot;")
+                ));
+            }
+            xml.push_str("</d:multistatus>");
+            (207, None, xml)
+        }
+        "GET" => match collection.resources.get(path) {
+            Some((body, etag)) => (200, Some(etag.clone()), body.clone()),
+            None => (404, None, String::new()),
+        },
+        "PUT" => {
+            let allowed = match (&request.if_match, &request.if_none_match) {
+                (Some(expected), _) => current.as_ref() == Some(expected),
+                (None, Some(any)) if any == "*" => current.is_none(),
+                _ => true,
+            };
+            if !allowed {
+                return (412, None, String::new());
+            }
+            let etag = collection.store(path, body);
+            (
+                if current.is_some() { 204 } else { 201 },
+                Some(etag),
+                String::new(),
+            )
+        }
+        "DELETE" => match current {
+            None => (404, None, String::new()),
+            Some(etag)
+                if request
+                    .if_match
+                    .as_ref()
+                    .is_some_and(|expected| *expected != etag) =>
+            {
+                (412, None, String::new())
+            }
+            Some(_) => {
+                collection.resources.remove(path);
+                (204, None, String::new())
+            }
+        },
+        _ => (405, None, String::new()),
+    }
+}
+
+/// One machine's copy of the tasks and what it last synced.
+struct Device {
+    tasks: Vec<Task>,
+    state: SyncState,
+    /// Tombstones left by the last sync, as they would be saved.
+    tombstones: Vec<Task>,
+}
+
+impl Device {
+    fn new(server: &Server, titles: &[&str]) -> Self {
+        Self {
+            tasks: titles
+                .iter()
+                .map(|title| Task::new(title.to_string(), Vec::new(), None))
+                .collect(),
+            state: SyncState::load(Path::new("/nonexistent/caldav.json"), &server.url).unwrap(),
+            tombstones: Vec::new(),
+        }
+    }
+
+    /// Syncs, then keeps the tasks apart from the tombstones as loading does.
+    fn sync(&mut self, server: &Server, rule: ConflictRule) -> CaldavReport {
+        let report =
+            caldav::sync(&mut self.tasks, &mut self.state, &server.client(), rule).unwrap();
+        let (tombstones, tasks) = std::mem::take(&mut self.tasks)
+            .into_iter()
+            .partition(|task| task.deleted_at.is_some());
+        self.tasks = tasks;
+        self.tombstones = tombstones;
+        report
+    }
+
+    fn task(&self, title: &str) -> &Task {
+        self.tasks.iter().find(|task| task.title == title).unwrap()
+    }
+
+    fn task_mut(&mut self, title: &str) -> &mut Task {
+        self.tasks
+            .iter_mut()
+            .find(|task| task.title == title)
+            .unwrap()
+    }
+
+    fn titles(&self) -> Vec<&str> {
+        let mut titles: Vec<&str> = self.tasks.iter().map(|task| task.title.as_str()).collect();
+        titles.sort();
+        titles
+    }
+}
+
+fn path_of(task: &Task) -> String {
+    format!("{}{}.ics", COLLECTION, task.id)
+}
+
+/// A server holding the tasks of a device that synced them first.
+fn synced(titles: &[&str]) -> (Server, Device) {
+    let server = Server::start();
+    let mut device = Device::new(&server, titles);
+    device.sync(&server, ConflictRule::Remote);
+    server.writes();
+    (server, device)
+}
+
+#[test]
+fn uploads_new_tasks_only_where_nothing_is_stored() {
+    let server = Server::start();
+    let mut device = Device::new(&server, &["Pay rent", "Call the bank"]);
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report.uploaded, 2);
+    assert_eq!(report.downloaded, 0);
+    assert!(report.conflicts.is_empty());
+    let rent = device.task("Pay rent");
+    assert!(
+        server
+            .body(&path_of(rent))
+            .unwrap()
+            .contains("SUMMARY:Pay rent")
+    );
+    for write in server.writes() {
+        assert_eq!(write.method, "PUT");
+        assert_eq!(write.if_none_match.as_deref(), Some("*"));
+        assert_eq!(write.if_match, None);
+    }
+}
+
+#[test]
+fn downloads_tasks_to_another_device() {
+    let (server, first) = synced(&["Pay rent", "Call the bank"]);
+    let mut second = Device::new(&server, &[]);
+
+    let report = second.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report.downloaded, 2);
+    assert_eq!(report.uploaded, 0);
+    assert_eq!(second.titles(), first.titles());
+    assert_eq!(second.task("Pay rent").id, first.task("Pay rent").id);
+    assert!(server.writes().is_empty());
+}
+
+#[test]
+fn a_second_sync_without_changes_does_nothing() {
+    let (server, mut device) = synced(&["Pay rent"]);
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report, CaldavReport::default());
+    assert!(server.writes().is_empty());
+}
+
+#[test]
+fn uploads_an_edit_over_the_version_last_seen() {
+    let (server, mut device) = synced(&["Pay rent"]);
+    let path = path_of(device.task("Pay rent"));
+    let etag = server.collection.lock().unwrap().resources[&path].1.clone();
+    device.task_mut("Pay rent").title = "Pay rent early".to_string();
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report.uploaded, 1);
+    assert!(
+        server
+            .body(&path)
+            .unwrap()
+            .contains("SUMMARY:Pay rent early")
+    );
+    let writes = server.writes();
+    assert_eq!(writes.len(), 1);
+    assert_eq!(writes[0].if_match, Some(etag));
+    assert_eq!(writes[0].if_none_match, None);
+}
+
+#[test]
+fn downloads_an_edit_made_on_the_server() {
+    let (server, mut device) = synced(&["Pay rent"]);
+    let path = path_of(device.task("Pay rent"));
+    server.edit(&path, "SUMMARY:Pay rent", "SUMMARY:Pay the landlord");
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report.downloaded, 1);
+    assert_eq!(report.uploaded, 0);
+    assert_eq!(device.titles(), vec!["Pay the landlord"]);
+    assert!(server.writes().is_empty());
+}
+
+#[test]
+fn edits_on_both_sides_keep_the_servers_under_the_remote_rule() {
+    let (server, mut device) = synced(&["Pay rent"]);
+    let path = path_of(device.task("Pay rent"));
+    server.edit(&path, "SUMMARY:Pay rent", "SUMMARY:From the phone");
+    device.task_mut("Pay rent").title = "From the laptop".to_string();
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report.downloaded, 1);
+    assert_eq!(report.uploaded, 0);
+    assert_eq!(report.conflicts.len(), 1);
+    assert!(report.conflicts[0].contains("kept the server's"));
+    assert_eq!(device.titles(), vec!["From the phone"]);
+    assert!(
+        server
+            .body(&path)
+            .unwrap()
+            .contains("SUMMARY:From the phone")
+    );
+    assert!(server.writes().is_empty());
+}
+
+#[test]
+fn edits_on_both_sides_keep_this_machines_under_the_local_rule() {
+    let (server, mut device) = synced(&["Pay rent"]);
+    let path = path_of(device.task("Pay rent"));
+    server.edit(&path, "SUMMARY:Pay rent", "SUMMARY:From the phone");
+    let etag = server.collection.lock().unwrap().resources[&path].1.clone();
+    device.task_mut("Pay rent").title = "From the laptop".to_string();
+
+    let report = device.sync(&server, ConflictRule::Local);
+
+    assert_eq!(report.uploaded, 1);
+    assert_eq!(report.downloaded, 0);
+    assert_eq!(report.conflicts.len(), 1);
+    assert!(report.conflicts[0].contains("kept this machine's"));
+    assert_eq!(device.titles(), vec!["From the laptop"]);
+    assert!(
+        server
+            .body(&path)
+            .unwrap()
+            .contains("SUMMARY:From the laptop")
+    );
+    // Uploaded over the server's version it just saw
+    assert_eq!(server.writes()[0].if_match, Some(etag));
+}
+
+#[test]
+fn a_task_deleted_here_is_deleted_on_the_server() {
+    let (server, mut device) = synced(&["Pay rent", "Call the bank"]);
+    let path = path_of(device.task("Pay rent"));
+    let etag = server.collection.lock().unwrap().resources[&path].1.clone();
+    device.tasks.retain(|task| task.title != "Pay rent");
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report.deleted_on_server, 1);
+    assert_eq!(server.paths(), vec![path_of(device.task("Call the bank"))]);
+    let writes = server.writes();
+    assert_eq!(writes.len(), 1);
+    assert_eq!(writes[0].method, "DELETE");
+    assert_eq!(writes[0].if_match, Some(etag));
+}
+
+#[test]
+fn a_task_deleted_on_the_server_leaves_a_tombstone_here() {
+    let (server, mut device) = synced(&["Pay rent", "Call the bank"]);
+    let id = device.task("Pay rent").id;
+    server.remove(&path_of(device.task("Pay rent")));
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report.deleted_here, 1);
+    assert_eq!(device.titles(), vec!["Call the bank"]);
+    assert_eq!(device.tombstones.len(), 1);
+    assert_eq!(device.tombstones[0].id, id);
+    assert!(server.writes().is_empty());
+
+    // The next sync has nothing left to do
+    assert_eq!(
+        device.sync(&server, ConflictRule::Remote),
+        CaldavReport::default()
+    );
+}
+
+#[test]
+fn a_task_deleted_on_the_server_but_edited_here_is_uploaded_again() {
+    let (server, mut device) = synced(&["Pay rent"]);
+    let path = path_of(device.task("Pay rent"));
+    server.remove(&path);
+    device.task_mut("Pay rent").title = "Pay rent early".to_string();
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report.uploaded, 1);
+    assert_eq!(report.deleted_here, 0);
+    assert_eq!(report.conflicts.len(), 1);
+    assert!(
+        server
+            .body(&path)
+            .unwrap()
+            .contains("SUMMARY:Pay rent early")
+    );
+    let writes = server.writes();
+    assert_eq!(writes[0].if_none_match.as_deref(), Some("*"));
+    assert_eq!(writes[0].if_match, None);
+}
+
+#[test]
+fn a_task_deleted_here_but_edited_on_the_server_is_restored() {
+    let (server, mut device) = synced(&["Pay rent"]);
+    let path = path_of(device.task("Pay rent"));
+    server.edit(&path, "SUMMARY:Pay rent", "SUMMARY:Pay rent early");
+    device.tasks.clear();
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report.downloaded, 1);
+    assert_eq!(report.deleted_on_server, 0);
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(device.titles(), vec!["Pay rent early"]);
+    assert!(server.writes().is_empty());
+}
+
+#[test]
+fn an_upload_racing_another_client_is_left_for_the_next_sync() {
+    let (server, mut device) = synced(&["Pay rent"]);
+    let path = path_of(device.task("Pay rent"));
+    let phone = server
+        .body(&path)
+        .unwrap()
+        .replace("SUMMARY:Pay rent", "SUMMARY:From the phone");
+    server.race(&path, phone);
+    device.task_mut("Pay rent").title = "From the laptop".to_string();
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    // The server answered 412 and kept the other client's version
+    assert_eq!(report.uploaded, 0);
+    assert_eq!(report.conflicts.len(), 1);
+    assert!(report.conflicts[0].contains("changed on the server during the sync"));
+    assert!(
+        server
+            .body(&path)
+            .unwrap()
+            .contains("SUMMARY:From the phone")
+    );
+
+    let report = device.sync(&server, ConflictRule::Remote);
+    assert_eq!(report.downloaded, 1);
+    assert_eq!(device.titles(), vec!["From the phone"]);
+}
+
+#[test]
+fn a_new_task_racing_another_client_is_not_overwritten() {
+    let server = Server::start();
+    let mut device = Device::new(&server, &["Pay rent"]);
+    let path = path_of(device.task("Pay rent"));
+    server.race(&path, "BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n".to_string());
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report.uploaded, 0);
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(
+        server.body(&path).as_deref(),
+        Some("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n")
+    );
+}
+
+#[test]
+fn a_delete_racing_another_client_keeps_the_servers_version() {
+    let (server, mut device) = synced(&["Pay rent"]);
+    let path = path_of(device.task("Pay rent"));
+    let phone = server
+        .body(&path)
+        .unwrap()
+        .replace("SUMMARY:Pay rent", "SUMMARY:From the phone");
+    server.race(&path, phone);
+    device.tasks.clear();
+
+    let report = device.sync(&server, ConflictRule::Remote);
+
+    assert_eq!(report.deleted_on_server, 0);
+    assert!(
+        server
+            .body(&path)
+            .unwrap()
+            .contains("SUMMARY:From the phone")
+    );
+
+    // The task comes back with the other client's change
+    let report = device.sync(&server, ConflictRule::Remote);
+    assert_eq!(report.downloaded, 1);
+    assert_eq!(device.titles(), vec!["From the phone"]);
+}